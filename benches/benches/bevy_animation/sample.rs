@@ -0,0 +1,39 @@
+use bevy_animation::{Keyframes, VariableCurve};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use glam::Vec3;
+
+criterion_group!(benches, sample_baked_curve);
+criterion_main!(benches);
+
+// Baked at 30 frames per second, from one second to a minute
+const FRAME_COUNTS: [usize; 3] = [30, 300, 1800];
+
+fn baked_curve(frame_count: usize) -> VariableCurve {
+    let timestamps: Vec<f32> = (0..frame_count).map(|frame| frame as f32 / 30.0).collect();
+    let translations = timestamps.iter().map(|&time| Vec3::X * time).collect();
+    VariableCurve::new(timestamps, Keyframes::Translation(translations))
+}
+
+fn sample_baked_curve(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sample_baked_curve");
+    for frame_count in FRAME_COUNTS {
+        let variable = baked_curve(frame_count);
+        let mut uniform = variable.clone();
+        uniform.detect_uniform_step(1e-5);
+        let duration = (frame_count - 1) as f32 / 30.0;
+        // Times spread over the curve, to not benefit from the branch predictor
+        let times: Vec<f32> = (0..256)
+            .map(|index| (index * 97 % 256) as f32 / 255.0 * duration)
+            .collect();
+        for (name, curve) in [("variable", &variable), ("uniform", &uniform)] {
+            group.bench_with_input(BenchmarkId::new(name, frame_count), curve, |b, curve| {
+                b.iter(|| {
+                    for &time in &times {
+                        black_box(curve.sample_translation(black_box(time)));
+                    }
+                });
+            });
+        }
+    }
+    group.finish();
+}
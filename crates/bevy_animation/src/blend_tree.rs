@@ -0,0 +1,395 @@
+use std::sync::Arc;
+
+use bevy_asset::{Assets, Handle};
+use bevy_math::{Quat, Vec2, Vec3};
+use bevy_transform::prelude::Transform;
+use bevy_utils::HashMap;
+
+use crate::{
+    average_rotations, blend, blend_clips, AnimationClip, AnimationSource, CurveKind, EntityPath,
+    Keyframes,
+};
+
+/// A node of a [`BlendTree`]
+///
+/// Nodes are evaluated to the pose of each animated entity. The parameters of the nodes are named
+/// parameters of the tree, a parameter that was never set being `0.0`.
+#[derive(Clone, Debug)]
+pub enum BlendNode {
+    /// Pose of a clip, looping over its duration.
+    Clip(Handle<AnimationClip>),
+    /// Blend between the two children whose thresholds surround the value of `parameter`.
+    ///
+    /// Below the lowest threshold or above the highest one, the pose of the closest child is
+    /// used.
+    Blend1D {
+        /// Name of the parameter choosing the children to blend.
+        parameter: String,
+        /// Children with their threshold, in any order.
+        children: Vec<(f32, BlendNode)>,
+    },
+    /// Blend between children placed on a plane, weighted by the inverse of their squared
+    /// distance to the point of the two `parameters`.
+    ///
+    /// A child exactly on the point has the full weight.
+    Blend2D {
+        /// Names of the parameters for the `x` and `y` coordinates of the point.
+        parameters: (String, String),
+        /// Children with their position on the plane.
+        children: Vec<(Vec2, BlendNode)>,
+    },
+    /// Pose of `base`, with the pose of `additive` added on top, relative to its pose at
+    /// `0.0`, as with [`BlendMode::Additive`](crate::BlendMode::Additive).
+    Additive {
+        /// Node the additive pose is added to.
+        base: Box<BlendNode>,
+        /// Node whose difference with its first frame is added.
+        additive: Box<BlendNode>,
+        /// Name of the parameter for the weight of `additive`.
+        weight: String,
+    },
+    /// Pose of `base`, blended toward the pose of `layer`, as with
+    /// [`BlendMode::Replace`](crate::BlendMode::Replace).
+    Layer {
+        /// Node the layer is blended over.
+        base: Box<BlendNode>,
+        /// Node blended over `base`.
+        layer: Box<BlendNode>,
+        /// Name of the parameter for the weight of `layer`.
+        weight: String,
+    },
+    /// Pose of an [`AnimationSource`], such as a [`ProceduralClip`](crate::ProceduralClip),
+    /// sampled at the time of the tree.
+    ///
+    /// Unlike clips, sources don't loop: they are sampled at the time the tree has been playing
+    /// for. Attributes a source doesn't animate are left to identity.
+    Source(Arc<dyn AnimationSource>),
+}
+
+impl BlendNode {
+    /// Node playing the clip of `handle`
+    pub fn clip(handle: Handle<AnimationClip>) -> Self {
+        BlendNode::Clip(handle)
+    }
+
+    /// Node sampling `source`, see [`BlendNode::Source`]
+    pub fn source(source: impl AnimationSource) -> Self {
+        BlendNode::Source(Arc::new(source))
+    }
+
+    /// Node blending `children` along `parameter`, see [`BlendNode::Blend1D`]
+    pub fn blend_1d(
+        parameter: impl Into<String>,
+        children: impl IntoIterator<Item = (f32, BlendNode)>,
+    ) -> Self {
+        BlendNode::Blend1D {
+            parameter: parameter.into(),
+            children: children.into_iter().collect(),
+        }
+    }
+
+    /// Node blending `children` on the plane of the parameters `x` and `y`, see
+    /// [`BlendNode::Blend2D`]
+    pub fn blend_2d(
+        x: impl Into<String>,
+        y: impl Into<String>,
+        children: impl IntoIterator<Item = (Vec2, BlendNode)>,
+    ) -> Self {
+        BlendNode::Blend2D {
+            parameters: (x.into(), y.into()),
+            children: children.into_iter().collect(),
+        }
+    }
+
+    /// Node adding `additive` over `base` with the weight `weight`, see [`BlendNode::Additive`]
+    pub fn additive(base: BlendNode, additive: BlendNode, weight: impl Into<String>) -> Self {
+        BlendNode::Additive {
+            base: Box::new(base),
+            additive: Box::new(additive),
+            weight: weight.into(),
+        }
+    }
+
+    /// Node blending `layer` over `base` with the weight `weight`, see [`BlendNode::Layer`]
+    pub fn layer(base: BlendNode, layer: BlendNode, weight: impl Into<String>) -> Self {
+        BlendNode::Layer {
+            base: Box::new(base),
+            layer: Box::new(layer),
+            weight: weight.into(),
+        }
+    }
+
+    /// Add the clips and sources of this node and its children to `leaves`
+    fn leaves<'a>(&'a self, leaves: &mut Vec<&'a BlendNode>) {
+        match self {
+            BlendNode::Clip(_) | BlendNode::Source(_) => leaves.push(self),
+            BlendNode::Blend1D { children, .. } => {
+                for (_, child) in children {
+                    child.leaves(leaves);
+                }
+            }
+            BlendNode::Blend2D { children, .. } => {
+                for (_, child) in children {
+                    child.leaves(leaves);
+                }
+            }
+            BlendNode::Additive { base, additive, .. } => {
+                base.leaves(leaves);
+                additive.leaves(leaves);
+            }
+            BlendNode::Layer { base, layer, .. } => {
+                base.leaves(leaves);
+                layer.leaves(leaves);
+            }
+        }
+    }
+}
+
+/// Blend tree, combining clips into a single pose driven by named parameters
+///
+/// The tree is declared once from [`BlendNode`]s, then its parameters are set every frame, for
+/// example from the velocity of a character. It can be evaluated directly with
+/// [`evaluate`](BlendTree::evaluate), or played by an [`AnimationPlayer`](crate::AnimationPlayer)
+/// with [`AnimationPlayer::play_blend_tree`](crate::AnimationPlayer::play_blend_tree).
+///
+/// ```
+/// # use bevy_animation::{AnimationClip, BlendNode, BlendTree};
+/// # use bevy_asset::Handle;
+/// # let (idle, walk, run, wave) = (
+/// #     Handle::<AnimationClip>::default(),
+/// #     Handle::default(),
+/// #     Handle::default(),
+/// #     Handle::default(),
+/// # );
+/// let locomotion = BlendNode::blend_1d(
+///     "speed",
+///     [
+///         (0.0, BlendNode::clip(idle)),
+///         (1.5, BlendNode::clip(walk)),
+///         (5.0, BlendNode::clip(run)),
+///     ],
+/// );
+/// let mut tree = BlendTree::new(BlendNode::layer(
+///     locomotion,
+///     BlendNode::clip(wave),
+///     "waving",
+/// ));
+/// tree.set_parameter("speed", 3.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct BlendTree {
+    root: BlendNode,
+    parameters: HashMap<String, f32>,
+}
+
+impl BlendTree {
+    /// Create a tree evaluating `root`, with all its parameters at `0.0`
+    pub fn new(root: BlendNode) -> Self {
+        Self {
+            root,
+            parameters: HashMap::default(),
+        }
+    }
+
+    /// Root node of the tree
+    pub fn root(&self) -> &BlendNode {
+        &self.root
+    }
+
+    /// Value of the parameter `name`, `0.0` if it was never set
+    pub fn parameter(&self, name: &str) -> f32 {
+        self.parameters.get(name).copied().unwrap_or(0.0)
+    }
+
+    /// Set the value of the parameter `name`
+    pub fn set_parameter(&mut self, name: impl Into<String>, value: f32) -> &mut Self {
+        self.parameters.insert(name.into(), value);
+        self
+    }
+
+    /// Paths animated by the clips and sources of the tree, without duplicates
+    ///
+    /// Clips that are not loaded are ignored.
+    pub fn paths<'a>(&'a self, animations: &'a Assets<AnimationClip>) -> Vec<&'a EntityPath> {
+        let mut leaves = Vec::new();
+        self.root.leaves(&mut leaves);
+        let mut paths: Vec<&EntityPath> = Vec::new();
+        for leaf in leaves {
+            let leaf_paths = match leaf {
+                BlendNode::Clip(handle) => match animations.get(handle) {
+                    Some(animation_clip) => animation_clip.paths.as_slice(),
+                    None => continue,
+                },
+                BlendNode::Source(source) => source.animated_paths(),
+                _ => continue,
+            };
+            for path in leaf_paths {
+                if !paths.contains(&path) {
+                    paths.push(path);
+                }
+            }
+        }
+        paths
+    }
+
+    /// Pose of the entity at `path` at `time`
+    ///
+    /// Each clip loops over its own duration. Children of a node that don't animate `path` are
+    /// left out of its blend. Returns `None` if no clip with a weight animates `path`.
+    pub fn evaluate(
+        &self,
+        time: f32,
+        animations: &Assets<AnimationClip>,
+        path: &EntityPath,
+    ) -> Option<Transform> {
+        self.evaluate_node(&self.root, time, animations, path)
+    }
+
+    fn evaluate_node(
+        &self,
+        node: &BlendNode,
+        time: f32,
+        animations: &Assets<AnimationClip>,
+        path: &EntityPath,
+    ) -> Option<Transform> {
+        match node {
+            BlendNode::Clip(handle) => {
+                let animation_clip = animations.get(handle)?;
+                let time = if animation_clip.duration > 0.0 {
+                    time.rem_euclid(animation_clip.duration)
+                } else {
+                    0.0
+                };
+                blend_clips(&[(animation_clip, 1.0)], time, path)
+            }
+            BlendNode::Source(source) => pose_from_keyframe(&source.sample(time, path)?),
+            BlendNode::Blend1D {
+                parameter,
+                children,
+            } => {
+                let value = self.parameter(parameter);
+                let below = children
+                    .iter()
+                    .filter(|(threshold, _)| *threshold <= value)
+                    .max_by(|(a, _), (b, _)| a.total_cmp(b));
+                let above = children
+                    .iter()
+                    .filter(|(threshold, _)| *threshold >= value)
+                    .min_by(|(a, _), (b, _)| a.total_cmp(b));
+                let weighted = match (below, above) {
+                    (Some((low, below)), Some((high, above))) if high > low => {
+                        let lerp = (value - low) / (high - low);
+                        vec![(below, 1.0 - lerp), (above, lerp)]
+                    }
+                    (Some((_, child)), _) | (None, Some((_, child))) => vec![(child, 1.0)],
+                    (None, None) => return None,
+                };
+                self.blend_children(&weighted, time, animations, path)
+            }
+            BlendNode::Blend2D {
+                parameters,
+                children,
+            } => {
+                let point = Vec2::new(self.parameter(&parameters.0), self.parameter(&parameters.1));
+                let weighted: Vec<_> = match children
+                    .iter()
+                    .find(|(position, _)| *position == point)
+                {
+                    Some((_, child)) => vec![(child, 1.0)],
+                    None => children
+                        .iter()
+                        .map(|(position, child)| (child, 1.0 / position.distance_squared(point)))
+                        .collect(),
+                };
+                self.blend_children(&weighted, time, animations, path)
+            }
+            BlendNode::Additive {
+                base,
+                additive,
+                weight,
+            } => {
+                let base = self.evaluate_node(base, time, animations, path);
+                let weight = self.parameter(weight);
+                let delta = match (
+                    self.evaluate_node(additive, time, animations, path),
+                    self.evaluate_node(additive, 0.0, animations, path),
+                ) {
+                    (Some(pose), Some(reference)) if weight != 0.0 => (pose, reference),
+                    _ => return base,
+                };
+                let mut pose = base.unwrap_or(Transform::IDENTITY);
+                let (additive, reference) = delta;
+                pose.translation += (additive.translation - reference.translation) * weight;
+                pose.rotation *=
+                    Quat::IDENTITY.slerp(reference.rotation.inverse() * additive.rotation, weight);
+                pose.scale *= Vec3::ONE.lerp(additive.scale / reference.scale, weight);
+                Some(pose)
+            }
+            BlendNode::Layer {
+                base,
+                layer,
+                weight,
+            } => {
+                let base = self.evaluate_node(base, time, animations, path);
+                let weight = self.parameter(weight).clamp(0.0, 1.0);
+                let layer = match self.evaluate_node(layer, time, animations, path) {
+                    Some(layer) if weight > 0.0 => layer,
+                    _ => return base,
+                };
+                let mut pose = base.unwrap_or(layer);
+                blend(&mut pose.translation, layer.translation, weight, Vec3::lerp);
+                blend(&mut pose.rotation, layer.rotation, weight, Quat::slerp);
+                blend(&mut pose.scale, layer.scale, weight, Vec3::lerp);
+                Some(pose)
+            }
+        }
+    }
+
+    /// Weighted average of the poses of `children`, renormalizing the weights of the children
+    /// animating `path`
+    fn blend_children(
+        &self,
+        children: &[(&BlendNode, f32)],
+        time: f32,
+        animations: &Assets<AnimationClip>,
+        path: &EntityPath,
+    ) -> Option<Transform> {
+        let poses: Vec<_> = children
+            .iter()
+            .filter(|(_, weight)| *weight > 0.0)
+            .filter_map(|&(child, weight)| {
+                Some((self.evaluate_node(child, time, animations, path)?, weight))
+            })
+            .collect();
+        let total: f32 = poses.iter().map(|(_, weight)| weight).sum();
+        if total <= 0.0 {
+            return None;
+        }
+        let rotations: Vec<_> = poses
+            .iter()
+            .map(|(pose, weight)| (pose.rotation, *weight))
+            .collect();
+        Some(Transform {
+            translation: poses.iter().fold(Vec3::ZERO, |sum, (pose, weight)| {
+                sum + pose.translation * *weight
+            }) / total,
+            rotation: average_rotations(&rotations)?,
+            scale: poses
+                .iter()
+                .fold(Vec3::ZERO, |sum, (pose, weight)| sum + pose.scale * *weight)
+                / total,
+        })
+    }
+}
+
+/// Pose of the first keyframe of `keyframes`, attributes it doesn't animate being left to
+/// identity
+fn pose_from_keyframe(keyframes: &Keyframes) -> Option<Transform> {
+    match keyframes.kind() {
+        CurveKind::Rotation => Some(Transform::from_rotation(keyframes.rotation(0)?)),
+        CurveKind::Translation => Some(Transform::from_translation(keyframes.translation(0)?)),
+        CurveKind::Scale => Some(Transform::from_scale(keyframes.scale(0)?)),
+        CurveKind::Trs => keyframes.trs(0),
+        CurveKind::Scalar | CurveKind::IkGoal | CurveKind::SpriteIndex => None,
+    }
+}
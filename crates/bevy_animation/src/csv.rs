@@ -0,0 +1,185 @@
+use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy_core::Name;
+use bevy_math::{Quat, Vec3};
+use bevy_utils::BoxedFuture;
+use thiserror::Error;
+
+use crate::{AnimationClip, CurveKind, EntityPath, Keyframes, VariableCurve};
+
+impl AnimationClip {
+    /// Parse a clip from the text format of the [`AnimationCsvLoader`]
+    pub fn from_csv(text: &str) -> Result<AnimationClip, AnimationCsvError> {
+        // Path, kind, timestamps and components of the keyframes of each curve, in the order
+        // they first appear
+        let mut curves: Vec<(EntityPath, CurveKind, Vec<f32>, Vec<f32>)> = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let columns: Vec<&str> = line.split(',').map(str::trim).collect();
+            let (path, kind, time, values) = match columns.as_slice() {
+                [path, kind, time, values @ ..] if !values.is_empty() => (path, kind, time, values),
+                _ => return Err(AnimationCsvError::MissingColumns { line: line_number }),
+            };
+            let (kind, expected) = match *kind {
+                "rotation" => (CurveKind::Rotation, 4),
+                "translation" => (CurveKind::Translation, 3),
+                "scale" => (CurveKind::Scale, 3),
+                "scalar" => (CurveKind::Scalar, 1),
+                _ => {
+                    return Err(AnimationCsvError::UnknownKind {
+                        line: line_number,
+                        kind: kind.to_string(),
+                    })
+                }
+            };
+            let number = |value: &&str| {
+                value
+                    .parse::<f32>()
+                    .map_err(|_| AnimationCsvError::InvalidNumber {
+                        line: line_number,
+                        value: value.to_string(),
+                    })
+            };
+            let time = number(time)?;
+            let values = values.iter().map(number).collect::<Result<Vec<_>, _>>()?;
+            if values.len() != expected {
+                return Err(AnimationCsvError::WrongValueCount {
+                    line: line_number,
+                    kind,
+                    expected,
+                    found: values.len(),
+                });
+            }
+            let path = EntityPath {
+                parts: path
+                    .split('/')
+                    .map(|name| Name::new(name.to_string()))
+                    .collect(),
+            };
+            match curves
+                .iter_mut()
+                .find(|(curve_path, curve_kind, ..)| *curve_path == path && *curve_kind == kind)
+            {
+                Some((_, _, timestamps, keyframes)) => {
+                    if matches!(timestamps.last(), Some(&last) if time < last) {
+                        return Err(AnimationCsvError::UnsortedTime {
+                            line: line_number,
+                            time,
+                        });
+                    }
+                    timestamps.push(time);
+                    keyframes.extend(values);
+                }
+                None => curves.push((path, kind, vec![time], values)),
+            }
+        }
+        let mut animation_clip = AnimationClip::default();
+        for (path, kind, timestamps, values) in curves {
+            let keyframes = match kind {
+                CurveKind::Rotation => Keyframes::Rotation(
+                    values
+                        .chunks_exact(4)
+                        .map(|rotation| Quat::from_slice(rotation).normalize())
+                        .collect(),
+                ),
+                CurveKind::Translation => {
+                    Keyframes::Translation(values.chunks_exact(3).map(Vec3::from_slice).collect())
+                }
+                CurveKind::Scale => {
+                    Keyframes::Scale(values.chunks_exact(3).map(Vec3::from_slice).collect())
+                }
+                _ => Keyframes::Scalar(values),
+            };
+            animation_clip.add_curve_to_path(path, VariableCurve::new(timestamps, keyframes));
+        }
+        Ok(animation_clip)
+    }
+}
+
+/// Loads [`AnimationClip`]s from `.anim.csv` files, a text format to write clips by hand
+///
+/// Each line is a keyframe, with comma-separated columns: the path of the animated entity, as
+/// names separated by `/`, the kind of the curve, the time of the keyframe, and its values.
+/// Kinds are `translation` and `scale` with three values, `rotation` with the four values of a
+/// quaternion as `x, y, z, w`, and `scalar` with one value. The keyframes of a curve are the lines
+/// with the same path and kind, which should be in increasing time. Empty lines and lines
+/// starting with `#` are ignored.
+///
+/// ```text
+/// # path, kind, time, values
+/// body/arm, rotation, 0.0, 0.0, 0.0, 0.0, 1.0
+/// body/arm, rotation, 1.0, 0.0, 0.7071, 0.0, 0.7071
+/// body, translation, 0.0, 0.0, 0.0, 0.0
+/// ```
+#[derive(Default)]
+pub struct AnimationCsvLoader;
+
+impl AssetLoader for AnimationCsvLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy_asset::Error>> {
+        Box::pin(async move {
+            let animation_clip = AnimationClip::from_csv(std::str::from_utf8(bytes)?)?;
+            load_context.set_default_asset(LoadedAsset::new(animation_clip));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["anim.csv"]
+    }
+}
+
+/// A problem found while parsing a clip with [`AnimationClip::from_csv`]
+///
+/// `line` is the number of the offending line, starting at `1`.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum AnimationCsvError {
+    /// The line doesn't have a path, a kind, a time and at least one value.
+    #[error("line {line}: expected a path, a kind, a time and values")]
+    MissingColumns {
+        /// Number of the line.
+        line: usize,
+    },
+    /// The kind of curve is not known.
+    #[error("line {line}: unknown curve kind {kind:?}")]
+    UnknownKind {
+        /// Number of the line.
+        line: usize,
+        /// The unknown kind.
+        kind: String,
+    },
+    /// The time or a value is not a number.
+    #[error("line {line}: {value:?} is not a number")]
+    InvalidNumber {
+        /// Number of the line.
+        line: usize,
+        /// The invalid column.
+        value: String,
+    },
+    /// The number of values doesn't match the kind of curve.
+    #[error("line {line}: {kind:?} keyframes have {expected} values, found {found}")]
+    WrongValueCount {
+        /// Number of the line.
+        line: usize,
+        /// Kind of the curve.
+        kind: CurveKind,
+        /// Number of values of this kind of curve.
+        expected: usize,
+        /// Number of values on the line.
+        found: usize,
+    },
+    /// The time is before the previous keyframe of the same curve.
+    #[error("line {line}: time {time} is before the previous keyframe of the curve")]
+    UnsortedTime {
+        /// Number of the line.
+        line: usize,
+        /// Time of the keyframe.
+        time: f32,
+    },
+}
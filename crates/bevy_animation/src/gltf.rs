@@ -0,0 +1,244 @@
+use bevy_math::{Quat, Vec3};
+
+use crate::{AnimationClip, EntityPath, Interpolation, Keyframes, VariableCurve};
+
+impl AnimationClip {
+    /// Extract the keyframes of the clip as glTF animation channels, to save a clip edited at
+    /// runtime
+    ///
+    /// [Rotation](Keyframes::Rotation), [translation](Keyframes::Translation) and
+    /// [scale](Keyframes::Scale) curves, quantized or not, become one channel each, and
+    /// [TRS](Keyframes::Trs) curves three. [Scalar](Keyframes::Scalar) curves become
+    /// [morph target weights](GltfProperty::MorphTargetWeights) with one target.
+    /// [Inverse kinematics goals](Keyframes::IkGoal) have no glTF counterpart and are skipped, as
+    /// are the settings of the curves other than their [`Interpolation`].
+    pub fn to_gltf_channels(&self) -> Vec<GltfChannel> {
+        let mut channels = Vec::new();
+        for path in &self.paths {
+            for curve in &self.curves[path] {
+                let timestamps = &curve.keyframe_timestamps;
+                let mut properties = Vec::new();
+                match curve.keyframes.dequantize() {
+                    Keyframes::Rotation(rotations) => {
+                        properties.push((GltfProperty::Rotation, continuous_rotations(&rotations)));
+                    }
+                    Keyframes::Translation(translations) => {
+                        properties.push((GltfProperty::Translation, flatten_vec3s(&translations)));
+                    }
+                    Keyframes::Scale(scales) => {
+                        properties.push((GltfProperty::Scale, flatten_vec3s(&scales)));
+                    }
+                    Keyframes::Scalar(weights) => {
+                        properties.push((GltfProperty::MorphTargetWeights, weights));
+                    }
+                    Keyframes::Trs(transforms) => {
+                        let rotations: Vec<_> = transforms.iter().map(|t| t.rotation).collect();
+                        let translations: Vec<_> =
+                            transforms.iter().map(|t| t.translation).collect();
+                        let scales: Vec<_> = transforms.iter().map(|t| t.scale).collect();
+                        properties.push((GltfProperty::Translation, flatten_vec3s(&translations)));
+                        properties.push((GltfProperty::Rotation, continuous_rotations(&rotations)));
+                        properties.push((GltfProperty::Scale, flatten_vec3s(&scales)));
+                    }
+                    _ => continue,
+                }
+                for (property, values) in properties {
+                    let (interpolation, output) = match curve.interpolation {
+                        Interpolation::Linear => (GltfInterpolation::Linear, values),
+                        Interpolation::Step => (GltfInterpolation::Step, values),
+                        Interpolation::Cubic => (
+                            GltfInterpolation::CubicSpline,
+                            cubic_spline_output(timestamps, &values),
+                        ),
+                    };
+                    channels.push(GltfChannel {
+                        path: path.clone(),
+                        property,
+                        interpolation,
+                        input: timestamps.clone(),
+                        output,
+                    });
+                }
+            }
+        }
+        channels
+    }
+
+    /// Build a clip from glTF animation channels, the counterpart of
+    /// [`AnimationClip::to_gltf_channels`]
+    ///
+    /// [Cubic spline](GltfInterpolation::CubicSpline) channels are interpolated with
+    /// [`Interpolation::Cubic`], whose tangents are computed from the keyframes, so the tangents
+    /// of the channel are ignored. A channel of [morph target
+    /// weights](GltfProperty::MorphTargetWeights) becomes one [scalar](Keyframes::Scalar) curve
+    /// per target.
+    pub fn from_gltf_channels(channels: &[GltfChannel]) -> AnimationClip {
+        let mut animation_clip = AnimationClip::default();
+        for channel in channels {
+            let keyframe_count = channel.input.len();
+            if keyframe_count == 0 {
+                continue;
+            }
+            let values: Vec<f32> = match channel.interpolation {
+                GltfInterpolation::CubicSpline => {
+                    // Each keyframe is an in-tangent, a value and an out-tangent
+                    let stride = channel.output.len() / keyframe_count;
+                    let components = stride / 3;
+                    channel
+                        .output
+                        .chunks_exact(stride.max(1))
+                        .flat_map(|keyframe| &keyframe[components..2 * components])
+                        .copied()
+                        .collect()
+                }
+                _ => channel.output.clone(),
+            };
+            let interpolation = match channel.interpolation {
+                GltfInterpolation::Linear => Interpolation::Linear,
+                GltfInterpolation::Step => Interpolation::Step,
+                GltfInterpolation::CubicSpline => Interpolation::Cubic,
+            };
+            let all_keyframes = match channel.property {
+                GltfProperty::Rotation => vec![Keyframes::Rotation(
+                    values
+                        .chunks_exact(4)
+                        .map(|rotation| Quat::from_slice(rotation).normalize())
+                        .collect(),
+                )],
+                GltfProperty::Translation => vec![Keyframes::Translation(
+                    values.chunks_exact(3).map(Vec3::from_slice).collect(),
+                )],
+                GltfProperty::Scale => vec![Keyframes::Scale(
+                    values.chunks_exact(3).map(Vec3::from_slice).collect(),
+                )],
+                GltfProperty::MorphTargetWeights => {
+                    let targets = values.len() / keyframe_count;
+                    (0..targets)
+                        .map(|target| {
+                            Keyframes::Scalar(
+                                values
+                                    .iter()
+                                    .skip(target)
+                                    .step_by(targets)
+                                    .copied()
+                                    .collect(),
+                            )
+                        })
+                        .collect()
+                }
+            };
+            for keyframes in all_keyframes {
+                let mut curve = VariableCurve::new(channel.input.clone(), keyframes);
+                curve.interpolation = interpolation;
+                animation_clip.add_curve_to_path(channel.path.clone(), curve);
+            }
+        }
+        animation_clip
+    }
+}
+
+/// Components of `values`, one after the other
+fn flatten_vec3s(values: &[Vec3]) -> Vec<f32> {
+    values.iter().flat_map(|value| value.to_array()).collect()
+}
+
+/// Components of `rotations`, flipped to stay in the same hemisphere as the previous rotation
+///
+/// glTF interpolates the components of the quaternions of cubic splines, which would go the long
+/// way around between `q` and `-q`.
+fn continuous_rotations(rotations: &[Quat]) -> Vec<f32> {
+    let mut previous = Quat::IDENTITY;
+    let mut components = Vec::with_capacity(rotations.len() * 4);
+    for &rotation in rotations {
+        let rotation = if previous.dot(rotation) < 0.0 {
+            -rotation
+        } else {
+            rotation
+        };
+        components.extend(rotation.to_array());
+        previous = rotation;
+    }
+    components
+}
+
+/// Output of a glTF cubic spline going through `values`, with the tangents of the Catmull-Rom
+/// spline of [`Interpolation::Cubic`]
+///
+/// The tangents of glTF are per second, so they are divided by the time to the neighbouring
+/// keyframe.
+fn cubic_spline_output(timestamps: &[f32], values: &[f32]) -> Vec<f32> {
+    let keyframe_count = timestamps.len();
+    if keyframe_count == 0 {
+        return Vec::new();
+    }
+    let components = values.len() / keyframe_count;
+    let value = |index: usize| &values[index * components..(index + 1) * components];
+    let mut output = Vec::with_capacity(values.len() * 3);
+    for index in 0..keyframe_count {
+        let previous = value(index.saturating_sub(1));
+        let next = value((index + 1).min(keyframe_count - 1));
+        let tangent = |duration: f32| {
+            previous.iter().zip(next).map(move |(previous, next)| {
+                if duration > 0.0 {
+                    (next - previous) / 2.0 / duration
+                } else {
+                    0.0
+                }
+            })
+        };
+        let before = index
+            .checked_sub(1)
+            .map_or(0.0, |previous| timestamps[index] - timestamps[previous]);
+        let after = timestamps
+            .get(index + 1)
+            .map_or(0.0, |next| next - timestamps[index]);
+        output.extend(tangent(before));
+        output.extend_from_slice(value(index));
+        output.extend(tangent(after));
+    }
+    output
+}
+
+/// An animation channel and its sampler in the layout of glTF, created with
+/// [`AnimationClip::to_gltf_channels`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct GltfChannel {
+    /// Path of the animated node.
+    pub path: EntityPath,
+    /// Property of the node the channel animates.
+    pub property: GltfProperty,
+    /// How the sampler is interpolated.
+    pub interpolation: GltfInterpolation,
+    /// Time of each keyframe, the input accessor of the sampler.
+    pub input: Vec<f32>,
+    /// Components of the keyframes one after the other, the output accessor of the sampler.
+    ///
+    /// With [`GltfInterpolation::CubicSpline`], each keyframe is an in-tangent, a value and an
+    /// out-tangent. For [`GltfProperty::MorphTargetWeights`], each keyframe has one weight per
+    /// morph target.
+    pub output: Vec<f32>,
+}
+
+/// Property of a node animated by a [`GltfChannel`], the `path` of a glTF channel target
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GltfProperty {
+    /// `translation`, three components per keyframe.
+    Translation,
+    /// `rotation`, the four components of a quaternion per keyframe, as `x, y, z, w`.
+    Rotation,
+    /// `scale`, three components per keyframe.
+    Scale,
+    /// `weights`, one component per morph target per keyframe.
+    MorphTargetWeights,
+}
+
+/// Interpolation of the sampler of a [`GltfChannel`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GltfInterpolation {
+    /// `LINEAR`
+    Linear,
+    /// `STEP`
+    Step,
+    /// `CUBICSPLINE`
+    CubicSpline,
+}
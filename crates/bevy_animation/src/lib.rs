@@ -2,36 +2,65 @@
 
 #![warn(missing_docs)]
 
-use std::ops::Deref;
+mod blend_tree;
+mod csv;
+mod gltf;
+
+use std::{
+    fmt,
+    ops::{Add, Deref, Mul, Sub},
+    sync::Arc,
+};
 
 use bevy_app::{App, CoreStage, Plugin};
-use bevy_asset::{AddAsset, Assets, Handle};
+use bevy_asset::{AddAsset, AssetEvent, Assets, Handle, HandleId};
 use bevy_core::Name;
 use bevy_ecs::{
-    change_detection::DetectChanges,
-    entity::Entity,
+    bundle::Bundle,
+    change_detection::{DetectChanges, Mut},
+    entity::{Entity, EntityMap, MapEntities, MapEntitiesError},
+    event::{EventReader, EventWriter},
     prelude::Component,
-    reflect::ReflectComponent,
-    schedule::IntoSystemDescriptor,
-    system::{Query, Res},
+    query::{ChangeTrackers, Changed},
+    reflect::{ReflectComponent, ReflectMapEntities, ReflectResource},
+    schedule::{IntoSystemDescriptor, StageLabel, StageLabelId, SystemLabel},
+    system::{Query, Res, ResMut, Resource},
+    world::{FromWorld, World},
 };
-use bevy_hierarchy::Children;
-use bevy_math::{Quat, Vec3};
-use bevy_reflect::{Reflect, TypeUuid};
+use bevy_hierarchy::{Children, Parent};
+use bevy_math::{EulerRot, Mat4, Quat, Vec3, Vec4};
+use bevy_reflect::{FromReflect, Reflect, ReflectMut, ReflectRef, TypeUuid};
 use bevy_time::Time;
-use bevy_transform::{prelude::Transform, TransformSystem};
-use bevy_utils::{tracing::warn, HashMap};
+use bevy_transform::{
+    prelude::{GlobalTransform, Transform},
+    TransformSystem,
+};
+use bevy_utils::{tracing::warn, Duration, HashMap};
+use thiserror::Error;
 
 #[allow(missing_docs)]
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        AnimationClip, AnimationPlayer, AnimationPlugin, EntityPath, Keyframes, VariableCurve,
+        ActiveAnimations, AnimationBundle, AnimationClip, AnimationClips, AnimationClock,
+        AnimationDriver, AnimationIntervalEntered, AnimationIntervalExited, AnimationMarkerReached,
+        AnimationMask, AnimationOutput, AnimationOverride, AnimationPlayer, AnimationPlugin,
+        AnimationSource, AnimationStrictMode, AnimationSystem, AnimationTarget, AnyAnimationActive,
+        BlendMode, BlendNode, BlendTree, BoneContribution, ChannelFlags, ClipRecorder, CurveCursor,
+        CurveKind, CurveSpace, DeltaSeconds, Easing, EdgeBehavior, EntityPath, FinishedBehavior,
+        GlobalAnimationPause, GlobalAnimationSpeed, IkGoalSpace, Interpolation, Keyframes,
+        ProceduralClip, QuantizedVec3s, RepeatAnimation, ResetAnimationPlayers, RestPose,
+        ScaleBlend, StuckAnimationPlayers, TimeWarp, UpAxis, ValueTransform, VariableCurve,
     };
 }
 
-/// List of keyframes for one of the attribute of a [`Transform`].
-#[derive(Clone, Debug)]
+pub use blend_tree::*;
+pub use csv::*;
+pub use gltf::*;
+
+/// List of keyframes for one of the attribute of a [`Transform`], or for a value of a custom
+/// component.
+#[derive(Reflect, FromReflect, Clone, Debug)]
 pub enum Keyframes {
     /// Keyframes for rotation.
     Rotation(Vec<Quat>),
@@ -39,263 +68,11429 @@ pub enum Keyframes {
     Translation(Vec<Vec3>),
     /// Keyframes for scale.
     Scale(Vec<Vec3>),
+    /// Keyframes for rotation, quantized to half their size.
+    ///
+    /// Each component of the normalized rotation is multiplied by [`i16::MAX`], which gives a
+    /// precision of about `3e-5`. Created with [`Keyframes::quantize`].
+    QuantizedRotation(Vec<[i16; 4]>),
+    /// Keyframes for translation, quantized to half their size.
+    QuantizedTranslation(QuantizedVec3s),
+    /// Keyframes for scale, quantized to half their size.
+    QuantizedScale(QuantizedVec3s),
+    /// Keyframes for a value of a component registered with
+    /// [`AnimationPlugin::register_animated`].
+    Scalar(Vec<f32>),
+    /// Keyframes for the position of an inverse kinematics goal, such as where a foot should be
+    /// planted.
+    ///
+    /// These don't move the targeted entity: they are sampled with
+    /// [`VariableCurve::sample_ik_goal`] by the system solving the inverse kinematics, for
+    /// example through [`AnimationPlugin::register_animated`].
+    IkGoal {
+        /// Space the positions are expressed in.
+        space: IkGoalSpace,
+        /// Position of the goal for each keyframe.
+        positions: Vec<Vec3>,
+    },
+    /// Keyframes for translation, rotation and scale together.
+    ///
+    /// For entities with all three attributes keyed at the same times, this finds the keyframes
+    /// to interpolate between once instead of once per attribute, and keeps the attributes in
+    /// sync. Sampled with [`VariableCurve::sample_trs`].
+    Trs(Vec<Transform>),
+    /// Keyframes for the index of a sprite in a texture atlas, for frame-by-frame 2D animations.
+    ///
+    /// Each index is held until the next keyframe, whatever the [`Interpolation`] of the curve.
+    /// With [`EdgeBehavior::Clamp`], the last index is also held until the end of the clip.
+    /// Sampled with [`VariableCurve::sample_sprite_index`], for example to set the index of a
+    /// texture atlas sprite registered with [`AnimationPlugin::register_animated`].
+    SpriteIndex(Vec<usize>),
 }
 
-/// Describes how an attribute of a [`Transform`] should be animated.
-///
-/// `keyframe_timestamps` and `keyframes` should have the same length.
-#[derive(Clone, Debug)]
-pub struct VariableCurve {
-    /// Timestamp for each of the keyframes.
-    pub keyframe_timestamps: Vec<f32>,
-    /// List of the keyframes.
-    pub keyframes: Keyframes,
+/// Space the positions of [`Keyframes::IkGoal`] are expressed in
+#[derive(Reflect, FromReflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IkGoalSpace {
+    /// Relative to the root of the animation, in the same space as the [`Transform`] of its
+    /// children. The goal follows the animated entity as it moves.
+    #[default]
+    Root,
+    /// In world space, as a [`GlobalTransform`](bevy_transform::prelude::GlobalTransform)
+    /// translation. The goal stays in place when the animated entity moves, for example to
+    /// lock a foot on the ground.
+    World,
 }
 
-/// Path to an entity, with [`Name`]s. Each entity in a path must have a name.
-#[derive(Clone, Debug, Hash, PartialEq, Eq, Default)]
-pub struct EntityPath {
-    /// Parts of the path
-    pub parts: Vec<Name>,
+/// Attribute animated by [`Keyframes`], whether they are quantized or not
+#[derive(Reflect, FromReflect, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CurveKind {
+    /// [`Keyframes::Rotation`] and [`Keyframes::QuantizedRotation`]
+    Rotation,
+    /// [`Keyframes::Translation`] and [`Keyframes::QuantizedTranslation`]
+    Translation,
+    /// [`Keyframes::Scale`] and [`Keyframes::QuantizedScale`]
+    Scale,
+    /// [`Keyframes::Scalar`]
+    Scalar,
+    /// [`Keyframes::IkGoal`]
+    IkGoal,
+    /// [`Keyframes::Trs`]
+    Trs,
+    /// [`Keyframes::SpriteIndex`]
+    SpriteIndex,
 }
 
-/// A list of [`VariableCurve`], and the [`EntityPath`] to which they apply.
-#[derive(Clone, TypeUuid, Debug, Default)]
-#[uuid = "d81b7179-0448-4eb0-89fe-c067222725bf"]
-pub struct AnimationClip {
-    curves: HashMap<EntityPath, Vec<VariableCurve>>,
-    duration: f32,
+bitflags::bitflags! {
+    /// Kinds of curves a clip has, as returned by [`AnimationClip::channels`]
+    #[derive(Default)]
+    pub struct ChannelFlags: u8 {
+        /// Has a translation curve, quantized or not, or a [TRS](Keyframes::Trs) curve.
+        const TRANSLATION = 1 << 0;
+        /// Has a rotation curve, quantized or not, or a [TRS](Keyframes::Trs) curve.
+        const ROTATION = 1 << 1;
+        /// Has a scale curve, quantized or not, or a [TRS](Keyframes::Trs) curve.
+        const SCALE = 1 << 2;
+        /// Has a [scalar](Keyframes::Scalar) curve, like morph target weights, which needs the
+        /// component registered with [`AnimationPlugin::register_animated`] on the target.
+        const SCALAR = 1 << 3;
+        /// Has an [inverse kinematics goal](Keyframes::IkGoal) curve.
+        const IK_GOAL = 1 << 4;
+        /// Has a [sprite index](Keyframes::SpriteIndex) curve.
+        const SPRITE_INDEX = 1 << 5;
+        /// Has a curve animating the [`Transform`].
+        const TRANSFORM = Self::TRANSLATION.bits | Self::ROTATION.bits | Self::SCALE.bits;
+    }
 }
 
-impl AnimationClip {
-    #[inline]
-    /// Hashmap of the [`VariableCurve`]s per [`EntityPath`].
-    pub fn curves(&self) -> &HashMap<EntityPath, Vec<VariableCurve>> {
-        &self.curves
+impl Keyframes {
+    /// Attribute animated by the keyframes
+    pub fn kind(&self) -> CurveKind {
+        match self {
+            Keyframes::Rotation(_) | Keyframes::QuantizedRotation(_) => CurveKind::Rotation,
+            Keyframes::Translation(_) | Keyframes::QuantizedTranslation(_) => {
+                CurveKind::Translation
+            }
+            Keyframes::Scale(_) | Keyframes::QuantizedScale(_) => CurveKind::Scale,
+            Keyframes::Scalar(_) => CurveKind::Scalar,
+            Keyframes::IkGoal { .. } => CurveKind::IkGoal,
+            Keyframes::Trs(_) => CurveKind::Trs,
+            Keyframes::SpriteIndex(_) => CurveKind::SpriteIndex,
+        }
     }
 
-    /// Duration of the clip, represented in seconds
-    #[inline]
-    pub fn duration(&self) -> f32 {
-        self.duration
+    /// Number of keyframes
+    fn len(&self) -> usize {
+        match self {
+            Keyframes::Rotation(keyframes) => keyframes.len(),
+            Keyframes::Translation(keyframes) | Keyframes::Scale(keyframes) => keyframes.len(),
+            Keyframes::QuantizedRotation(keyframes) => keyframes.len(),
+            Keyframes::QuantizedTranslation(keyframes) | Keyframes::QuantizedScale(keyframes) => {
+                keyframes.len()
+            }
+            Keyframes::Scalar(keyframes) => keyframes.len(),
+            Keyframes::IkGoal { positions, .. } => positions.len(),
+            Keyframes::Trs(keyframes) => keyframes.len(),
+            Keyframes::SpriteIndex(keyframes) => keyframes.len(),
+        }
     }
 
-    /// Add a [`VariableCurve`] to an [`EntityPath`].
-    pub fn add_curve_to_path(&mut self, path: EntityPath, curve: VariableCurve) {
-        // Update the duration of the animation by this curve duration if it's longer
-        self.duration = self
-            .duration
-            .max(*curve.keyframe_timestamps.last().unwrap_or(&0.0));
-        self.curves.entry(path).or_default().push(curve);
+    /// Are all the keyframes finite
+    fn is_finite(&self) -> bool {
+        match self {
+            Keyframes::Rotation(keyframes) => keyframes.iter().all(|rotation| rotation.is_finite()),
+            Keyframes::Translation(keyframes) | Keyframes::Scale(keyframes) => {
+                keyframes.iter().all(|value| value.is_finite())
+            }
+            Keyframes::QuantizedRotation(_) => true,
+            Keyframes::QuantizedTranslation(keyframes) | Keyframes::QuantizedScale(keyframes) => {
+                keyframes.min.is_finite() && keyframes.max.is_finite()
+            }
+            Keyframes::Scalar(keyframes) => keyframes.iter().all(|value| value.is_finite()),
+            Keyframes::IkGoal { positions, .. } => {
+                positions.iter().all(|position| position.is_finite())
+            }
+            Keyframes::Trs(keyframes) => keyframes.iter().all(|transform| {
+                transform.translation.is_finite()
+                    && transform.rotation.is_finite()
+                    && transform.scale.is_finite()
+            }),
+            Keyframes::SpriteIndex(_) => true,
+        }
+    }
+
+    /// Are the keyframes at indices `a` and `b` equal, within `epsilon`
+    fn approx_eq(&self, a: usize, b: usize, epsilon: f32) -> bool {
+        match self {
+            Keyframes::Rotation(_) | Keyframes::QuantizedRotation(_) => {
+                match (self.rotation(a), self.rotation(b)) {
+                    // Opposite quaternions are the same rotation
+                    (Some(a), Some(b)) => a.abs_diff_eq(b, epsilon) || a.abs_diff_eq(-b, epsilon),
+                    _ => false,
+                }
+            }
+            Keyframes::Translation(_) | Keyframes::QuantizedTranslation(_) => {
+                match (self.translation(a), self.translation(b)) {
+                    (Some(a), Some(b)) => a.abs_diff_eq(b, epsilon),
+                    _ => false,
+                }
+            }
+            Keyframes::Scale(_) | Keyframes::QuantizedScale(_) => {
+                match (self.scale(a), self.scale(b)) {
+                    (Some(a), Some(b)) => a.abs_diff_eq(b, epsilon),
+                    _ => false,
+                }
+            }
+            Keyframes::Scalar(_) => match (self.scalar(a), self.scalar(b)) {
+                (Some(a), Some(b)) => (a - b).abs() <= epsilon,
+                _ => false,
+            },
+            Keyframes::IkGoal { .. } => match (self.ik_goal(a), self.ik_goal(b)) {
+                (Some(a), Some(b)) => a.abs_diff_eq(b, epsilon),
+                _ => false,
+            },
+            Keyframes::Trs(_) => match (self.trs(a), self.trs(b)) {
+                (Some(a), Some(b)) => {
+                    a.translation.abs_diff_eq(b.translation, epsilon)
+                        && (a.rotation.abs_diff_eq(b.rotation, epsilon)
+                            || a.rotation.abs_diff_eq(-b.rotation, epsilon))
+                        && a.scale.abs_diff_eq(b.scale, epsilon)
+                }
+                _ => false,
+            },
+            Keyframes::SpriteIndex(_) => {
+                matches!(self.sprite_index(a), Some(index) if self.sprite_index(b) == Some(index))
+            }
+        }
+    }
+
+    /// Number of bytes allocated on the heap for the keyframes
+    fn heap_size(&self) -> usize {
+        match self {
+            Keyframes::Rotation(keyframes) => vec_heap_size(keyframes),
+            Keyframes::Translation(keyframes) | Keyframes::Scale(keyframes) => {
+                vec_heap_size(keyframes)
+            }
+            Keyframes::QuantizedRotation(keyframes) => vec_heap_size(keyframes),
+            Keyframes::QuantizedTranslation(keyframes) | Keyframes::QuantizedScale(keyframes) => {
+                vec_heap_size(&keyframes.values)
+            }
+            Keyframes::Scalar(keyframes) => vec_heap_size(keyframes),
+            Keyframes::IkGoal { positions, .. } => vec_heap_size(positions),
+            Keyframes::Trs(keyframes) => vec_heap_size(keyframes),
+            Keyframes::SpriteIndex(keyframes) => vec_heap_size(keyframes),
+        }
+    }
+
+    /// Keep only the first `len` keyframes
+    fn truncate(&mut self, len: usize) {
+        match self {
+            Keyframes::Rotation(keyframes) => keyframes.truncate(len),
+            Keyframes::Translation(keyframes) | Keyframes::Scale(keyframes) => {
+                keyframes.truncate(len);
+            }
+            Keyframes::QuantizedRotation(keyframes) => keyframes.truncate(len),
+            Keyframes::QuantizedTranslation(keyframes) | Keyframes::QuantizedScale(keyframes) => {
+                keyframes.values.truncate(len);
+            }
+            Keyframes::Scalar(keyframes) => keyframes.truncate(len),
+            Keyframes::IkGoal { positions, .. } => positions.truncate(len),
+            Keyframes::Trs(keyframes) => keyframes.truncate(len),
+            Keyframes::SpriteIndex(keyframes) => keyframes.truncate(len),
+        }
+    }
+
+    /// Do both keyframes animate the same attribute, whether they are quantized or not
+    fn animates_same_attribute(&self, other: &Keyframes) -> bool {
+        self.kind() == other.kind()
+    }
+
+    /// Rotation keyframe at `index`, or `None` if these are not rotation keyframes
+    fn rotation(&self, index: usize) -> Option<Quat> {
+        match self {
+            Keyframes::Rotation(keyframes) => keyframes.get(index).copied(),
+            Keyframes::QuantizedRotation(keyframes) => {
+                let rotation = keyframes.get(index)?;
+                Some(
+                    Quat::from_array(rotation.map(|component| component as f32 / i16::MAX as f32))
+                        .normalize(),
+                )
+            }
+            _ => None,
+        }
+    }
+
+    /// Translation keyframe at `index`, or `None` if these are not translation keyframes
+    fn translation(&self, index: usize) -> Option<Vec3> {
+        match self {
+            Keyframes::Translation(keyframes) => keyframes.get(index).copied(),
+            Keyframes::QuantizedTranslation(keyframes) => keyframes.get(index),
+            _ => None,
+        }
+    }
+
+    /// Scale keyframe at `index`, or `None` if these are not scale keyframes
+    fn scale(&self, index: usize) -> Option<Vec3> {
+        match self {
+            Keyframes::Scale(keyframes) => keyframes.get(index).copied(),
+            Keyframes::QuantizedScale(keyframes) => keyframes.get(index),
+            _ => None,
+        }
+    }
+
+    /// Sprite index keyframe at `index`, or `None` if these are not sprite index keyframes
+    fn sprite_index(&self, index: usize) -> Option<usize> {
+        match self {
+            Keyframes::SpriteIndex(keyframes) => keyframes.get(index).copied(),
+            _ => None,
+        }
+    }
+
+    /// Scalar keyframe at `index`, or `None` if these are not scalar keyframes
+    fn scalar(&self, index: usize) -> Option<f32> {
+        match self {
+            Keyframes::Scalar(keyframes) => keyframes.get(index).copied(),
+            _ => None,
+        }
+    }
+
+    /// Inverse kinematics goal keyframe at `index`, or `None` if these are not inverse kinematics
+    /// goal keyframes
+    fn ik_goal(&self, index: usize) -> Option<Vec3> {
+        match self {
+            Keyframes::IkGoal { positions, .. } => positions.get(index).copied(),
+            _ => None,
+        }
+    }
+
+    /// Translation, rotation and scale keyframe at `index`, or `None` if these are not
+    /// [`Keyframes::Trs`]
+    fn trs(&self, index: usize) -> Option<Transform> {
+        match self {
+            Keyframes::Trs(keyframes) => keyframes.get(index).copied(),
+            _ => None,
+        }
+    }
+
+    /// Quantize the keyframes, halving their size at the cost of precision
+    ///
+    /// Keyframes that are already quantized, or other than rotation, translation or scale
+    /// keyframes, are returned unchanged. See the
+    /// quantized variants of [`Keyframes`] for the precision of each attribute.
+    pub fn quantize(&self) -> Keyframes {
+        match self {
+            Keyframes::Rotation(keyframes) => Keyframes::QuantizedRotation(
+                keyframes
+                    .iter()
+                    .map(|rotation| {
+                        rotation
+                            .normalize()
+                            .to_array()
+                            .map(|component| (component * i16::MAX as f32).round() as i16)
+                    })
+                    .collect(),
+            ),
+            Keyframes::Translation(keyframes) => {
+                Keyframes::QuantizedTranslation(QuantizedVec3s::new(keyframes))
+            }
+            Keyframes::Scale(keyframes) => {
+                Keyframes::QuantizedScale(QuantizedVec3s::new(keyframes))
+            }
+            quantized => quantized.clone(),
+        }
+    }
+
+    /// Decode quantized keyframes back to full precision keyframes
+    ///
+    /// Keyframes that are not quantized are returned unchanged.
+    pub fn dequantize(&self) -> Keyframes {
+        match self {
+            Keyframes::QuantizedRotation(keyframes) => Keyframes::Rotation(
+                (0..keyframes.len())
+                    .filter_map(|index| self.rotation(index))
+                    .collect(),
+            ),
+            Keyframes::QuantizedTranslation(keyframes) => {
+                Keyframes::Translation(keyframes.to_vec())
+            }
+            Keyframes::QuantizedScale(keyframes) => Keyframes::Scale(keyframes.to_vec()),
+            keyframes => keyframes.clone(),
+        }
     }
 }
 
-/// Animation controls
-#[derive(Component, Reflect)]
-#[reflect(Component)]
-pub struct AnimationPlayer {
-    paused: bool,
-    repeat: bool,
-    speed: f32,
-    elapsed: f32,
-    animation_clip: Handle<AnimationClip>,
+/// Vectors quantized to 16 bits per component inside of their bounding box
+///
+/// The precision of each component is the extent of the values along this axis divided by
+/// [`u16::MAX`], so about `1.5e-4` for translations spanning 10 meters.
+#[derive(Reflect, FromReflect, Clone, Debug, Default)]
+pub struct QuantizedVec3s {
+    min: Vec3,
+    max: Vec3,
+    values: Vec<[u16; 3]>,
 }
 
-impl Default for AnimationPlayer {
-    fn default() -> Self {
-        Self {
-            paused: false,
-            repeat: false,
-            speed: 1.0,
-            elapsed: 0.0,
-            animation_clip: Default::default(),
+impl QuantizedVec3s {
+    /// Quantize `values`
+    ///
+    /// The values should be finite, as the bounding box of non-finite values can't be quantized.
+    pub fn new(values: &[Vec3]) -> Self {
+        if values.is_empty() {
+            return Self::default();
         }
+        let min = values
+            .iter()
+            .copied()
+            .fold(Vec3::splat(f32::MAX), Vec3::min);
+        let max = values
+            .iter()
+            .copied()
+            .fold(Vec3::splat(f32::MIN), Vec3::max);
+        let extent = max - min;
+        let values = values
+            .iter()
+            .map(|value| {
+                let normalized = Vec3::select(
+                    extent.cmpgt(Vec3::ZERO),
+                    (*value - min) / extent,
+                    Vec3::ZERO,
+                );
+                (normalized * u16::MAX as f32)
+                    .round()
+                    .to_array()
+                    .map(|component| component as u16)
+            })
+            .collect();
+        Self { min, max, values }
     }
-}
 
-impl AnimationPlayer {
-    /// Start playing an animation, resetting state of the player
-    pub fn play(&mut self, handle: Handle<AnimationClip>) -> &mut Self {
-        *self = Self {
-            animation_clip: handle,
-            ..Default::default()
-        };
-        self
+    /// Decoded value at `index`
+    pub fn get(&self, index: usize) -> Option<Vec3> {
+        let value = Vec3::from_array(self.values.get(index)?.map(f32::from));
+        Some(self.min + (self.max - self.min) * value / u16::MAX as f32)
     }
 
-    /// Set the animation to repeat
-    pub fn repeat(&mut self) -> &mut Self {
-        self.repeat = true;
-        self
+    /// Number of values
+    pub fn len(&self) -> usize {
+        self.values.len()
     }
 
-    /// Stop the animation from repeating
-    pub fn stop_repeating(&mut self) -> &mut Self {
-        self.repeat = false;
-        self
+    /// Are there no values
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
     }
 
-    /// Pause the animation
-    pub fn pause(&mut self) {
-        self.paused = true;
+    /// The value at `index` alone, quantized in the same bounding box
+    fn single(&self, index: usize) -> Option<QuantizedVec3s> {
+        Some(QuantizedVec3s {
+            min: self.min,
+            max: self.max,
+            values: vec![*self.values.get(index)?],
+        })
     }
 
-    /// Unpause the animation
-    pub fn resume(&mut self) {
-        self.paused = false;
+    /// Bounding box and quantized values, without decoding them
+    fn raw_components(&self) -> Vec<f32> {
+        self.min
+            .to_array()
+            .into_iter()
+            .chain(self.max.to_array())
+            .chain(
+                self.values
+                    .iter()
+                    .flatten()
+                    .map(|&component| component as f32),
+            )
+            .collect()
     }
 
-    /// Is the animation paused
-    pub fn is_paused(&self) -> bool {
-        self.paused
+    /// Decode all the values
+    pub fn to_vec(&self) -> Vec<Vec3> {
+        (0..self.len())
+            .filter_map(|index| self.get(index))
+            .collect()
     }
+}
 
-    /// Speed of the animation playback
-    pub fn speed(&self) -> f32 {
-        self.speed
+/// Describes how an attribute of a [`Transform`] should be animated.
+///
+/// `keyframe_timestamps` and `keyframes` should have the same length.
+#[derive(Reflect, FromReflect, Clone, Debug)]
+pub struct VariableCurve {
+    /// Timestamp for each of the keyframes.
+    pub keyframe_timestamps: Vec<f32>,
+    /// List of the keyframes.
+    pub keyframes: Keyframes,
+    /// Whether rotations are interpolated along the shortest path between two keyframes.
+    ///
+    /// When `false`, the rotation between two keyframes more than 180° apart goes the long way
+    /// around, which is needed for spinning animations keyed with few keyframes.
+    pub take_shortest_path: bool,
+    /// Multiplier of the weight this curve is applied with.
+    ///
+    /// It is multiplied with the [weight](PlayingAnimation::set_weight) of the animation, so a
+    /// curve with a weight of `0.5` only goes halfway from the current pose toward its keyframes
+    /// when the animation has a weight of `1.0`.
+    pub weight: f32,
+    /// What the curve samples before its first keyframe or after its last one.
+    pub edge_behavior: EdgeBehavior,
+    /// How the curve is interpolated between two keyframes.
+    pub interpolation: Interpolation,
+    /// Point the rotations of this curve turn around, in the local space of the animated entity.
+    ///
+    /// When set, the translation of the entity is offset so that the pivot stays in place as the
+    /// rotation changes, like a door turning around its hinge. The pivot is not affected by the
+    /// scale of the entity. A translation curve applied after this one replaces the offset.
+    pub pivot: Option<Vec3>,
+    /// Whether the curve is applied.
+    ///
+    /// Disabling a curve mutes it without removing it from its clip, for example to compare a clip
+    /// with and without one of its channels in a tool.
+    pub enabled: bool,
+    /// How the scale of this curve is combined with the scale of the entity, overriding the
+    /// [scale blend](PlayingAnimation::set_scale_blend) of the animation when set.
+    ///
+    /// With [`ScaleBlend::Multiplicative`], a scale set by a procedural system, such as a stretch
+    /// bone, is multiplied by the clip instead of being replaced. That scale should then be set
+    /// again every frame before the animations are applied, as it otherwise accumulates.
+    pub scale_blend: Option<ScaleBlend>,
+    /// Whether the values of a [scalar](Keyframes::Scalar) curve are clamped between `0.0` and
+    /// `1.0` after interpolation.
+    ///
+    /// [Cubic](Interpolation::Cubic) interpolation can overshoot the keyframes, which some
+    /// consumers of weights, such as morph target weights, don't expect. Overshooting can also be
+    /// intentional, so this is `false` by default.
+    pub clamp_scalar: bool,
+    /// Whether the components of a scale that change sign between two keyframes flip halfway
+    /// instead of being interpolated through `0.0`.
+    ///
+    /// Keying a scale from `1.0` to `-1.0` mirrors a mesh, but interpolating it collapses the mesh
+    /// to nothing in the middle. When set, the magnitude of such components is interpolated and
+    /// their sign steps from the one of the first keyframe to the other at the middle of the
+    /// interval. Other components, and [step](Interpolation::Step) interpolation, are unaffected.
+    pub preserve_scale_sign: bool,
+    /// Time between two keyframes, when they are evenly spaced.
+    ///
+    /// When set, the keyframes to interpolate between are computed from the time instead of
+    /// searched, which speeds up sampling long baked curves. Set by
+    /// [`VariableCurve::detect_uniform_step`]. A step that doesn't match the timestamps, for
+    /// example after inserting a keyframe, only loses the speedup, as the keyframes are then
+    /// searched.
+    pub uniform_step: Option<f32>,
+}
+
+/// How a [`VariableCurve`] is interpolated between two keyframes
+#[derive(Reflect, FromReflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Interpolation {
+    /// Interpolate linearly, with a spherical linear interpolation for rotations.
+    #[default]
+    Linear,
+    /// Hold the value of a keyframe until the next one.
+    Step,
+    /// Interpolate along a Catmull-Rom spline going through the keyframes, for smooth motion
+    /// without authoring tangents.
+    ///
+    /// The keyframes are assumed to be evenly spaced in time. The first and last keyframes are
+    /// repeated to compute the spline at the ends of the curve. Rotations are interpolated along
+    /// the shortest path, whatever [`VariableCurve::take_shortest_path`] is.
+    Cubic,
+}
+
+/// What a [`VariableCurve`] samples outside of its keyframes
+#[derive(Reflect, FromReflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EdgeBehavior {
+    /// Nothing is sampled, leaving the animated property at its previous value.
+    #[default]
+    Skip,
+    /// The first keyframe is sampled before the curve, and the last one after it.
+    Clamp,
+}
+
+impl VariableCurve {
+    /// Create a curve from its keyframes and their timestamps.
+    pub fn new(keyframe_timestamps: Vec<f32>, keyframes: Keyframes) -> Self {
+        Self {
+            keyframe_timestamps,
+            keyframes,
+            take_shortest_path: true,
+            weight: 1.0,
+            edge_behavior: EdgeBehavior::Skip,
+            interpolation: Interpolation::Linear,
+            pivot: None,
+            enabled: true,
+            scale_blend: None,
+            clamp_scalar: false,
+            preserve_scale_sign: false,
+            uniform_step: None,
+        }
     }
 
-    /// Set the speed of the animation playback
-    pub fn set_speed(&mut self, speed: f32) -> &mut Self {
-        self.speed = speed;
-        self
+    /// Set [`uniform_step`](VariableCurve::uniform_step) if the keyframes are evenly spaced,
+    /// with gaps differing by at most `epsilon`, and return it
+    ///
+    /// The step is cleared if the keyframes are not evenly spaced.
+    pub fn detect_uniform_step(&mut self, epsilon: f32) -> Option<f32> {
+        let timestamps = &self.keyframe_timestamps;
+        let step = match (timestamps.first(), timestamps.last()) {
+            (Some(first), Some(last)) if timestamps.len() > 1 && last > first => {
+                (last - first) / (timestamps.len() - 1) as f32
+            }
+            _ => 0.0,
+        };
+        self.uniform_step = (step > 0.0
+            && timestamps
+                .windows(2)
+                .all(|pair| (pair[1] - pair[0] - step).abs() <= epsilon))
+        .then_some(step);
+        self.uniform_step
     }
 
-    /// Time elapsed playing the animation
-    pub fn elapsed(&self) -> f32 {
-        self.elapsed
+    /// Set the point the rotations of this curve turn around, see [`VariableCurve::pivot`]
+    pub fn with_pivot(mut self, pivot: Vec3) -> Self {
+        self.pivot = Some(pivot);
+        self
     }
 
-    /// Seek to a specific time in the animation
-    pub fn set_elapsed(&mut self, elapsed: f32) -> &mut Self {
-        self.elapsed = elapsed;
+    /// Set how the curve is interpolated between two keyframes
+    ///
+    /// ```
+    /// # use bevy_animation::{Interpolation, Keyframes, VariableCurve};
+    /// # use bevy_math::Vec3;
+    /// let curve = VariableCurve::new(
+    ///     vec![0.0, 1.0],
+    ///     Keyframes::Translation(vec![Vec3::ZERO, Vec3::X]),
+    /// )
+    /// .with_interpolation(Interpolation::Step);
+    /// assert_eq!(curve.sample_translation(0.5), Some(Vec3::ZERO));
+    /// ```
+    pub fn with_interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
         self
     }
-}
 
-/// System that will play all animations, using any entity with a [`AnimationPlayer`]
-/// and a [`Handle<AnimationClip>`] as an animation root
-pub fn animation_player(
-    time: Res<Time>,
-    animations: Res<Assets<AnimationClip>>,
-    mut animation_players: Query<(Entity, &mut AnimationPlayer)>,
-    names: Query<&Name>,
-    mut transforms: Query<&mut Transform>,
-    children: Query<&Children>,
-) {
-    for (entity, mut player) in &mut animation_players {
-        if let Some(animation_clip) = animations.get(&player.animation_clip) {
-            // Continue if paused unless the `AnimationPlayer` was changed
-            // This allow the animation to still be updated if the player.elapsed field was manually updated in pause
-            if player.paused && !player.is_changed() {
-                continue;
+    /// Create a rotation curve from three scalar curves of Euler angles, as `(timestamps, angles)`
+    ///
+    /// The curves are the first, second and third angles of `order`, in radians, as given to
+    /// [`Quat::from_euler`]. They can have different timestamps: a rotation keyframe is created at
+    /// each timestamp of any of the curves, and a curve is clamped to its first and last angles
+    /// outside of its timestamps. The supported orders are [`EulerRot::XYZ`], [`EulerRot::XZY`],
+    /// [`EulerRot::YXZ`], [`EulerRot::YZX`], [`EulerRot::ZXY`] and [`EulerRot::ZYX`].
+    ///
+    /// Rotations are interpolated without taking the shortest path, so that a turn of more than
+    /// 180° between two keyframes is kept, as it would be by interpolating the angles. Curves
+    /// without any angle are treated as a constant angle of `0.0`, and NaN timestamps are skipped.
+    pub fn from_euler_curves(order: EulerRot, curves: [(&[f32], &[f32]); 3]) -> Self {
+        let mut keyframe_timestamps: Vec<f32> = curves
+            .iter()
+            .flat_map(|(timestamps, _)| timestamps.iter().copied())
+            .filter(|timestamp| !timestamp.is_nan())
+            .collect();
+        keyframe_timestamps.sort_by(f32::total_cmp);
+        keyframe_timestamps.dedup();
+        let angle = |(timestamps, angles): (&[f32], &[f32]), time| {
+            if angles.is_empty() {
+                0.0
+            } else {
+                sample_scalar(timestamps, angles, time)
             }
-            if !player.paused {
-                player.elapsed += time.delta_seconds() * player.speed;
+        };
+        let keyframes = keyframe_timestamps
+            .iter()
+            .map(|&time| {
+                Quat::from_euler(
+                    order,
+                    angle(curves[0], time),
+                    angle(curves[1], time),
+                    angle(curves[2], time),
+                )
+            })
+            .collect();
+        Self {
+            take_shortest_path: false,
+            ..Self::new(keyframe_timestamps, Keyframes::Rotation(keyframes))
+        }
+    }
+
+    /// Timestamp and value of the keyframe at `index`, without interpolation
+    ///
+    /// The value is returned as [`Keyframes`] of the same kind as the curve with a single element.
+    /// Returns `None` if there is no timestamp or keyframe at `index`.
+    pub fn keyframe(&self, index: usize) -> Option<(f32, Keyframes)> {
+        let timestamp = *self.keyframe_timestamps.get(index)?;
+        let keyframe = match &self.keyframes {
+            Keyframes::Rotation(keyframes) => Keyframes::Rotation(vec![*keyframes.get(index)?]),
+            Keyframes::Translation(keyframes) => {
+                Keyframes::Translation(vec![*keyframes.get(index)?])
             }
-            let mut elapsed = player.elapsed;
-            if player.repeat {
-                elapsed %= animation_clip.duration;
+            Keyframes::Scale(keyframes) => Keyframes::Scale(vec![*keyframes.get(index)?]),
+            Keyframes::QuantizedRotation(keyframes) => {
+                Keyframes::QuantizedRotation(vec![*keyframes.get(index)?])
             }
-            if elapsed < 0.0 {
-                elapsed += animation_clip.duration;
+            Keyframes::QuantizedTranslation(keyframes) => {
+                Keyframes::QuantizedTranslation(keyframes.single(index)?)
             }
-            'entity: for (path, curves) in &animation_clip.curves {
-                // PERF: finding the target entity can be optimised
-                let mut current_entity = entity;
-                // Ignore the first name, it is the root node which we already have
-                for part in path.parts.iter().skip(1) {
-                    let mut found = false;
-                    if let Ok(children) = children.get(current_entity) {
-                        for child in children.deref() {
-                            if let Ok(name) = names.get(*child) {
-                                if name == part {
-                                    // Found a children with the right name, continue to the next part
-                                    current_entity = *child;
-                                    found = true;
-                                    break;
-                                }
-                            }
-                        }
-                    }
-                    if !found {
-                        warn!("Entity not found for path {:?} on part {:?}", path, part);
-                        continue 'entity;
-                    }
-                }
-                if let Ok(mut transform) = transforms.get_mut(current_entity) {
-                    for curve in curves {
-                        // Some curves have only one keyframe used to set a transform
-                        if curve.keyframe_timestamps.len() == 1 {
-                            match &curve.keyframes {
-                                Keyframes::Rotation(keyframes) => transform.rotation = keyframes[0],
-                                Keyframes::Translation(keyframes) => {
-                                    transform.translation = keyframes[0];
-                                }
-                                Keyframes::Scale(keyframes) => transform.scale = keyframes[0],
-                            }
-                            continue;
-                        }
+            Keyframes::QuantizedScale(keyframes) => {
+                Keyframes::QuantizedScale(keyframes.single(index)?)
+            }
+            Keyframes::Scalar(keyframes) => Keyframes::Scalar(vec![*keyframes.get(index)?]),
+            Keyframes::IkGoal { space, positions } => Keyframes::IkGoal {
+                space: *space,
+                positions: vec![*positions.get(index)?],
+            },
+            Keyframes::Trs(keyframes) => Keyframes::Trs(vec![*keyframes.get(index)?]),
+            Keyframes::SpriteIndex(keyframes) => {
+                Keyframes::SpriteIndex(vec![*keyframes.get(index)?])
+            }
+        };
+        Some((timestamp, keyframe))
+    }
 
-                        // Find the current keyframe
-                        // PERF: finding the current keyframe can be optimised
-                        let step_start = match curve
-                            .keyframe_timestamps
-                            .binary_search_by(|probe| probe.partial_cmp(&elapsed).unwrap())
-                        {
-                            Ok(i) => i,
-                            Err(0) => continue, // this curve isn't started yet
-                            Err(n) if n > curve.keyframe_timestamps.len() - 1 => continue, // this curve is finished
-                            Err(i) => i - 1,
-                        };
-                        let ts_start = curve.keyframe_timestamps[step_start];
-                        let ts_end = curve.keyframe_timestamps[step_start + 1];
-                        let lerp = (elapsed - ts_start) / (ts_end - ts_start);
-
-                        // Apply the keyframe
-                        match &curve.keyframes {
-                            Keyframes::Rotation(keyframes) => {
-                                let rot_start = keyframes[step_start];
-                                let mut rot_end = keyframes[step_start + 1];
-                                // Choose the smallest angle for the rotation
-                                if rot_end.dot(rot_start) < 0.0 {
-                                    rot_end = -rot_end;
-                                }
-                                // Rotations are using a spherical linear interpolation
-                                transform.rotation =
-                                    rot_start.normalize().slerp(rot_end.normalize(), lerp);
-                            }
-                            Keyframes::Translation(keyframes) => {
-                                let translation_start = keyframes[step_start];
-                                let translation_end = keyframes[step_start + 1];
-                                let result = translation_start.lerp(translation_end, lerp);
-                                transform.translation = result;
-                            }
-                            Keyframes::Scale(keyframes) => {
-                                let scale_start = keyframes[step_start];
-                                let scale_end = keyframes[step_start + 1];
-                                let result = scale_start.lerp(scale_end, lerp);
-                                transform.scale = result;
-                            }
-                        }
-                    }
+    /// Insert a keyframe at `time`, with the value the curve has at this time
+    ///
+    /// The curve is unchanged until the new keyframe is edited, except for
+    /// [cubic](Interpolation::Cubic) curves, whose spline assumes evenly spaced keyframes. Outside
+    /// of the curve, the value of the closest keyframe is used. Quantized keyframes are quantized
+    /// again with the new keyframe. Returns the index of the keyframe at `time`, which is the
+    /// existing one if there is already a keyframe at this time, or `None` if the curve has no
+    /// keyframes.
+    pub fn insert_keyframe(&mut self, time: f32) -> Option<usize> {
+        let index = self
+            .keyframe_timestamps
+            .partition_point(|&timestamp| timestamp < time);
+        if self.keyframe_timestamps.get(index) == Some(&time) {
+            return Some(index);
+        }
+        let first = *self.keyframe_timestamps.first()?;
+        let last = *self.keyframe_timestamps.last()?;
+        let sample_time = time.clamp(first, last);
+        let quantized = matches!(
+            self.keyframes,
+            Keyframes::QuantizedRotation(_)
+                | Keyframes::QuantizedTranslation(_)
+                | Keyframes::QuantizedScale(_)
+        );
+        let mut keyframes = self.keyframes.dequantize();
+        match &mut keyframes {
+            Keyframes::Rotation(keyframes) => {
+                keyframes.insert(index, self.sample_rotation(sample_time)?);
+            }
+            Keyframes::Translation(keyframes) => {
+                keyframes.insert(index, self.sample_translation(sample_time)?);
+            }
+            Keyframes::Scale(keyframes) => {
+                keyframes.insert(index, self.sample_scale(sample_time)?);
+            }
+            Keyframes::Scalar(keyframes) => {
+                keyframes.insert(index, self.sample_scalar(sample_time)?);
+            }
+            Keyframes::IkGoal { positions, .. } => {
+                positions.insert(index, self.sample_ik_goal(sample_time)?);
+            }
+            Keyframes::Trs(keyframes) => {
+                keyframes.insert(index, self.sample_trs(sample_time)?);
+            }
+            Keyframes::SpriteIndex(keyframes) => {
+                keyframes.insert(index, self.sample_sprite_index(sample_time)?);
+            }
+            Keyframes::QuantizedRotation(_)
+            | Keyframes::QuantizedTranslation(_)
+            | Keyframes::QuantizedScale(_) => unreachable!("keyframes are dequantized"),
+        }
+        self.keyframes = if quantized {
+            keyframes.quantize()
+        } else {
+            keyframes
+        };
+        self.keyframe_timestamps.insert(index, time);
+        Some(index)
+    }
+
+    /// Part of this curve within `[start, end]`, with timestamps starting at `start`
+    ///
+    /// Keyframes are sampled at the edges of the range, and the curve is held at its first or
+    /// last keyframe if it doesn't cover the range. Returns `None` if the curve has no keyframes.
+    fn slice(&self, start: f32, end: f32) -> Option<VariableCurve> {
+        let first = *self.keyframe_timestamps.first()?;
+        let last = *self.keyframe_timestamps.last()?;
+        let mut sample_times = vec![start.clamp(first, last)];
+        sample_times.extend(
+            self.keyframe_timestamps
+                .iter()
+                .copied()
+                .filter(|&timestamp| timestamp > start && timestamp < end),
+        );
+        sample_times.push(end.clamp(first, last));
+        sample_times.dedup();
+        let keyframes = match &self.keyframes {
+            Keyframes::Rotation(_) | Keyframes::QuantizedRotation(_) => Keyframes::Rotation(
+                sample_times
+                    .iter()
+                    .map(|&time| self.sample_rotation(time))
+                    .collect::<Option<_>>()?,
+            ),
+            Keyframes::Translation(_) | Keyframes::QuantizedTranslation(_) => {
+                Keyframes::Translation(
+                    sample_times
+                        .iter()
+                        .map(|&time| self.sample_translation(time))
+                        .collect::<Option<_>>()?,
+                )
+            }
+            Keyframes::Scale(_) | Keyframes::QuantizedScale(_) => Keyframes::Scale(
+                sample_times
+                    .iter()
+                    .map(|&time| self.sample_scale(time))
+                    .collect::<Option<_>>()?,
+            ),
+            Keyframes::Scalar(_) => Keyframes::Scalar(
+                sample_times
+                    .iter()
+                    .map(|&time| self.sample_scalar(time))
+                    .collect::<Option<_>>()?,
+            ),
+            Keyframes::IkGoal { space, .. } => Keyframes::IkGoal {
+                space: *space,
+                positions: sample_times
+                    .iter()
+                    .map(|&time| self.sample_ik_goal(time))
+                    .collect::<Option<_>>()?,
+            },
+            Keyframes::Trs(_) => Keyframes::Trs(
+                sample_times
+                    .iter()
+                    .map(|&time| self.sample_trs(time))
+                    .collect::<Option<_>>()?,
+            ),
+            Keyframes::SpriteIndex(_) => Keyframes::SpriteIndex(
+                sample_times
+                    .iter()
+                    .map(|&time| self.sample_sprite_index(time))
+                    .collect::<Option<_>>()?,
+            ),
+        };
+        let quantized = matches!(
+            self.keyframes,
+            Keyframes::QuantizedRotation(_)
+                | Keyframes::QuantizedTranslation(_)
+                | Keyframes::QuantizedScale(_)
+        );
+        Some(VariableCurve {
+            keyframe_timestamps: sample_times
+                .iter()
+                .map(|&time| (time - start).clamp(0.0, end - start))
+                .collect(),
+            keyframes: if quantized {
+                keyframes.quantize()
+            } else {
+                keyframes
+            },
+            ..self.clone()
+        })
+    }
+
+    /// This curve merged with `other`, a later curve animating the same attribute
+    ///
+    /// The merged curve has a keyframe at each timestamp of both curves, sampled from `other`
+    /// where it has keyframes around the timestamp and from this curve elsewhere, as `other` is
+    /// applied over this curve. It keeps the settings of this curve. Returns `None` if the curves
+    /// can't be sampled at their timestamps, or are inverse kinematics goals in different spaces.
+    fn merged_with(&self, other: &VariableCurve) -> Option<VariableCurve> {
+        let mut timestamps: Vec<f32> = self
+            .keyframe_timestamps
+            .iter()
+            .chain(&other.keyframe_timestamps)
+            .copied()
+            .collect();
+        timestamps.sort_by(f32::total_cmp);
+        timestamps.dedup();
+        fn values<T>(
+            timestamps: &[f32],
+            (curve, other): (&VariableCurve, &VariableCurve),
+            sample: impl Fn(&VariableCurve, f32) -> Option<T>,
+        ) -> Option<Vec<T>> {
+            timestamps
+                .iter()
+                .map(|&time| {
+                    let covered = matches!(
+                        (other.keyframe_timestamps.first(), other.keyframe_timestamps.last()),
+                        (Some(&first), Some(&last)) if first <= time && time <= last
+                    );
+                    sample(if covered { other } else { curve }, time)
+                })
+                .collect()
+        }
+        let curves = (self, other);
+        let keyframes = match (&self.keyframes, &other.keyframes) {
+            (Keyframes::Rotation(_) | Keyframes::QuantizedRotation(_), _) => {
+                Keyframes::Rotation(values(&timestamps, curves, VariableCurve::sample_rotation)?)
+            }
+            (Keyframes::Translation(_) | Keyframes::QuantizedTranslation(_), _) => {
+                Keyframes::Translation(values(
+                    &timestamps,
+                    curves,
+                    VariableCurve::sample_translation,
+                )?)
+            }
+            (Keyframes::Scale(_) | Keyframes::QuantizedScale(_), _) => {
+                Keyframes::Scale(values(&timestamps, curves, VariableCurve::sample_scale)?)
+            }
+            (Keyframes::Scalar(_), _) => {
+                Keyframes::Scalar(values(&timestamps, curves, VariableCurve::sample_scalar)?)
+            }
+            (
+                Keyframes::IkGoal { space, .. },
+                Keyframes::IkGoal {
+                    space: other_space, ..
+                },
+            ) if space == other_space => Keyframes::IkGoal {
+                space: *space,
+                positions: values(&timestamps, curves, VariableCurve::sample_ik_goal)?,
+            },
+            (Keyframes::IkGoal { .. }, _) => return None,
+            (Keyframes::Trs(_), _) => {
+                Keyframes::Trs(values(&timestamps, curves, VariableCurve::sample_trs)?)
+            }
+            (Keyframes::SpriteIndex(_), _) => Keyframes::SpriteIndex(values(
+                &timestamps,
+                curves,
+                VariableCurve::sample_sprite_index,
+            )?),
+        };
+        let quantized = matches!(
+            self.keyframes,
+            Keyframes::QuantizedRotation(_)
+                | Keyframes::QuantizedTranslation(_)
+                | Keyframes::QuantizedScale(_)
+        );
+        Some(VariableCurve {
+            keyframe_timestamps: timestamps,
+            keyframes: if quantized {
+                keyframes.quantize()
+            } else {
+                keyframes
+            },
+            ..self.clone()
+        })
+    }
+
+    /// Problems of this curve, the curve at `index` of `path`, as found by
+    /// [`AnimationClip::validate`]
+    fn validate(&self, path: &EntityPath, index: usize) -> Vec<AnimationClipError> {
+        let mut errors = Vec::new();
+        let timestamps = &self.keyframe_timestamps;
+        if timestamps.is_empty() || self.keyframes.len() == 0 {
+            errors.push(AnimationClipError::EmptyCurve {
+                path: path.clone(),
+                curve: index,
+            });
+        }
+        if timestamps.len() != self.keyframes.len() {
+            errors.push(AnimationClipError::MismatchedLengths {
+                path: path.clone(),
+                curve: index,
+                timestamps: timestamps.len(),
+                keyframes: self.keyframes.len(),
+            });
+        }
+        if !self.has_sorted_timestamps() {
+            errors.push(AnimationClipError::UnsortedTimestamps {
+                path: path.clone(),
+                curve: index,
+            });
+        }
+        for pair in timestamps.windows(2) {
+            if pair[0] == pair[1] {
+                errors.push(AnimationClipError::ZeroLengthSegment {
+                    path: path.clone(),
+                    curve: index,
+                    time: pair[0],
+                });
+            }
+        }
+        if !timestamps.iter().all(|time| time.is_finite()) || !self.keyframes.is_finite() {
+            errors.push(AnimationClipError::NonFiniteValue {
+                path: path.clone(),
+                curve: index,
+            });
+        }
+        errors
+    }
+
+    /// Feed the data of the curve to the hasher of [`AnimationClip::fingerprint`]
+    fn hash_into(&self, hasher: &mut Fnv1aHasher) {
+        hasher.write_usize(self.keyframe_timestamps.len());
+        for &timestamp in &self.keyframe_timestamps {
+            hasher.write_f32(timestamp);
+        }
+        let (kind, values): (u8, Vec<f32>) = match &self.keyframes {
+            Keyframes::Rotation(keyframes) => {
+                (0, keyframes.iter().flat_map(|q| q.to_array()).collect())
+            }
+            Keyframes::Translation(keyframes) => {
+                (1, keyframes.iter().flat_map(|v| v.to_array()).collect())
+            }
+            Keyframes::Scale(keyframes) => {
+                (2, keyframes.iter().flat_map(|v| v.to_array()).collect())
+            }
+            Keyframes::QuantizedRotation(keyframes) => {
+                (3, keyframes.iter().flatten().map(|&c| c as f32).collect())
+            }
+            Keyframes::QuantizedTranslation(keyframes) => (4, keyframes.raw_components()),
+            Keyframes::QuantizedScale(keyframes) => (5, keyframes.raw_components()),
+            Keyframes::Scalar(keyframes) => (6, keyframes.clone()),
+            Keyframes::IkGoal { space, positions } => (
+                7 + *space as u8,
+                positions.iter().flat_map(|v| v.to_array()).collect(),
+            ),
+            Keyframes::Trs(keyframes) => (
+                9,
+                keyframes
+                    .iter()
+                    .flat_map(|t| {
+                        t.translation
+                            .to_array()
+                            .into_iter()
+                            .chain(t.rotation.to_array())
+                            .chain(t.scale.to_array())
+                    })
+                    .collect(),
+            ),
+            Keyframes::SpriteIndex(keyframes) => {
+                (10, keyframes.iter().map(|&index| index as f32).collect())
+            }
+        };
+        hasher.write(&[kind]);
+        hasher.write_usize(values.len());
+        for value in values {
+            hasher.write_f32(value);
+        }
+        hasher.write(&[
+            self.take_shortest_path as u8,
+            self.edge_behavior as u8,
+            self.interpolation as u8,
+        ]);
+        hasher.write_f32(self.weight);
+        match self.pivot {
+            Some(pivot) => {
+                hasher.write(&[1]);
+                for component in pivot.to_array() {
+                    hasher.write_f32(component);
                 }
             }
+            None => hasher.write(&[0]),
+        }
+        match self.scale_blend {
+            Some(scale_blend) => hasher.write(&[1, scale_blend as u8]),
+            None => hasher.write(&[0]),
         }
+        hasher.write(&[
+            self.enabled as u8,
+            self.clamp_scalar as u8,
+            self.preserve_scale_sign as u8,
+        ]);
     }
-}
 
-/// Adds animation support to an app
-#[derive(Default)]
-pub struct AnimationPlugin {}
+    /// Are the keyframe timestamps sorted in non-decreasing order
+    ///
+    /// Sampling a curve with unsorted timestamps gives incorrect results.
+    pub fn has_sorted_timestamps(&self) -> bool {
+        self.keyframe_timestamps
+            .windows(2)
+            .all(|timestamps| timestamps[0] <= timestamps[1])
+    }
 
-impl Plugin for AnimationPlugin {
-    fn build(&self, app: &mut App) {
-        app.add_asset::<AnimationClip>()
-            .register_type::<AnimationPlayer>()
-            .add_system_to_stage(
-                CoreStage::PostUpdate,
-                animation_player.before(TransformSystem::TransformPropagate),
-            );
+    /// Sample the rotation of this curve at `time`
+    ///
+    /// Returns `None` if this is not a rotation curve, or if `time` is outside of the curve and its
+    /// [edge behavior](EdgeBehavior) is `Skip`.
+    pub fn sample_rotation(&self, time: f32) -> Option<Quat> {
+        self.sample_rotation_looping(time, None, self.take_shortest_path, None)
+    }
+
+    /// Sample the translation of this curve at `time`
+    ///
+    /// Returns `None` if this is not a translation curve, or if `time` is outside of the curve and
+    /// its [edge behavior](EdgeBehavior) is `Skip`.
+    pub fn sample_translation(&self, time: f32) -> Option<Vec3> {
+        self.sample_translation_looping(time, None, None)
+    }
+
+    /// Sample the scale of this curve at `time`
+    ///
+    /// Returns `None` if this is not a scale curve, or if `time` is outside of the curve and its
+    /// [edge behavior](EdgeBehavior) is `Skip`.
+    pub fn sample_scale(&self, time: f32) -> Option<Vec3> {
+        self.sample_scale_looping(time, None, None)
+    }
+
+    /// Sample the value of this curve at `time`
+    ///
+    /// Returns `None` if this is not a [scalar](Keyframes::Scalar) curve, or if `time` is outside
+    /// of the curve and its [edge behavior](EdgeBehavior) is `Skip`.
+    pub fn sample_scalar(&self, time: f32) -> Option<f32> {
+        self.sample_scalar_with(time, None)
+    }
+
+    /// Sample the position of the inverse kinematics goal of this curve at `time`
+    ///
+    /// The position is in the [space](IkGoalSpace) of the keyframes. Returns `None` if this is not
+    /// an [inverse kinematics goal](Keyframes::IkGoal) curve, or if `time` is outside of the curve
+    /// and its [edge behavior](EdgeBehavior) is `Skip`.
+    pub fn sample_ik_goal(&self, time: f32) -> Option<Vec3> {
+        self.sample_ik_goal_with(time, None)
+    }
+
+    /// Sample the translation, rotation and scale of this curve at `time`
+    ///
+    /// Returns `None` if this is not a [`Keyframes::Trs`] curve, or if `time` is outside of the
+    /// curve and its [edge behavior](EdgeBehavior) is `Skip`.
+    pub fn sample_trs(&self, time: f32) -> Option<Transform> {
+        self.sample_trs_looping(time, None, self.take_shortest_path, None)
+    }
+
+    /// Sample the sprite index of this curve at `time`
+    ///
+    /// The index of the last keyframe at or before `time` is returned. Returns `None` if this is
+    /// not a [sprite index](Keyframes::SpriteIndex) curve, or if `time` is outside of the curve and
+    /// its [edge behavior](EdgeBehavior) is `Skip`.
+    pub fn sample_sprite_index(&self, time: f32) -> Option<usize> {
+        self.sample_sprite_index_with(time, None)
+    }
+
+    /// Same as [`sample_sprite_index`](VariableCurve::sample_sprite_index), starting the search
+    /// of the keyframes from `cursor`
+    fn sample_sprite_index_with(
+        &self,
+        time: f32,
+        cursor: Option<&mut CurveCursor>,
+    ) -> Option<usize> {
+        let (step_start, step_end, lerp) = self.find_keyframe(time, None, cursor)?;
+        // Only reached exactly on the last keyframe
+        let index = if lerp >= 1.0 { step_end } else { step_start };
+        self.keyframes.sprite_index(index)
+    }
+
+    /// Same as [`sample_scalar`](VariableCurve::sample_scalar), starting the search of the
+    /// keyframes from `cursor`
+    fn sample_scalar_with(&self, time: f32, cursor: Option<&mut CurveCursor>) -> Option<f32> {
+        self.sample(
+            time,
+            None,
+            cursor,
+            |index| self.keyframes.scalar(index),
+            catmull_rom,
+            |start, end, lerp| start + (end - start) * lerp,
+        )
+        .map(|value| {
+            if self.clamp_scalar {
+                value.clamp(0.0, 1.0)
+            } else {
+                value
+            }
+        })
+    }
+
+    /// Same as [`sample_ik_goal`](VariableCurve::sample_ik_goal), starting the search of the
+    /// keyframes from `cursor`
+    fn sample_ik_goal_with(&self, time: f32, cursor: Option<&mut CurveCursor>) -> Option<Vec3> {
+        self.sample(
+            time,
+            None,
+            cursor,
+            |index| self.keyframes.ik_goal(index),
+            catmull_rom,
+            Vec3::lerp,
+        )
+    }
+
+    /// Same as [`sample_rotation`](VariableCurve::sample_rotation), interpolating from the last
+    /// keyframe back to the first one outside of the curve if it loops over `loop_duration`
+    ///
+    /// `take_shortest_path` overrides the field of the same name.
+    fn sample_rotation_looping(
+        &self,
+        time: f32,
+        loop_duration: Option<f32>,
+        take_shortest_path: bool,
+        cursor: Option<&mut CurveCursor>,
+    ) -> Option<Quat> {
+        self.sample(
+            time,
+            loop_duration,
+            cursor,
+            |index| self.keyframes.rotation(index),
+            catmull_rom_rotation,
+            |start, end, lerp| interpolate_rotation(start, end, lerp, take_shortest_path),
+        )
+    }
+
+    /// Sample the keyframes returned by `keyframe` at `time`, following the interpolation of the
+    /// curve
+    ///
+    /// `linear` interpolates between two keyframes, and `cubic` along the spline going through
+    /// four consecutive keyframes.
+    fn sample<T: Copy>(
+        &self,
+        time: f32,
+        loop_duration: Option<f32>,
+        cursor: Option<&mut CurveCursor>,
+        keyframe: impl Fn(usize) -> Option<T>,
+        cubic: impl Fn([T; 4], f32) -> T,
+        linear: impl Fn(T, T, f32) -> T,
+    ) -> Option<T> {
+        let (step_start, step_end, lerp) = self.find_keyframe(time, loop_duration, cursor)?;
+        match self.interpolation {
+            Interpolation::Linear => interpolate(&keyframe, step_start, step_end, lerp, linear),
+            Interpolation::Step => {
+                interpolate(&keyframe, step_start, step_end, lerp, |start, end, lerp| {
+                    // Only reached exactly on the last keyframe
+                    if lerp >= 1.0 {
+                        end
+                    } else {
+                        start
+                    }
+                })
+            }
+            Interpolation::Cubic => {
+                // When looping from the last keyframe to the first one, these are the keyframes
+                // before the last one and after the first one
+                let before = step_start.checked_sub(1).and_then(&keyframe);
+                let after = keyframe(step_end + 1);
+                interpolate(&keyframe, step_start, step_end, lerp, |start, end, lerp| {
+                    cubic(
+                        [before.unwrap_or(start), start, end, after.unwrap_or(end)],
+                        lerp,
+                    )
+                })
+            }
+        }
+    }
+
+    /// Find the keyframes to interpolate between at `time`, following the edge behavior outside of
+    /// the curve
+    ///
+    /// The segment of `cursor` and the one after it are tried before searching all the keyframes,
+    /// and `cursor` is moved to the segment found.
+    fn find_keyframe(
+        &self,
+        time: f32,
+        loop_duration: Option<f32>,
+        cursor: Option<&mut CurveCursor>,
+    ) -> Option<(usize, usize, f32)> {
+        let timestamps = &self.keyframe_timestamps;
+        let search = || {
+            self.uniform_step
+                .and_then(|step| find_keyframe_uniform(timestamps, time, step))
+                .or_else(|| find_keyframe_looping(timestamps, time, loop_duration))
+        };
+        let found = match cursor {
+            Some(cursor) => {
+                let found = find_keyframe_near(timestamps, time, cursor.step_start).or_else(search);
+                if let Some((step_start, step_end, _)) = found {
+                    if step_end == step_start + 1 {
+                        cursor.step_start = step_start;
+                    }
+                }
+                found
+            }
+            None => search(),
+        };
+        match found {
+            None if self.edge_behavior == EdgeBehavior::Clamp && !timestamps.is_empty() => {
+                let edge = if time < timestamps[0] {
+                    0
+                } else {
+                    timestamps.len() - 1
+                };
+                Some((edge, edge, 0.0))
+            }
+            found => found,
+        }
+    }
+
+    /// Same as [`sample_translation`](VariableCurve::sample_translation), interpolating from the
+    /// last keyframe back to the first one outside of the curve if it loops over `loop_duration`
+    fn sample_translation_looping(
+        &self,
+        time: f32,
+        loop_duration: Option<f32>,
+        cursor: Option<&mut CurveCursor>,
+    ) -> Option<Vec3> {
+        self.sample(
+            time,
+            loop_duration,
+            cursor,
+            |index| self.keyframes.translation(index),
+            catmull_rom,
+            Vec3::lerp,
+        )
+    }
+
+    /// Same as [`sample_scale`](VariableCurve::sample_scale), interpolating from the last
+    /// keyframe back to the first one outside of the curve if it loops over `loop_duration`
+    fn sample_scale_looping(
+        &self,
+        time: f32,
+        loop_duration: Option<f32>,
+        cursor: Option<&mut CurveCursor>,
+    ) -> Option<Vec3> {
+        self.sample(
+            time,
+            loop_duration,
+            cursor,
+            |index| self.keyframes.scale(index),
+            |points, t| self.flip_scale(catmull_rom(points, t), points[1], points[2], t),
+            |start, end, t| self.flip_scale(start.lerp(end, t), start, end, t),
+        )
+    }
+
+    /// Same as [`sample_trs`](VariableCurve::sample_trs), interpolating from the last keyframe
+    /// back to the first one outside of the curve if it loops over `loop_duration`
+    ///
+    /// `take_shortest_path` overrides the field of the same name.
+    fn sample_trs_looping(
+        &self,
+        time: f32,
+        loop_duration: Option<f32>,
+        take_shortest_path: bool,
+        cursor: Option<&mut CurveCursor>,
+    ) -> Option<Transform> {
+        self.sample(
+            time,
+            loop_duration,
+            cursor,
+            |index| self.keyframes.trs(index),
+            |[a, b, c, d], t| Transform {
+                translation: catmull_rom([a, b, c, d].map(|p| p.translation), t),
+                rotation: catmull_rom_rotation([a, b, c, d].map(|p| p.rotation), t),
+                scale: self.flip_scale(
+                    catmull_rom([a, b, c, d].map(|p| p.scale), t),
+                    b.scale,
+                    c.scale,
+                    t,
+                ),
+            },
+            |start, end, lerp| Transform {
+                translation: start.translation.lerp(end.translation, lerp),
+                rotation: interpolate_rotation(
+                    start.rotation,
+                    end.rotation,
+                    lerp,
+                    take_shortest_path,
+                ),
+                scale: self.flip_scale(
+                    start.scale.lerp(end.scale, lerp),
+                    start.scale,
+                    end.scale,
+                    lerp,
+                ),
+            },
+        )
+    }
+
+    /// Replace the components of `scale`, interpolated between `start` and `end` at `t`, that
+    /// change sign between them, if the curve [preserves](VariableCurve::preserve_scale_sign) it
+    fn flip_scale(&self, scale: Vec3, start: Vec3, end: Vec3, t: f32) -> Vec3 {
+        if !self.preserve_scale_sign {
+            return scale;
+        }
+        let sign = if t < 0.5 {
+            start.signum()
+        } else {
+            end.signum()
+        };
+        let flipped = start.abs().lerp(end.abs(), t) * sign;
+        Vec3::select((start * end).cmplt(Vec3::ZERO), flipped, scale)
+    }
+}
+
+/// Remembers where the last sample of a [`VariableCurve`] was, to sample it again faster at the
+/// same or a nearby time
+///
+/// Sampling a curve searches through all its keyframes, which adds up when sampling it many times
+/// per frame, for example to draw gizmos. The results are the same as the `sample_*` methods of
+/// [`VariableCurve`]. A cursor can be used with any curve, but is only faster with the one it last
+/// sampled.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CurveCursor {
+    step_start: usize,
+}
+
+impl CurveCursor {
+    /// Same as [`VariableCurve::sample_rotation`]
+    pub fn sample_rotation(&mut self, curve: &VariableCurve, time: f32) -> Option<Quat> {
+        curve.sample_rotation_looping(time, None, curve.take_shortest_path, Some(self))
+    }
+
+    /// Same as [`VariableCurve::sample_translation`]
+    pub fn sample_translation(&mut self, curve: &VariableCurve, time: f32) -> Option<Vec3> {
+        curve.sample_translation_looping(time, None, Some(self))
+    }
+
+    /// Same as [`VariableCurve::sample_scale`]
+    pub fn sample_scale(&mut self, curve: &VariableCurve, time: f32) -> Option<Vec3> {
+        curve.sample_scale_looping(time, None, Some(self))
+    }
+
+    /// Same as [`VariableCurve::sample_scalar`]
+    pub fn sample_scalar(&mut self, curve: &VariableCurve, time: f32) -> Option<f32> {
+        curve.sample_scalar_with(time, Some(self))
+    }
+
+    /// Same as [`VariableCurve::sample_sprite_index`]
+    pub fn sample_sprite_index(&mut self, curve: &VariableCurve, time: f32) -> Option<usize> {
+        curve.sample_sprite_index_with(time, Some(self))
+    }
+
+    /// Same as [`VariableCurve::sample_ik_goal`]
+    pub fn sample_ik_goal(&mut self, curve: &VariableCurve, time: f32) -> Option<Vec3> {
+        curve.sample_ik_goal_with(time, Some(self))
+    }
+
+    /// Same as [`VariableCurve::sample_trs`]
+    pub fn sample_trs(&mut self, curve: &VariableCurve, time: f32) -> Option<Transform> {
+        curve.sample_trs_looping(time, None, curve.take_shortest_path, Some(self))
+    }
+}
+
+/// Number of bytes allocated by `values`, including its unused capacity
+fn vec_heap_size<T>(values: &Vec<T>) -> usize {
+    values.capacity() * std::mem::size_of::<T>()
+}
+
+/// 64-bit FNV-1a hasher, used to hash clips the same way across runs and platforms
+struct Fnv1aHasher(u64);
+
+impl Default for Fnv1aHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Fnv1aHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn write_usize(&mut self, value: usize) {
+        self.write(&(value as u64).to_le_bytes());
+    }
+
+    fn write_f32(&mut self, value: f32) {
+        // `-0.0 == 0.0` but they have different bits
+        let value = if value == 0.0 { 0.0 } else { value };
+        self.write(&value.to_bits().to_le_bytes());
+    }
+}
+
+/// Find the keyframes to interpolate between at `time`, and how far `time` is toward the second
+/// one
+///
+/// Returns `None` if `time` is before the first or after the last timestamp.
+fn find_keyframe(keyframe_timestamps: &[f32], time: f32) -> Option<(usize, usize, f32)> {
+    // Some curves have only one keyframe used to set a transform
+    if keyframe_timestamps.len() == 1 {
+        return Some((0, 0, 0.0));
+    }
+
+    // PERF: finding the current keyframe can be optimised
+    let step_start =
+        match keyframe_timestamps.binary_search_by(|probe| probe.partial_cmp(&time).unwrap()) {
+            // Exactly on the last keyframe, interpolate to the end of the last step
+            Ok(i) => i.min(keyframe_timestamps.len() - 2),
+            Err(0) => return None, // this curve isn't started yet
+            Err(n) if n > keyframe_timestamps.len() - 1 => return None, // this curve is finished
+            Err(i) => i - 1,
+        };
+    Some(segment(keyframe_timestamps, step_start, time))
+}
+
+/// Same as [`find_keyframe`], but only looks at the segment starting at keyframe `step_start` and
+/// the one after it
+///
+/// Returns `None` if `time` is in neither of them.
+fn find_keyframe_near(
+    keyframe_timestamps: &[f32],
+    time: f32,
+    step_start: usize,
+) -> Option<(usize, usize, f32)> {
+    let last = keyframe_timestamps.len().checked_sub(1)?;
+    (step_start..(step_start + 2).min(last))
+        .find(|&start| {
+            keyframe_timestamps[start] <= time
+                && (time < keyframe_timestamps[start + 1]
+                    || (start + 1 == last && time == keyframe_timestamps[last]))
+        })
+        .map(|start| segment(keyframe_timestamps, start, time))
+}
+
+/// Same as [`find_keyframe`], for keyframes evenly spaced by `step`, computing the segment from
+/// `time` instead of searching it
+///
+/// Returns `None` if `time` is outside of the curve, or if the keyframes around `time` are not
+/// spaced by `step`.
+fn find_keyframe_uniform(
+    keyframe_timestamps: &[f32],
+    time: f32,
+    step: f32,
+) -> Option<(usize, usize, f32)> {
+    let first = *keyframe_timestamps.first()?;
+    let last = keyframe_timestamps.len().checked_sub(1)?;
+    if step <= 0.0 || last == 0 || time < first {
+        return None;
+    }
+    let step_start = (((time - first) / step) as usize).min(last - 1);
+    // Rounding errors can land on a neighbouring segment
+    find_keyframe_near(keyframe_timestamps, time, step_start.saturating_sub(1))
+}
+
+/// The segment from keyframe `step_start` to the next one, and how far `time` is along it
+fn segment(keyframe_timestamps: &[f32], step_start: usize, time: f32) -> (usize, usize, f32) {
+    let ts_start = keyframe_timestamps[step_start];
+    let ts_end = keyframe_timestamps[step_start + 1];
+    // Two keyframes at the same instant make a step instead of a segment to interpolate
+    let lerp = if ts_end == ts_start {
+        0.0
+    } else {
+        (time - ts_start) / (ts_end - ts_start)
+    };
+    (step_start, step_start + 1, lerp)
+}
+
+/// Same as [`find_keyframe`], but if the curve loops over `loop_duration`, a time outside of the
+/// curve is in a segment going from the last keyframe to the first one
+fn find_keyframe_looping(
+    keyframe_timestamps: &[f32],
+    time: f32,
+    loop_duration: Option<f32>,
+) -> Option<(usize, usize, f32)> {
+    let found = find_keyframe(keyframe_timestamps, time);
+    match (found, loop_duration) {
+        (None, Some(duration)) if keyframe_timestamps.len() > 1 => {
+            let first = keyframe_timestamps[0];
+            let last = keyframe_timestamps.len() - 1;
+            let gap = duration - keyframe_timestamps[last] + first;
+            if gap <= 0.0 {
+                return None;
+            }
+            let since_last = if time < first {
+                time + duration - keyframe_timestamps[last]
+            } else {
+                time - keyframe_timestamps[last]
+            };
+            Some((last, 0, since_last / gap))
+        }
+        _ => found,
+    }
+}
+
+/// Weighted average of `rotations`, as `(rotation, weight)`, independent of their order
+///
+/// Chaining [`Quat::slerp`] to blend three or more rotations gives a result that depends on the
+/// order they are blended in. This instead sums the rotations scaled by their weight, flipping the
+/// ones in the opposite hemisphere of the sum so far, and normalizes the sum. This is accurate for
+/// rotations within about 90° of each other, as is the case for the clips of a blend.
+///
+/// Returns `None` if the weights sum to `0.0`, or if the rotations cancel each other out.
+pub fn average_rotations(rotations: &[(Quat, f32)]) -> Option<Quat> {
+    let sum = rotations
+        .iter()
+        .fold(Vec4::ZERO, |sum, &(rotation, weight)| {
+            // `q` and `-q` are the same rotation, but would cancel each other out
+            let rotation = Vec4::from(rotation);
+            if rotation.dot(sum) < 0.0 {
+                sum - rotation * weight
+            } else {
+                sum + rotation * weight
+            }
+        });
+    let length = sum.length();
+    if length > f32::EPSILON {
+        Some(Quat::from_vec4(sum / length))
+    } else {
+        None
+    }
+}
+
+/// Interpolate between two rotation keyframes, along the shortest path or not
+fn interpolate_rotation(start: Quat, mut end: Quat, lerp: f32, take_shortest_path: bool) -> Quat {
+    if !take_shortest_path {
+        return slerp_unaligned(start.normalize(), end.normalize(), lerp);
+    }
+    // Choose the smallest angle for the rotation
+    if end.dot(start) < 0.0 {
+        end = -end;
+    }
+    // Rotations are using a spherical linear interpolation
+    start.normalize().slerp(end.normalize(), lerp)
+}
+
+/// Spherical linear interpolation that doesn't choose the smallest angle between `start` and `end`
+///
+/// Quaternions almost opposite to each other represent a full turn around an undefined axis, in
+/// which case the shortest path is used.
+fn slerp_unaligned(start: Quat, end: Quat, s: f32) -> Quat {
+    const DOT_THRESHOLD: f32 = 0.9995;
+
+    let dot = start.dot(end);
+    if dot > DOT_THRESHOLD {
+        (start * (1.0 - s) + end * s).normalize()
+    } else if dot < -DOT_THRESHOLD {
+        start.slerp(end, s)
+    } else {
+        let theta = dot.acos();
+        (start * (theta * (1.0 - s)).sin() + end * (theta * s).sin()) * theta.sin().recip()
+    }
+}
+
+/// Point at `t` on the uniform Catmull-Rom spline segment between `points[1]` and `points[2]`
+fn catmull_rom<T>(points: [T; 4], t: f32) -> T
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<f32, Output = T>,
+{
+    let [p0, p1, p2, p3] = points;
+    let t2 = t * t;
+    let t3 = t2 * t;
+    (p1 * 2.0
+        + (p2 - p0) * t
+        + (p0 * 2.0 - p1 * 5.0 + p2 * 4.0 - p3) * t2
+        + (p1 * 3.0 - p0 - p2 * 3.0 + p3) * t3)
+        * 0.5
+}
+
+/// Same as [`catmull_rom`] for rotations, with the quaternions aligned to go along the shortest
+/// path
+fn catmull_rom_rotation(rotations: [Quat; 4], t: f32) -> Quat {
+    let mut points = rotations.map(|rotation| Vec4::from(rotation.normalize()));
+    for index in 1..4 {
+        if points[index].dot(points[index - 1]) < 0.0 {
+            points[index] = -points[index];
+        }
+    }
+    Quat::from_vec4(catmull_rom(points, t)).normalize()
+}
+
+/// Interpolate between the keyframes at `step_start` and `step_end`
+///
+/// Returns `None` if there is no keyframe at `step_start`.
+fn interpolate<T>(
+    keyframe: impl Fn(usize) -> Option<T>,
+    step_start: usize,
+    step_end: usize,
+    lerp: f32,
+    interpolation: impl Fn(T, T, f32) -> T,
+) -> Option<T> {
+    let start = keyframe(step_start)?;
+    match keyframe(step_end) {
+        Some(end) if step_end != step_start => Some(interpolation(start, end, lerp)),
+        _ => Some(start),
+    }
+}
+
+/// Remaps the time of an animation, to slow down or speed up parts of a clip
+///
+/// Both `keyframe_timestamps` and `keyframes` are normalized times, `0.0` being the start of the
+/// clip and `1.0` its end. A time before the first timestamp or after the last one is mapped to
+/// the first or last keyframe.
+///
+/// The keyframes should be monotonically increasing. A time warp that goes back in time plays
+/// parts of the animation in reverse, and seeking in such an animation gives unexpected results.
+#[derive(Reflect, FromReflect, Clone, Debug, Default)]
+pub struct TimeWarp {
+    /// Normalized timestamp for each of the keyframes.
+    pub keyframe_timestamps: Vec<f32>,
+    /// Normalized time of the clip at each keyframe.
+    pub keyframes: Vec<f32>,
+}
+
+impl TimeWarp {
+    /// Remap the normalized time `time` of an animation
+    pub fn sample(&self, time: f32) -> f32 {
+        if self.keyframes.is_empty() {
+            return time;
+        }
+        sample_scalar(&self.keyframe_timestamps, &self.keyframes, time)
+    }
+}
+
+/// Linearly interpolate the scalar keyframes `values` at `time`, clamping to the first and last
+/// keyframes outside of the curve
+///
+/// `values` must not be empty.
+fn sample_scalar(keyframe_timestamps: &[f32], values: &[f32], time: f32) -> f32 {
+    match (
+        find_keyframe(keyframe_timestamps, time),
+        keyframe_timestamps.first(),
+    ) {
+        (Some((step_start, step_end, lerp)), _) => interpolate(
+            |index| values.get(index).copied(),
+            step_start,
+            step_end,
+            lerp,
+            |start, end, lerp| start + (end - start) * lerp,
+        )
+        .unwrap_or(values[values.len() - 1]),
+        (None, Some(start)) if time >= *start => values[values.len() - 1],
+        (None, _) => values[0],
+    }
+}
+
+/// Path to an entity, with [`Name`]s. Each entity in a path must have a name.
+///
+/// The first part is the name of the animation root, the entity with the [`AnimationPlayer`]
+/// unless it has a [root offset](AnimationPlayer::set_root_offset). A path with a single part
+/// targets the animation root itself.
+#[derive(Reflect, FromReflect, Clone, Debug, Hash, PartialEq, Eq, Default)]
+#[reflect_value(Debug, Hash, PartialEq)]
+pub struct EntityPath {
+    /// Parts of the path
+    pub parts: Vec<Name>,
+}
+
+/// Marks an entity as the target of `path` in the animations of the [`AnimationPlayer`] on
+/// `root`
+///
+/// Targets are added to the cache of the player when this component is added or changed, or when
+/// the cache no longer has their path, for example after changing the
+/// [root offset](AnimationPlayer::set_root_offset) of the player. Their path doesn't have to be
+/// resolved by walking the [`Children`] and [`Name`]s of the hierarchy, even when several
+/// siblings share a name. The entity should keep the [`Name`] of the last part of `path`, which
+/// is checked to know if the cached entity is still valid.
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component, MapEntities)]
+pub struct AnimationTarget {
+    /// Entity of the [`AnimationPlayer`]
+    pub root: Entity,
+    /// Path of this entity in the animations of the player
+    pub path: EntityPath,
+}
+
+impl MapEntities for AnimationTarget {
+    fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+        self.root = entity_map.get(self.root)?;
+        Ok(())
+    }
+}
+
+// This is required for `AnimationTarget` to be reflected as a component, `root` is set when the
+// component is inserted.
+impl FromWorld for AnimationTarget {
+    fn from_world(_world: &mut World) -> Self {
+        AnimationTarget {
+            root: Entity::from_raw(u32::MAX),
+            path: EntityPath::default(),
+        }
+    }
+}
+
+/// A list of [`VariableCurve`], and the [`EntityPath`] to which they apply.
+#[derive(Reflect, Clone, TypeUuid, Debug, Default)]
+#[uuid = "d81b7179-0448-4eb0-89fe-c067222725bf"]
+pub struct AnimationClip {
+    curves: HashMap<EntityPath, Vec<VariableCurve>>,
+    /// Keys of `curves`, in the order they were added, to apply them in the same order every run
+    paths: Vec<EntityPath>,
+    duration: f32,
+    loop_points: Option<(f32, f32)>,
+    interpolate_loop: bool,
+    preserve_rotation_winding: bool,
+    space: CurveSpace,
+    metadata: HashMap<String, String>,
+    interval_events: Vec<(f32, f32, u32)>,
+    default_repeat: Option<RepeatAnimation>,
+}
+
+impl AnimationClip {
+    #[inline]
+    /// Hashmap of the [`VariableCurve`]s per [`EntityPath`].
+    pub fn curves(&self) -> &HashMap<EntityPath, Vec<VariableCurve>> {
+        &self.curves
+    }
+
+    /// Curves of `path` animating the attribute `kind`, in the order they were added
+    pub fn curves_of_kind<'a>(
+        &'a self,
+        path: &EntityPath,
+        kind: CurveKind,
+    ) -> impl Iterator<Item = &'a VariableCurve> {
+        self.curves
+            .get(path)
+            .into_iter()
+            .flatten()
+            .filter(move |curve| curve.keyframes.kind() == kind)
+    }
+
+    /// First curve of `path` animating the attribute `kind`
+    ///
+    /// A path can have several curves for the same attribute, which are applied in order, each
+    /// blending over the previous ones. Only the first one is returned, see
+    /// [`curves_of_kind`](AnimationClip::curves_of_kind) to get all of them.
+    pub fn get_curve(&self, path: &EntityPath, kind: CurveKind) -> Option<&VariableCurve> {
+        self.curves_of_kind(path, kind).next()
+    }
+
+    /// Returns `true` if the clip has no curves
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.curves.is_empty()
+    }
+
+    /// Iterator over the [`EntityPath`]s targeted by this clip, in the order they were added
+    ///
+    /// The paths are animated in this order, which is the same on every run.
+    #[inline]
+    pub fn paths(&self) -> impl Iterator<Item = &EntityPath> {
+        self.paths.iter()
+    }
+
+    /// Number of entities animated by this clip, one per [`EntityPath`]
+    #[inline]
+    pub fn bone_count(&self) -> usize {
+        self.curves.len()
+    }
+
+    /// Number of curves in this clip, over all its [`EntityPath`]s
+    pub fn curve_count(&self) -> usize {
+        self.curves.values().map(Vec::len).sum()
+    }
+
+    /// Duration of the clip, represented in seconds
+    #[inline]
+    pub fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    /// Time range covered by the keyframes of the curves of an [`EntityPath`], as `(start, end)`
+    ///
+    /// This can be shorter than the [`duration`](AnimationClip::duration) of the clip. Returns
+    /// `None` if the clip has no keyframes for this path.
+    pub fn path_time_range(&self, path: &EntityPath) -> Option<(f32, f32)> {
+        self.curves
+            .get(path)?
+            .iter()
+            .filter_map(|curve| {
+                Some((
+                    *curve.keyframe_timestamps.first()?,
+                    *curve.keyframe_timestamps.last()?,
+                ))
+            })
+            .reduce(|(start, end), (curve_start, curve_end)| {
+                (start.min(curve_start), end.max(curve_end))
+            })
+    }
+
+    /// Loop points of the clip as `(loop_start, intro_end)`, if any
+    pub fn loop_points(&self) -> Option<(f32, f32)> {
+        self.loop_points
+    }
+
+    /// Set the loop points of the clip as `(loop_start, intro_end)`
+    ///
+    /// A clip with loop points is played from its start to `intro_end`, then loops between
+    /// `loop_start` and `intro_end` until [`PlayingAnimation::play_outro`] is called, after which
+    /// the rest of the clip is played to its end. `loop_start` must be lower than `intro_end`,
+    /// otherwise the loop points are removed with a warning, and loop points only apply to
+    /// animations playing forward.
+    pub fn set_loop_points(&mut self, loop_points: Option<(f32, f32)>) {
+        self.loop_points = match loop_points {
+            Some((loop_start, intro_end)) if loop_start < intro_end => loop_points,
+            Some((loop_start, intro_end)) => {
+                warn!(
+                    "Ignoring loop points ({}, {}): the loop must start before it ends",
+                    loop_start, intro_end
+                );
+                None
+            }
+            None => None,
+        };
+    }
+
+    /// Does a looping animation interpolate from the last keyframes of the curves back to the
+    /// first ones
+    pub fn interpolates_loop(&self) -> bool {
+        self.interpolate_loop
+    }
+
+    /// Set whether a looping animation interpolates from the last keyframes of the curves back to
+    /// the first ones
+    ///
+    /// By default, a curve holds its last keyframe until the end of the clip and snaps back to its
+    /// first keyframe when the animation loops. With loop interpolation, the time between the
+    /// last keyframe and the end of the clip, plus the time before the first keyframe, is used to
+    /// interpolate toward the first keyframe, which is needed for clips whose first and last poses
+    /// differ.
+    pub fn set_interpolate_loop(&mut self, interpolate_loop: bool) {
+        self.interpolate_loop = interpolate_loop;
+    }
+
+    /// Are the rotations of all the curves interpolated without taking the shortest path
+    pub fn preserves_rotation_winding(&self) -> bool {
+        self.preserve_rotation_winding
+    }
+
+    /// Set whether the rotations of all the curves are interpolated without taking the shortest
+    /// path, as if [`VariableCurve::take_shortest_path`] was `false` for each of them
+    ///
+    /// This is meant to be set when loading clips from exporters that bake rotations of more than
+    /// half a turn between two keyframes. Unlike the per-curve setting, it can't be used to keep
+    /// the shortest path for some curves of the clip, but it doesn't require knowing which curves
+    /// need it.
+    pub fn set_preserve_rotation_winding(&mut self, preserve_rotation_winding: bool) {
+        self.preserve_rotation_winding = preserve_rotation_winding;
+    }
+
+    /// Space the keyframes of the clip are expressed in
+    pub fn space(&self) -> CurveSpace {
+        self.space
+    }
+
+    /// Set the space the keyframes of the clip are expressed in
+    pub fn set_space(&mut self, space: CurveSpace) {
+        self.space = space;
+    }
+
+    /// How many times the clip is played unless its animation sets it, if the clip has an intent
+    pub fn default_repeat(&self) -> Option<RepeatAnimation> {
+        self.default_repeat
+    }
+
+    /// Set how many times the clip is played by the animations that don't
+    /// [set it](PlayingAnimation::set_repeat) after [playing](PlayingAnimation::play) it
+    ///
+    /// This carries the intent of the author with the clip, such as a walk cycle repeating
+    /// forever and an attack playing once, so that it doesn't need to be set by each player. It is
+    /// used from the first update of the animation after the clip is loaded.
+    pub fn set_default_repeat(&mut self, default_repeat: Option<RepeatAnimation>) {
+        self.default_repeat = default_repeat;
+    }
+
+    /// Add an interval from `start` to `end` in the clip, like the frames during which the hitbox
+    /// of an attack is active
+    ///
+    /// An [`AnimationIntervalEntered`] event with `id` is sent each time the main animation of a
+    /// player plays forward past `start`, and an [`AnimationIntervalExited`] one each time it
+    /// plays forward past `end`, on every playthrough. A frame stepping over the whole interval
+    /// sends both. `start` and `end` are swapped if `end` is before `start`.
+    pub fn add_interval_event(&mut self, start: f32, end: f32, id: u32) {
+        self.interval_events
+            .push((start.min(end), start.max(end), id));
+    }
+
+    /// Intervals of the clip, as `(start, end, id)`, in the order they were added
+    pub fn interval_events(&self) -> &[(f32, f32, u32)] {
+        &self.interval_events
+    }
+
+    /// Metadata of the clip for `key`, if any
+    ///
+    /// Metadata lets gameplay systems tag clips, for example as `"combat"` clips. Clips loaded
+    /// from glTF have the JSON of the extras of their animation as `"gltf_extras"`.
+    pub fn metadata(&self, key: &str) -> Option<&str> {
+        self.metadata.get(key).map(String::as_str)
+    }
+
+    /// Set the metadata of the clip for `key`, returning its previous value
+    pub fn set_metadata(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Option<String> {
+        self.metadata.insert(key.into(), value.into())
+    }
+
+    /// Remove the metadata of the clip for `key`, returning its value
+    pub fn remove_metadata(&mut self, key: &str) -> Option<String> {
+        self.metadata.remove(key)
+    }
+
+    /// Iterator over the metadata of the clip, as `(key, value)`
+    pub fn metadata_iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.metadata
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// Offset all the keyframes of the clip, and its loop points, in time
+    ///
+    /// The duration of the clip is updated accordingly. A negative `offset` that would move
+    /// keyframes before `0.0` is clamped so the earliest keyframe starts at `0.0`, with a warning.
+    /// Loop points and interval events are clamped to `0.0`.
+    pub fn shift_time(&mut self, offset: f32) {
+        let start = self
+            .curves
+            .values()
+            .flatten()
+            .filter_map(|curve| curve.keyframe_timestamps.first())
+            .fold(f32::INFINITY, |start, &curve_start| start.min(curve_start));
+        let offset = if start.is_finite() && start + offset < 0.0 {
+            warn!(
+                "Shifting the clip by {} would move keyframes before 0.0, shifting by {} instead",
+                offset, -start
+            );
+            -start
+        } else {
+            offset
+        };
+        for curve in self.curves.values_mut().flatten() {
+            for timestamp in &mut curve.keyframe_timestamps {
+                *timestamp += offset;
+            }
+        }
+        // Like the interval events, loop points are clamped to the start of the clip, and removed
+        // with a warning if the loop becomes empty
+        if let Some((loop_start, intro_end)) = self.loop_points {
+            self.set_loop_points(Some((
+                (loop_start + offset).max(0.0),
+                (intro_end + offset).max(0.0),
+            )));
+        }
+        for (start, end, _) in &mut self.interval_events {
+            *start = (*start + offset).max(0.0);
+            *end = (*end + offset).max(0.0);
+        }
+        self.duration = (self.duration + offset).max(0.0);
+    }
+
+    /// Set the [uniform step](VariableCurve::uniform_step) of the curves whose keyframes are
+    /// evenly spaced, with gaps differing by at most `epsilon`
+    ///
+    /// This is meant to be called once on baked clips, which have a keyframe every frame, to
+    /// speed up sampling them.
+    pub fn detect_uniform_steps(&mut self, epsilon: f32) {
+        for curve in self.curves.values_mut().flatten() {
+            curve.detect_uniform_step(epsilon);
+        }
+    }
+
+    /// Extract the part of this clip within `[start, end]`, as a clip starting at `0.0`
+    ///
+    /// This is meant to carve the takes out of a timeline holding several animations. Keyframes
+    /// are sampled at `start` and `end` when none falls exactly on them, and curves that don't
+    /// cover the whole range are held at their first or last keyframe. The duration of the slice
+    /// is `end - start`, and its loop points are cleared. Interval events are cut to the range,
+    /// and the ones outside of it are dropped.
+    pub fn slice(&self, start: f32, end: f32) -> AnimationClip {
+        let end = end.max(start);
+        let interval_events = self
+            .interval_events
+            .iter()
+            .filter_map(|&(event_start, event_end, id)| {
+                let (clipped_start, clipped_end) = (event_start.max(start), event_end.min(end));
+                // Intervals only touching the range become empty, unlike instants within it
+                let kept = clipped_start < clipped_end
+                    || event_start == event_end && (start..=end).contains(&event_start);
+                kept.then_some((clipped_start - start, clipped_end - start, id))
+            })
+            .collect();
+        let mut clip = AnimationClip {
+            curves: HashMap::default(),
+            duration: end - start,
+            loop_points: None,
+            interval_events,
+            ..self.clone()
+        };
+        for (path, curves) in &self.curves {
+            let curves = curves
+                .iter()
+                .filter_map(|curve| curve.slice(start, end))
+                .collect();
+            clip.curves.insert(path.clone(), curves);
+        }
+        clip
+    }
+
+    /// Convert the curves of this clip from the coordinate system `from` to `to`
+    ///
+    /// Translations and rotations are expressed in the new basis, and the axes of scales are
+    /// swapped accordingly. This is meant to be done once when loading a clip.
+    pub fn convert_axes(&self, from: UpAxis, to: UpAxis) -> AnimationClip {
+        let basis = to.rotation_from_y_up() * from.rotation_from_y_up().inverse();
+        let mut clip = self.clone();
+        for curve in clip.curves.values_mut().flatten() {
+            let quantized = matches!(
+                curve.keyframes,
+                Keyframes::QuantizedRotation(_)
+                    | Keyframes::QuantizedTranslation(_)
+                    | Keyframes::QuantizedScale(_)
+            );
+            if quantized {
+                curve.keyframes = curve.keyframes.dequantize();
+            }
+            match &mut curve.keyframes {
+                Keyframes::Rotation(keyframes) => {
+                    for rotation in keyframes {
+                        *rotation = basis * *rotation * basis.inverse();
+                    }
+                }
+                Keyframes::Translation(keyframes) => {
+                    for translation in keyframes {
+                        *translation = basis * *translation;
+                    }
+                }
+                Keyframes::Scale(keyframes) => {
+                    for scale in keyframes {
+                        *scale = (basis * *scale).abs();
+                    }
+                }
+                Keyframes::IkGoal { positions, .. } => {
+                    for position in positions {
+                        *position = basis * *position;
+                    }
+                }
+                Keyframes::Trs(keyframes) => {
+                    for transform in keyframes {
+                        transform.translation = basis * transform.translation;
+                        transform.rotation = basis * transform.rotation * basis.inverse();
+                        transform.scale = (basis * transform.scale).abs();
+                    }
+                }
+                Keyframes::Scalar(_) | Keyframes::SpriteIndex(_) => {}
+                Keyframes::QuantizedRotation(_)
+                | Keyframes::QuantizedTranslation(_)
+                | Keyframes::QuantizedScale(_) => unreachable!("keyframes are dequantized"),
+            }
+            if quantized {
+                curve.keyframes = curve.keyframes.quantize();
+            }
+        }
+        clip
+    }
+
+    /// Quantize the keyframes of all the curves of this clip, halving their size
+    ///
+    /// This is meant to be done once when baking a clip, for example to reduce the memory used by
+    /// the clips of large crowds. The precision lost is described in the quantized variants of
+    /// [`Keyframes`].
+    pub fn quantize(&self) -> AnimationClip {
+        let mut clip = self.clone();
+        for curve in clip.curves.values_mut().flatten() {
+            curve.keyframes = curve.keyframes.quantize();
+        }
+        clip
+    }
+
+    /// Average number of keyframes per second over all the curves of the clip
+    ///
+    /// This is the total number of keyframes divided by the sum of the time covered by each
+    /// curve. Returns `0.0` if no curve covers any time, for example for a clip made only of
+    /// static poses.
+    pub fn average_keyframe_rate(&self) -> f32 {
+        let (keyframes, duration) = self
+            .curves
+            .values()
+            .flatten()
+            .filter_map(|curve| {
+                let start = curve.keyframe_timestamps.first()?;
+                let end = curve.keyframe_timestamps.last()?;
+                Some((curve.keyframe_timestamps.len(), end - start))
+            })
+            .fold(
+                (0, 0.0),
+                |(keyframes, duration), (curve_keyframes, curve_duration)| {
+                    (keyframes + curve_keyframes, duration + curve_duration)
+                },
+            );
+        if duration > 0.0 {
+            keyframes as f32 / duration
+        } else {
+            0.0
+        }
+    }
+
+    /// Time of the first keyframe strictly after `after`, across all the curves of the clip
+    ///
+    /// Returns `None` if there is no keyframe after `after`. The timestamps of each curve are
+    /// binary searched, so they should be sorted.
+    pub fn next_keyframe_time(&self, after: f32) -> Option<f32> {
+        self.curves
+            .values()
+            .flatten()
+            .filter_map(|curve| {
+                let timestamps = &curve.keyframe_timestamps;
+                timestamps
+                    .get(timestamps.partition_point(|&timestamp| timestamp <= after))
+                    .copied()
+            })
+            .min_by(|a, b| a.total_cmp(b))
+    }
+
+    /// Time of the last keyframe strictly before `before`, across all the curves of the clip
+    ///
+    /// Returns `None` if there is no keyframe before `before`. The timestamps of each curve are
+    /// binary searched, so they should be sorted.
+    pub fn prev_keyframe_time(&self, before: f32) -> Option<f32> {
+        self.curves
+            .values()
+            .flatten()
+            .filter_map(|curve| {
+                let timestamps = &curve.keyframe_timestamps;
+                let index = timestamps.partition_point(|&timestamp| timestamp < before);
+                index.checked_sub(1).map(|index| timestamps[index])
+            })
+            .max_by(|a, b| a.total_cmp(b))
+    }
+
+    /// Times in `[t0, t1]` at which an animation repeating this clip forever at `speed` completes a
+    /// loop, in increasing order
+    ///
+    /// The times are measured from the start of playback, at which the animation is at the start
+    /// of the clip. These are the times its [completions](PlayingAnimation::completions) increase,
+    /// so that effects firing every few loops can be planned ahead. A negative speed completes as
+    /// often as the positive one. Returns no time if the speed is `0.0` or the clip has no
+    /// duration.
+    pub fn completion_times(&self, t0: f32, t1: f32, speed: f32) -> Vec<f32> {
+        let loop_time = self.duration / speed.abs();
+        if !(loop_time.is_finite() && loop_time > 0.0) || t1 < t0 {
+            return Vec::new();
+        }
+        // The start of playback isn't a completion
+        let first = (t0.max(0.0) / loop_time).ceil().max(1.0) as u32;
+        let last = (t1 / loop_time).floor().max(0.0) as u32;
+        (first..=last)
+            .map(|completion| completion as f32 * loop_time)
+            .collect()
+    }
+
+    /// Difference of the pose of `path` between the times `t0` and `t1`
+    ///
+    /// The translation of the result is `translation(t1) - translation(t0)`, its rotation
+    /// `rotation(t1) * rotation(t0).inverse()` and its scale `scale(t1) / scale(t0)`. Components
+    /// that are not animated, or that can't be sampled at one of the times, are left to identity.
+    /// Returns `None` if the clip has no curves for `path`.
+    pub fn pose_delta(&self, path: &EntityPath, t0: f32, t1: f32) -> Option<Transform> {
+        let mut delta = Transform::IDENTITY;
+        for curve in self.curves.get(path)? {
+            match curve.keyframes {
+                Keyframes::Rotation(_) | Keyframes::QuantizedRotation(_) => {
+                    if let (Some(r0), Some(r1)) =
+                        (curve.sample_rotation(t0), curve.sample_rotation(t1))
+                    {
+                        delta.rotation = r1 * r0.inverse();
+                    }
+                }
+                Keyframes::Translation(_) | Keyframes::QuantizedTranslation(_) => {
+                    if let (Some(p0), Some(p1)) =
+                        (curve.sample_translation(t0), curve.sample_translation(t1))
+                    {
+                        delta.translation = p1 - p0;
+                    }
+                }
+                Keyframes::Scale(_) | Keyframes::QuantizedScale(_) => {
+                    if let (Some(s0), Some(s1)) = (curve.sample_scale(t0), curve.sample_scale(t1)) {
+                        delta.scale = s1 / s0;
+                    }
+                }
+                Keyframes::Trs(_) => {
+                    if let (Some(p0), Some(p1)) = (curve.sample_trs(t0), curve.sample_trs(t1)) {
+                        delta.translation = p1.translation - p0.translation;
+                        delta.rotation = p1.rotation * p0.rotation.inverse();
+                        delta.scale = p1.scale / p0.scale;
+                    }
+                }
+                Keyframes::Scalar(_) | Keyframes::IkGoal { .. } | Keyframes::SpriteIndex(_) => {}
+            }
+        }
+        Some(delta)
+    }
+
+    /// Sample the pose of each path of the clip at `time`, writing it to `out`
+    ///
+    /// `out` is cleared, then filled with `(index, pose)` where `index` is the position of the
+    /// path in [`paths`](AnimationClip::paths). The curves are sampled directly at `time`, clamped
+    /// to `[0, duration]` of the clip, without the wrapping of an animation. The poses are
+    /// [`Transform`]s rather than [`Keyframes`], so that all the curves of a path are combined
+    /// into one value, each blended over the previous ones with its weight. Components of a pose
+    /// that are not animated are left to identity, and the curves of a clip in
+    /// [`CurveSpace::DeltaFromRest`] are composed with the identity. Reusing `out` avoids
+    /// allocating when sampling every frame.
+    pub fn sample_into(&self, time: f32, out: &mut Vec<(usize, Transform)>) {
+        out.clear();
+        let time = time.clamp(0.0, self.duration.max(0.0));
+        out.extend(self.paths.iter().enumerate().map(|(index, path)| {
+            let mut pose = Transform::IDENTITY;
+            for curve in self.curves[path].iter().filter(|curve| curve.enabled) {
+                let take_shortest_path =
+                    curve.take_shortest_path && !self.preserve_rotation_winding;
+                let (rotation, translation, scale) = match &curve.keyframes {
+                    Keyframes::Rotation(_) | Keyframes::QuantizedRotation(_) => (
+                        curve.sample_rotation_looping(time, None, take_shortest_path, None),
+                        None,
+                        None,
+                    ),
+                    Keyframes::Translation(_) | Keyframes::QuantizedTranslation(_) => {
+                        (None, curve.sample_translation(time), None)
+                    }
+                    Keyframes::Scale(_) | Keyframes::QuantizedScale(_) => {
+                        (None, None, curve.sample_scale(time))
+                    }
+                    Keyframes::Trs(_) => {
+                        match curve.sample_trs_looping(time, None, take_shortest_path, None) {
+                            Some(trs) => {
+                                (Some(trs.rotation), Some(trs.translation), Some(trs.scale))
+                            }
+                            None => (None, None, None),
+                        }
+                    }
+                    Keyframes::Scalar(_) | Keyframes::IkGoal { .. } | Keyframes::SpriteIndex(_) => {
+                        (None, None, None)
+                    }
+                };
+                if let Some(rotation) = rotation {
+                    let previous_rotation = pose.rotation;
+                    blend(&mut pose.rotation, rotation, curve.weight, Quat::slerp);
+                    if let Some(pivot) = curve.pivot {
+                        pose.translation += previous_rotation * pivot - pose.rotation * pivot;
+                    }
+                }
+                if let Some(translation) = translation {
+                    blend(&mut pose.translation, translation, curve.weight, Vec3::lerp);
+                }
+                if let Some(scale) = scale {
+                    blend(&mut pose.scale, scale, curve.weight, Vec3::lerp);
+                }
+            }
+            (index, pose)
+        }));
+    }
+
+    /// Hash of the data of the clip, stable across runs and platforms
+    ///
+    /// Two clips with the same curves, paths, duration and settings have the same fingerprint,
+    /// whatever the order their curves were added in, for example to detect if a reimported clip
+    /// changed or to check that peers play the same clip. Floats are hashed by their bits, except
+    /// for `-0.0` which is hashed as `0.0`.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = Fnv1aHasher::default();
+        hasher.write_f32(self.duration);
+        match self.loop_points {
+            Some((loop_start, intro_end)) => {
+                hasher.write(&[1]);
+                hasher.write_f32(loop_start);
+                hasher.write_f32(intro_end);
+            }
+            None => hasher.write(&[0]),
+        }
+        hasher.write(&[
+            self.interpolate_loop as u8,
+            self.preserve_rotation_winding as u8,
+            self.space as u8,
+        ]);
+        match self.default_repeat {
+            Some(RepeatAnimation::Never) => hasher.write(&[1]),
+            Some(RepeatAnimation::Count(count)) => {
+                hasher.write(&[2]);
+                hasher.write(&count.to_le_bytes());
+            }
+            Some(RepeatAnimation::Forever) => hasher.write(&[3]),
+            Some(RepeatAnimation::ClampForever) => hasher.write(&[4]),
+            None => hasher.write(&[0]),
+        }
+        hasher.write(&[5]);
+        hasher.write_usize(self.interval_events.len());
+        for &(start, end, id) in &self.interval_events {
+            hasher.write_f32(start);
+            hasher.write_f32(end);
+            hasher.write(&id.to_le_bytes());
+        }
+        let mut paths: Vec<_> = self.curves.iter().collect();
+        paths.sort_by(|(a, _), (b, _)| {
+            a.parts
+                .iter()
+                .map(Name::as_str)
+                .cmp(b.parts.iter().map(Name::as_str))
+        });
+        for (path, curves) in paths {
+            hasher.write_usize(path.parts.len());
+            for part in &path.parts {
+                hasher.write_usize(part.len());
+                hasher.write(part.as_bytes());
+            }
+            hasher.write_usize(curves.len());
+            for curve in curves {
+                curve.hash_into(&mut hasher);
+            }
+        }
+        hasher.0
+    }
+
+    /// Check the clip for problems that would make it play incorrectly
+    ///
+    /// Returns all the problems found, which is empty for a valid clip. The order of the problems
+    /// of different paths is unspecified.
+    pub fn validate(&self) -> Vec<AnimationClipError> {
+        let mut errors = Vec::new();
+        for (path, curves) in &self.curves {
+            if path.parts.is_empty() {
+                errors.push(AnimationClipError::EmptyPath { path: path.clone() });
+            }
+            for (index, curve) in curves.iter().enumerate() {
+                errors.extend(curve.validate(path, index));
+            }
+        }
+        errors
+    }
+
+    /// Truncate the timestamps or the keyframes of the curves that have more of one than of the
+    /// other, so that they have the same length
+    ///
+    /// Clips exported by buggy tools can have mismatched lengths, which can't be sampled past the
+    /// shorter one. A warning is emitted for each repaired curve. Returns the number of repaired
+    /// curves.
+    pub fn repair_lengths(&mut self) -> usize {
+        let mut repaired = 0;
+        for (path, curves) in &mut self.curves {
+            for (index, curve) in curves.iter_mut().enumerate() {
+                let timestamps = curve.keyframe_timestamps.len();
+                let keyframes = curve.keyframes.len();
+                if timestamps != keyframes {
+                    warn!(
+                        "Curve {} of path {:?} has {} timestamps but {} keyframes, truncating to {}",
+                        index,
+                        path,
+                        timestamps,
+                        keyframes,
+                        timestamps.min(keyframes)
+                    );
+                    curve.keyframe_timestamps.truncate(keyframes);
+                    curve.keyframes.truncate(timestamps);
+                    repaired += 1;
+                }
+            }
+        }
+        repaired
+    }
+
+    /// Does any curve have a different number of timestamps and keyframes
+    fn has_mismatched_lengths(&self) -> bool {
+        self.curves
+            .values()
+            .flatten()
+            .any(|curve| curve.keyframe_timestamps.len() != curve.keyframes.len())
+    }
+
+    /// Add a [`VariableCurve`] to an [`EntityPath`], if the curve has no problem
+    ///
+    /// Unlike [`add_curve_to_path`](AnimationClip::add_curve_to_path), the curve is checked the
+    /// same way as by [`validate`](AnimationClip::validate), and the first problem found is
+    /// returned instead of adding the curve.
+    pub fn try_add_curve_to_path(
+        &mut self,
+        path: EntityPath,
+        curve: VariableCurve,
+    ) -> Result<(), AnimationClipError> {
+        if path.parts.is_empty() {
+            return Err(AnimationClipError::EmptyPath { path });
+        }
+        let index = self.curves.get(&path).map_or(0, Vec::len);
+        if let Some(error) = curve.validate(&path, index).into_iter().next() {
+            return Err(error);
+        }
+        self.add_curve_to_path(path, curve);
+        Ok(())
+    }
+
+    /// Add a [`VariableCurve`] to an [`EntityPath`].
+    ///
+    /// In debug builds, a warning is emitted if the timestamps of the curve are not sorted.
+    pub fn add_curve_to_path(&mut self, path: EntityPath, curve: VariableCurve) {
+        if cfg!(debug_assertions) && !curve.has_sorted_timestamps() {
+            warn!(
+                "Keyframe timestamps of a curve for path {:?} are not sorted, it will not be sampled correctly",
+                path
+            );
+        }
+        // Update the duration of the animation by this curve duration if it's longer
+        self.duration = self
+            .duration
+            .max(*curve.keyframe_timestamps.last().unwrap_or(&0.0));
+        match self.curves.get_mut(&path) {
+            Some(curves) => curves.push(curve),
+            None => {
+                self.paths.push(path.clone());
+                self.curves.insert(path, vec![curve]);
+            }
+        }
+    }
+
+    /// Set the duration of the clip, to hold the last keyframes before the animation loops
+    ///
+    /// The duration is clamped to the last keyframe of the longest curve, as a shorter duration
+    /// would cut the end of the animation. A warning is emitted if `duration` is clamped.
+    pub fn set_duration_min(&mut self, duration: f32) {
+        let curves_end = self
+            .curves
+            .values()
+            .flatten()
+            .filter_map(|curve| curve.keyframe_timestamps.last())
+            .fold(0.0, |end: f32, &curve_end| end.max(curve_end));
+        if duration < curves_end {
+            warn!(
+                "Duration {} is shorter than the curves of the clip, using {} instead",
+                duration, curves_end
+            );
+        }
+        self.duration = duration.max(curves_end);
+    }
+
+    /// Remove the keyframes holding the final pose at the end of the curves, and shorten the
+    /// duration of the clip to the last keyframe where any curve changes
+    ///
+    /// Keyframes are equal when all their components are within `epsilon` of each other. The
+    /// first keyframe of a hold is kept, as it defines the final pose. Curves left with a single
+    /// keyframe don't count toward the duration, as they hold the same pose for the whole clip.
+    /// The loop points are clamped to the new duration.
+    pub fn trim_static_tail(&mut self, epsilon: f32) {
+        let mut curves_end: f32 = 0.0;
+        for curve in self.curves.values_mut().flatten() {
+            let len = curve.keyframe_timestamps.len().min(curve.keyframes.len());
+            let mut last_change = 0;
+            for index in 1..len {
+                if !curve.keyframes.approx_eq(index - 1, index, epsilon) {
+                    last_change = index;
+                }
+            }
+            curve.keyframe_timestamps.truncate(last_change + 1);
+            curve.keyframes.truncate(last_change + 1);
+            // A single keyframe is held for the whole clip, whatever its timestamp
+            if last_change > 0 {
+                curves_end = curves_end.max(curve.keyframe_timestamps[last_change]);
+            }
+        }
+        self.duration = self.duration.min(curves_end);
+        if let Some((loop_start, intro_end)) = self.loop_points {
+            // Loop points past the end collapse into an empty loop, which is removed
+            self.set_loop_points(Some((
+                loop_start.min(self.duration),
+                intro_end.min(self.duration),
+            )));
+        }
+    }
+
+    /// Merge the enabled curves animating the same attribute of a path into a single curve
+    ///
+    /// A path can end up with several curves for the same attribute, for example after combining
+    /// clips, which are applied one over the other. The merged curve has a keyframe at each
+    /// timestamp of the curves, sampled from the last curve having keyframes around it, which is
+    /// the value that was applied at that time. It replaces the first of the curves and keeps its
+    /// settings. Disabled curves are left as they are.
+    pub fn consolidate(&mut self) {
+        for curves in self.curves.values_mut() {
+            let mut consolidated: Vec<VariableCurve> = Vec::with_capacity(curves.len());
+            for curve in std::mem::take(curves) {
+                let merged = consolidated
+                    .iter_mut()
+                    .filter(|existing| {
+                        existing.enabled
+                            && curve.enabled
+                            && existing.keyframes.animates_same_attribute(&curve.keyframes)
+                    })
+                    .find_map(|existing| Some((existing.merged_with(&curve)?, existing)));
+                match merged {
+                    Some((merged, existing)) => *existing = merged,
+                    None => consolidated.push(curve),
+                }
+            }
+            *curves = consolidated;
+        }
+    }
+
+    /// Kinds of curves of the clip, enabled or not
+    ///
+    /// This is meant to set up the components of the target before playing the clip, for
+    /// example to insert the component of [scalar](ChannelFlags::SCALAR) curves, whose values
+    /// are otherwise silently skipped.
+    pub fn channels(&self) -> ChannelFlags {
+        self.curves
+            .values()
+            .flatten()
+            .fold(ChannelFlags::empty(), |flags, curve| {
+                flags
+                    | match curve.keyframes.kind() {
+                        CurveKind::Translation => ChannelFlags::TRANSLATION,
+                        CurveKind::Rotation => ChannelFlags::ROTATION,
+                        CurveKind::Scale => ChannelFlags::SCALE,
+                        CurveKind::Trs => ChannelFlags::TRANSFORM,
+                        CurveKind::Scalar => ChannelFlags::SCALAR,
+                        CurveKind::IkGoal => ChannelFlags::IK_GOAL,
+                        CurveKind::SpriteIndex => ChannelFlags::SPRITE_INDEX,
+                    }
+            })
+    }
+
+    /// Paths with an enabled curve that changes over the clip, in the order they were added
+    ///
+    /// A curve changes when any of its keyframes differs from the first one by more than
+    /// `threshold` in one of its components. The other paths only hold a static pose, or have no
+    /// curves at all, which helps finding redundant channels in imported clips.
+    pub fn affected_bones(&self, threshold: f32) -> Vec<EntityPath> {
+        self.paths
+            .iter()
+            .filter(|path| {
+                self.curves[*path]
+                    .iter()
+                    .filter(|curve| curve.enabled)
+                    .any(|curve| {
+                        (1..curve.keyframes.len())
+                            .any(|index| !curve.keyframes.approx_eq(0, index, threshold))
+                    })
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Approximate number of bytes used by this clip, including the unused capacity of its
+    /// allocations
+    ///
+    /// This counts the clip itself, its paths, and the timestamps and keyframes of its curves,
+    /// which are usually most of it. It is meant to compare clips when budgeting memory, for
+    /// example before and after [quantizing](Keyframes::quantize) them.
+    pub fn memory_usage(&self) -> usize {
+        let path_size = |path: &EntityPath| vec_heap_size(&path.parts);
+        let curves_size: usize = self
+            .curves
+            .iter()
+            .map(|(path, curves)| {
+                path_size(path)
+                    + vec_heap_size(curves)
+                    + curves
+                        .iter()
+                        .map(|curve| {
+                            vec_heap_size(&curve.keyframe_timestamps) + curve.keyframes.heap_size()
+                        })
+                        .sum::<usize>()
+            })
+            .sum();
+        let map_entry_size =
+            std::mem::size_of::<EntityPath>() + std::mem::size_of::<Vec<VariableCurve>>();
+        std::mem::size_of::<Self>()
+            + self.curves.capacity() * map_entry_size
+            + curves_size
+            + vec_heap_size(&self.paths)
+            + self.paths.iter().map(path_size).sum::<usize>()
+            + vec_heap_size(&self.interval_events)
+    }
+
+    /// Do the first and last keyframes of every enabled curve match, within `epsilon`
+    ///
+    /// A clip that isn't seamless visibly pops when it [repeats](RepeatAnimation::Forever), as
+    /// its pose jumps from the last keyframe back to the first one.
+    pub fn is_seamless(&self, epsilon: f32) -> bool {
+        self.curves
+            .values()
+            .flatten()
+            .filter(|curve| curve.enabled)
+            .all(|curve| {
+                let len = curve.keyframes.len();
+                len == 0 || curve.keyframes.approx_eq(0, len - 1, epsilon)
+            })
+    }
+}
+
+/// Builds an [`AnimationClip`] from transforms sampled over time, for example to record the
+/// motion of a physics simulation
+///
+/// Each recorded path gets a translation, a rotation and a scale curve, with a keyframe for each
+/// of its samples. The samples of a path are sorted by time when building the clip.
+#[derive(Clone, Debug, Default)]
+pub struct ClipRecorder {
+    samples: HashMap<EntityPath, Vec<(f32, Transform)>>,
+}
+
+impl ClipRecorder {
+    /// Record the `transform` of the entity at `path` at `time`
+    pub fn record(&mut self, time: f32, path: &EntityPath, transform: Transform) {
+        match self.samples.get_mut(path) {
+            Some(samples) => samples.push((time, transform)),
+            None => {
+                self.samples.insert(path.clone(), vec![(time, transform)]);
+            }
+        }
+    }
+
+    /// Has nothing been recorded yet
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Build the clip from the recorded samples
+    pub fn into_clip(self) -> AnimationClip {
+        let mut animation_clip = AnimationClip::default();
+        for (path, mut samples) in self.samples {
+            samples.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+            let timestamps: Vec<_> = samples.iter().map(|&(time, _)| time).collect();
+            for keyframes in [
+                Keyframes::Translation(samples.iter().map(|(_, t)| t.translation).collect()),
+                Keyframes::Rotation(samples.iter().map(|(_, t)| t.rotation).collect()),
+                Keyframes::Scale(samples.iter().map(|(_, t)| t.scale).collect()),
+            ] {
+                animation_clip.add_curve_to_path(
+                    path.clone(),
+                    VariableCurve::new(timestamps.clone(), keyframes),
+                );
+            }
+        }
+        animation_clip
+    }
+}
+
+/// A problem found in an [`AnimationClip`] by [`AnimationClip::validate`]
+///
+/// `curve` is the index of the offending curve among the curves of `path`.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum AnimationClipError {
+    /// The path has no parts, so it can't target any entity.
+    #[error("path {path:?} is empty")]
+    EmptyPath {
+        /// The empty path.
+        path: EntityPath,
+    },
+    /// The curve has no keyframes.
+    #[error("curve {curve} of path {path:?} has no keyframes")]
+    EmptyCurve {
+        /// Path of the curve.
+        path: EntityPath,
+        /// Index of the curve.
+        curve: usize,
+    },
+    /// The keyframe timestamps are not sorted in non-decreasing order.
+    #[error("curve {curve} of path {path:?} has unsorted keyframe timestamps")]
+    UnsortedTimestamps {
+        /// Path of the curve.
+        path: EntityPath,
+        /// Index of the curve.
+        curve: usize,
+    },
+    /// The numbers of timestamps and keyframes differ.
+    #[error(
+        "curve {curve} of path {path:?} has {timestamps} timestamps but {keyframes} keyframes"
+    )]
+    MismatchedLengths {
+        /// Path of the curve.
+        path: EntityPath,
+        /// Index of the curve.
+        curve: usize,
+        /// Number of timestamps.
+        timestamps: usize,
+        /// Number of keyframes.
+        keyframes: usize,
+    },
+    /// Two consecutive keyframes share the same timestamp, making a step in the curve.
+    #[error("curve {curve} of path {path:?} has several keyframes at {time}")]
+    ZeroLengthSegment {
+        /// Path of the curve.
+        path: EntityPath,
+        /// Index of the curve.
+        curve: usize,
+        /// Timestamp of the keyframes.
+        time: f32,
+    },
+    /// A timestamp or a keyframe is infinite or NaN.
+    #[error("curve {curve} of path {path:?} has non-finite values")]
+    NonFiniteValue {
+        /// Path of the curve.
+        path: EntityPath,
+        /// Index of the curve.
+        curve: usize,
+    },
+}
+
+/// How many times an animation should be played
+#[derive(Reflect, FromReflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RepeatAnimation {
+    /// Play the animation once, then stop updating the animated entities.
+    #[default]
+    Never,
+    /// Play the animation the given number of times, then stop updating the animated entities.
+    Count(u32),
+    /// Repeat the animation forever.
+    Forever,
+    /// Play the animation once, then keep applying its last frame.
+    ///
+    /// The animation is [finished](PlayingAnimation::is_finished) after it has been played once,
+    /// but unlike [`RepeatAnimation::Never`], the animated entities are held in the final pose
+    /// rather than left free for other systems to take over.
+    ClampForever,
+}
+
+/// What an animation does once it's [finished](PlayingAnimation::is_finished)
+#[derive(Reflect, FromReflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FinishedBehavior {
+    /// Keep applying the last frame, holding the animated entities in the final pose.
+    ///
+    /// This makes [`RepeatAnimation::Never`] behave like [`RepeatAnimation::ClampForever`].
+    HoldPose,
+    /// Stop applying the animation, leaving the animated entities free for other systems to take
+    /// over.
+    #[default]
+    FreeTransform,
+    /// Clear the clip of the animation, as if nothing was played.
+    Stop,
+}
+
+/// How an animation is combined with the pose produced by the animations applied before it
+#[derive(Reflect, FromReflect, Clone, Debug, Default, PartialEq)]
+pub enum BlendMode {
+    /// Blend toward the pose of the animation according to its weight.
+    #[default]
+    Replace,
+    /// Add the difference between the pose of the animation and its own pose at
+    /// `reference_time`, scaled by its weight.
+    ///
+    /// `reference_time` is clamped to the keyframes of each curve, so `0.0` measures the curves
+    /// against their first keyframes. A later time measures them against a neutral pose in the
+    /// middle of the clip, such as the center of an aim offset.
+    Additive {
+        /// Time in the clip of the reference pose.
+        reference_time: f32,
+    },
+    /// Add the difference between the pose of the animation and the pose of another clip sampled
+    /// at the same phase, scaled by its weight.
+    ///
+    /// Curves without a curve of the same kind for their path in the reference clip are ignored,
+    /// and the animation is not applied until the reference clip is loaded.
+    AdditiveFrom(Handle<AnimationClip>),
+}
+
+/// How the scale of an animation blended with [`BlendMode::Replace`] is combined with the scale
+/// of the animations applied before it
+#[derive(Reflect, FromReflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScaleBlend {
+    /// Interpolate linearly toward the scale of the animation according to its weight.
+    #[default]
+    Linear,
+    /// Multiply the scale by the scale of the animation, scaled by its weight as
+    /// `scale *= Vec3::ONE.lerp(keyframe, weight)`.
+    ///
+    /// This composes overlays such as a squash onto the scale of the base animation instead of
+    /// averaging them.
+    Multiplicative,
+}
+
+/// Weights of the entities an animation is applied to, by path
+///
+/// The weight of each path multiplies the weight of the animation. Paths that are not in the
+/// mask have a weight of `0.0`, so they are not animated.
+#[derive(Reflect, FromReflect, Clone, Debug, Default)]
+pub struct AnimationMask {
+    weights: HashMap<EntityPath, f32>,
+}
+
+impl AnimationMask {
+    /// Create a mask ramping along a chain of bones, such as the spine for upper-body animations
+    ///
+    /// `falloff` maps the position of a bone along the chain, from `0.0` for the first bone to
+    /// `1.0` for the last one, to its weight. For example, `|t| t` gives a linear ramp and
+    /// `|t| Easing::EaseIn.ease(t)` starts slowly from the first bone.
+    pub fn from_chain(chain: &[EntityPath], falloff: impl Fn(f32) -> f32) -> Self {
+        let mut mask = Self::default();
+        let last = chain.len().saturating_sub(1).max(1) as f32;
+        for (index, path) in chain.iter().enumerate() {
+            mask.set_weight(path.clone(), falloff(index as f32 / last));
+        }
+        mask
+    }
+
+    /// Weight of `path`, `0.0` if it is not in the mask
+    pub fn weight(&self, path: &EntityPath) -> f32 {
+        self.weights.get(path).copied().unwrap_or(0.0)
+    }
+
+    /// Set the weight of `path`
+    pub fn set_weight(&mut self, path: EntityPath, weight: f32) -> &mut Self {
+        self.weights.insert(path, weight);
+        self
+    }
+
+    /// Iterator over the paths in the mask and their weight
+    pub fn iter(&self) -> impl Iterator<Item = (&EntityPath, f32)> {
+        self.weights.iter().map(|(path, weight)| (path, *weight))
+    }
+}
+
+/// An animation played by an [`AnimationPlayer`], with its own playback state
+#[derive(Reflect, FromReflect, Clone)]
+pub struct PlayingAnimation {
+    paused: bool,
+    repeat: RepeatAnimation,
+    repeat_set: bool,
+    speed: f32,
+    elapsed: f32,
+    completions: u32,
+    weight: f32,
+    blend_mode: BlendMode,
+    scale_blend: ScaleBlend,
+    mask: Option<AnimationMask>,
+    #[reflect(ignore)]
+    mask_fade: Option<MaskFade>,
+    outro: bool,
+    finished_behavior: FinishedBehavior,
+    loop_speed_multiplier: f32,
+    priority: i32,
+    animation_clip: Handle<AnimationClip>,
+    time_warp: Option<TimeWarp>,
+    /// Elapsed time and completions before a seek firing the events of the skipped range
+    #[reflect(ignore)]
+    seek_from: Option<(f32, u32)>,
+}
+
+/// A mask replaced with [`PlayingAnimation::set_mask_with_fade`], whose weights fade to the ones
+/// of the new mask
+#[derive(Clone)]
+struct MaskFade {
+    from: Option<AnimationMask>,
+    elapsed: f32,
+    duration: f32,
+}
+
+impl Default for PlayingAnimation {
+    fn default() -> Self {
+        Self {
+            paused: false,
+            repeat: RepeatAnimation::Never,
+            repeat_set: false,
+            speed: 1.0,
+            elapsed: 0.0,
+            completions: 0,
+            weight: 1.0,
+            blend_mode: BlendMode::Replace,
+            scale_blend: ScaleBlend::Linear,
+            mask: None,
+            mask_fade: None,
+            outro: false,
+            finished_behavior: FinishedBehavior::FreeTransform,
+            loop_speed_multiplier: 1.0,
+            priority: 0,
+            animation_clip: Default::default(),
+            time_warp: None,
+            seek_from: None,
+        }
+    }
+}
+
+impl PlayingAnimation {
+    /// Start playing an animation, resetting its playback state
+    ///
+    /// The weight, blend modes, mask, finished behavior and priority of the animation are kept.
+    pub fn play(&mut self, handle: Handle<AnimationClip>) -> &mut Self {
+        *self = Self {
+            animation_clip: handle,
+            weight: self.weight,
+            blend_mode: std::mem::take(&mut self.blend_mode),
+            scale_blend: self.scale_blend,
+            mask: self.mask.take(),
+            mask_fade: self.mask_fade.take(),
+            finished_behavior: self.finished_behavior,
+            loop_speed_multiplier: self.loop_speed_multiplier,
+            priority: self.priority,
+            ..Default::default()
+        };
+        self
+    }
+
+    /// Start playing an animation backward from its end, resetting its playback state
+    ///
+    /// A negative elapsed time is measured from the end of the clip, so the animation starts at
+    /// its last frame without needing to know the duration of the clip. If it doesn't repeat, the
+    /// animation is finished once it reaches the start of the clip.
+    pub fn play_reversed(&mut self, handle: Handle<AnimationClip>) -> &mut Self {
+        self.play(handle).set_speed(-1.0)
+    }
+
+    /// Handle to the [`AnimationClip`] being played
+    pub fn animation_clip(&self) -> &Handle<AnimationClip> {
+        &self.animation_clip
+    }
+
+    /// Set the animation to repeat forever
+    pub fn repeat(&mut self) -> &mut Self {
+        self.set_repeat(RepeatAnimation::Forever)
+    }
+
+    /// Stop the animation from repeating
+    pub fn stop_repeating(&mut self) -> &mut Self {
+        self.set_repeat(RepeatAnimation::Never)
+    }
+
+    /// How many times the animation is played
+    pub fn repeat_mode(&self) -> RepeatAnimation {
+        self.repeat
+    }
+
+    /// Set how many times the animation is played
+    ///
+    /// Until this is set, the animation follows the
+    /// [default repeat](AnimationClip::set_default_repeat) of its clip, if it has one.
+    pub fn set_repeat(&mut self, repeat: RepeatAnimation) -> &mut Self {
+        self.repeat = repeat;
+        self.repeat_set = true;
+        self
+    }
+
+    /// What the animation does once it's finished
+    pub fn finished_behavior(&self) -> FinishedBehavior {
+        self.finished_behavior
+    }
+
+    /// Set what the animation does once it's finished
+    pub fn set_finished_behavior(&mut self, finished_behavior: FinishedBehavior) -> &mut Self {
+        self.finished_behavior = finished_behavior;
+        self
+    }
+
+    /// Number of times the animation reached the end of the clip, or its start when played in
+    /// reverse
+    ///
+    /// This is updated by [`animation_player`] from the elapsed time.
+    pub fn completions(&self) -> u32 {
+        self.completions
+    }
+
+    /// Has the animation been played as many times as its [`RepeatAnimation`] requires
+    ///
+    /// An animation repeating forever is never finished. A finished animation isn't advanced
+    /// anymore. This is computed from the completions, which [`animation_player`] updates from the
+    /// elapsed time every frame even if the animation is paused or has a speed of `0.0`: an
+    /// animation [seeked](PlayingAnimation::set_elapsed) to the end of its last playthrough is
+    /// finished after the next update.
+    pub fn is_finished(&self) -> bool {
+        match self.repeat {
+            RepeatAnimation::Forever => false,
+            RepeatAnimation::Never | RepeatAnimation::ClampForever => self.completions >= 1,
+            RepeatAnimation::Count(count) => self.completions >= count,
+        }
+    }
+
+    /// Time until the animation next reaches the end of its clip, or its start when played in
+    /// reverse, incrementing its [completions](PlayingAnimation::completions)
+    ///
+    /// This is scaled by the speed of the animation, and defined for animations repeating
+    /// forever. Returns `None` if the animation is paused, finished or has a speed of `0.0`, or
+    /// if the clip has no duration.
+    pub fn time_to_next_completion(&self, animation_clip: &AnimationClip) -> Option<Duration> {
+        let duration = animation_clip.duration;
+        if self.paused || self.speed == 0.0 || duration <= 0.0 || self.is_finished() {
+            return None;
+        }
+        let phase = self.elapsed.abs() % duration;
+        // Playing away from `0.0` reaches the next multiple of the duration, playing toward it
+        // reaches the previous one
+        let remaining = if self.elapsed == 0.0 || self.elapsed.signum() == self.speed.signum() {
+            duration - phase
+        } else if phase > 0.0 {
+            phase
+        } else {
+            duration
+        };
+        Some(Duration::from_secs_f32(remaining / self.speed.abs()))
+    }
+
+    /// Pause the animation
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Unpause the animation
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Is the animation paused
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Speed of the animation playback
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Set the speed of the animation playback
+    ///
+    /// With a speed of `0.0`, the elapsed time isn't advanced but the clip is still sampled and
+    /// applied at it every frame, and the completions still follow it when it is
+    /// [set](PlayingAnimation::set_elapsed) manually. A negative speed plays the clip in reverse.
+    pub fn set_speed(&mut self, speed: f32) -> &mut Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Factor the speed of the animation is multiplied by each time it completes
+    pub fn loop_speed_multiplier(&self) -> f32 {
+        self.loop_speed_multiplier
+    }
+
+    /// Set the factor the speed of the animation is multiplied by each time it completes, `1.0`
+    /// by default
+    ///
+    /// With a factor of `1.1`, each loop plays 10% faster than the one before. The direction of
+    /// playback is kept, and [`set_speed`](PlayingAnimation::set_speed) sets the speed the next
+    /// loops are multiplied from. Only completions reached during playback change the speed, not
+    /// seeking or an [external driver](AnimationDriver::External).
+    pub fn set_loop_speed_multiplier(&mut self, multiplier: f32) -> &mut Self {
+        self.loop_speed_multiplier = multiplier;
+        self
+    }
+
+    /// Priority of the animation when it plays in a slot, set with
+    /// [`AnimationPlayer::set_slot_priority`]
+    pub fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    /// Time elapsed playing the animation
+    ///
+    /// For an animation repeating [forever](RepeatAnimation::Forever), this is the time elapsed
+    /// since the start of the current playthrough, so that it doesn't lose precision over time.
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    /// Seek to a specific time in the animation
+    pub fn set_elapsed(&mut self, elapsed: f32) -> &mut Self {
+        self.elapsed = elapsed;
+        self
+    }
+
+    /// Seek to a specific time in the animation, kept within the time the animation plays for
+    /// according to its repeat mode
+    ///
+    /// The time is wrapped to the duration of `animation_clip` for an animation repeating
+    /// [forever](RepeatAnimation::Forever), and clamped between `0.0` and the end of its last
+    /// playthrough otherwise, so scrubbing past the end pins the animation to its last frame.
+    pub fn set_elapsed_clamped(
+        &mut self,
+        elapsed: f32,
+        animation_clip: &AnimationClip,
+    ) -> &mut Self {
+        let duration = animation_clip.duration.max(0.0);
+        self.elapsed = match self.repeat {
+            _ if duration == 0.0 => 0.0,
+            RepeatAnimation::Forever => elapsed.rem_euclid(duration),
+            RepeatAnimation::Count(count) => elapsed.clamp(0.0, duration * count as f32),
+            RepeatAnimation::Never | RepeatAnimation::ClampForever => elapsed.clamp(0.0, duration),
+        };
+        self
+    }
+
+    /// Time in `animation_clip` at which the animation is sampled
+    ///
+    /// Unlike [`elapsed`](PlayingAnimation::elapsed), this is wrapped for looping animations and
+    /// remapped by the [time warp](PlayingAnimation::time_warp), staying within
+    /// `[0, duration]` of the clip. Returns `0.0` for a clip without duration.
+    pub fn elapsed_wrapped(&self, animation_clip: &AnimationClip) -> f32 {
+        if animation_clip.duration > 0.0 {
+            self.sample_time(animation_clip)
+                .clamp(0.0, animation_clip.duration)
+        } else {
+            0.0
+        }
+    }
+
+    /// Position of the animation within its current loop of `animation_clip`, in `[0, 1)`
+    ///
+    /// Unlike [`elapsed_wrapped`](PlayingAnimation::elapsed_wrapped), this always wraps at the
+    /// end of the clip whatever the [`RepeatAnimation`] of the animation, and ignores the
+    /// [time warp](PlayingAnimation::time_warp), giving a steady phase to drive a UI gauge. It
+    /// decreases when the animation is played in reverse. Returns `0.0` for a clip without
+    /// duration.
+    pub fn loop_progress(&self, animation_clip: &AnimationClip) -> f32 {
+        if animation_clip.duration <= 0.0 {
+            return 0.0;
+        }
+        let progress = (self.elapsed / animation_clip.duration).rem_euclid(1.0);
+        // A tiny negative elapsed time rounds up to `1.0`
+        if progress < 1.0 {
+            progress
+        } else {
+            0.0
+        }
+    }
+
+    /// Weight of the animation
+    pub fn weight(&self) -> f32 {
+        self.weight
+    }
+
+    /// Set the weight of the animation
+    ///
+    /// The animation is blended with the pose produced by the animations applied before it, a
+    /// weight of `1.0` overriding this pose and a weight of `0.0` leaving it untouched.
+    pub fn set_weight(&mut self, weight: f32) -> &mut Self {
+        self.weight = weight;
+        self
+    }
+
+    /// How the animation is combined with the animations applied before it
+    pub fn blend_mode(&self) -> &BlendMode {
+        &self.blend_mode
+    }
+
+    /// Set how the animation is combined with the animations applied before it
+    pub fn set_blend_mode(&mut self, blend_mode: BlendMode) -> &mut Self {
+        self.blend_mode = blend_mode;
+        self
+    }
+
+    /// How the scale of the animation is combined with the animations applied before it
+    pub fn scale_blend(&self) -> ScaleBlend {
+        self.scale_blend
+    }
+
+    /// Set how the scale of the animation is combined with the animations applied before it
+    ///
+    /// This only applies to animations blended with [`BlendMode::Replace`], additive animations
+    /// always multiply the scale.
+    pub fn set_scale_blend(&mut self, scale_blend: ScaleBlend) -> &mut Self {
+        self.scale_blend = scale_blend;
+        self
+    }
+
+    /// Mask of the entities the animation is applied to, if any
+    pub fn mask(&self) -> Option<&AnimationMask> {
+        self.mask.as_ref()
+    }
+
+    /// Set the mask of the entities the animation is applied to
+    ///
+    /// Without a mask, the animation is applied to all the entities animated by its clip.
+    pub fn set_mask(&mut self, mask: Option<AnimationMask>) -> &mut Self {
+        self.mask = mask;
+        self.mask_fade = None;
+        self
+    }
+
+    /// Set the mask of the entities the animation is applied to, fading the weight of each path
+    /// from the current mask to the new one over `duration`
+    ///
+    /// Without a mask, every path has a weight of `1.0`, so turning a mask on or off with a fade
+    /// smoothly hands the masked paths over to or back from the animations applied before this
+    /// one. The fade advances with the frame delta, whatever the speed of the animation. A fade
+    /// interrupted by a new mask restarts from the weights of the mask it was fading to.
+    pub fn set_mask_with_fade(
+        &mut self,
+        mask: Option<AnimationMask>,
+        duration: Duration,
+    ) -> &mut Self {
+        let from = std::mem::replace(&mut self.mask, mask);
+        self.mask_fade = (!duration.is_zero()).then(|| MaskFade {
+            from,
+            elapsed: 0.0,
+            duration: duration.as_secs_f32(),
+        });
+        self
+    }
+
+    /// Weight of `path` in the mask of the animation, `1.0` without a mask
+    ///
+    /// While a [fade](PlayingAnimation::set_mask_with_fade) is ongoing, this is between the
+    /// weights of the previous and the new mask.
+    pub fn mask_weight(&self, path: &EntityPath) -> f32 {
+        let weight = |mask: &Option<AnimationMask>| match mask {
+            Some(mask) => mask.weight(path),
+            None => 1.0,
+        };
+        let target = weight(&self.mask);
+        match &self.mask_fade {
+            Some(fade) => {
+                let from = weight(&fade.from);
+                from + (target - from) * (fade.elapsed / fade.duration).min(1.0)
+            }
+            None => target,
+        }
+    }
+
+    /// Time warp remapping the time of the animation, if any
+    pub fn time_warp(&self) -> Option<&TimeWarp> {
+        self.time_warp.as_ref()
+    }
+
+    /// Set a [`TimeWarp`] remapping the time of the animation before sampling it
+    ///
+    /// The elapsed time of the animation is not remapped, only the time used to sample the clip.
+    pub fn set_time_warp(&mut self, time_warp: Option<TimeWarp>) -> &mut Self {
+        self.time_warp = time_warp;
+        self
+    }
+
+    /// Advance the animation by `delta`, scaled by its speed, even if it's paused
+    ///
+    /// This can be used to step an animation by an exact amount of time rather than the frame
+    /// delta of [`Time`], for example when rendering frame by frame. See
+    /// [`AnimationPlayer::advance`].
+    pub fn advance(&mut self, delta: Duration, animation_clip: &AnimationClip) -> &mut Self {
+        self.advance_by(delta.as_secs_f32(), animation_clip);
+        self
+    }
+
+    /// Move the elapsed time by `delta` seconds, not scaled by the speed of the animation
+    ///
+    /// A negative `delta` seeks backward. The elapsed time is clamped to the playthroughs allowed
+    /// by the [`RepeatAnimation`] of the animation, and the completions are updated if a loop
+    /// boundary is crossed. Seeking backward before the start of an animation repeating forever
+    /// wraps around to the end of its clip, removing a completion for each loop boundary crossed,
+    /// down to `0`.
+    pub fn seek_by(&mut self, delta: f32, animation_clip: &AnimationClip) -> &mut Self {
+        let duration = animation_clip.duration;
+        let elapsed = self.elapsed + delta;
+        self.elapsed = match self.repeat {
+            RepeatAnimation::Forever if elapsed < 0.0 && self.elapsed >= 0.0 && duration > 0.0 => {
+                let wraps = (-elapsed / duration).ceil() as u32;
+                self.completions = self.completions.saturating_sub(wraps);
+                elapsed.rem_euclid(duration)
+            }
+            RepeatAnimation::Forever => elapsed,
+            repeat => {
+                let end = match repeat {
+                    RepeatAnimation::Count(count) => count as f32 * duration,
+                    _ => duration,
+                };
+                // Animations played in reverse have a negative elapsed time
+                if self.elapsed < 0.0 {
+                    elapsed.clamp(-end, 0.0)
+                } else {
+                    elapsed.clamp(0.0, end)
+                }
+            }
+        };
+        self.update_completions(duration);
+        self
+    }
+
+    /// Leave the loop of a clip with [loop points](AnimationClip::set_loop_points), playing the
+    /// rest of the clip to its end
+    pub fn play_outro(&mut self) -> &mut Self {
+        self.outro = true;
+        self
+    }
+
+    /// Is the animation playing the end of a clip after leaving its loop
+    pub fn is_playing_outro(&self) -> bool {
+        self.outro
+    }
+
+    /// Advance the animation by `delta` seconds unless it's paused
+    fn update(&mut self, delta: f32, animation_clip: &AnimationClip) {
+        self.follow_default_repeat(animation_clip);
+        if let Some(fade) = &mut self.mask_fade {
+            fade.elapsed += delta;
+            if fade.elapsed >= fade.duration {
+                self.mask_fade = None;
+            }
+        }
+        if self.paused {
+            self.update_completions(animation_clip.duration);
+        } else {
+            self.advance_by(delta, animation_clip);
+        }
+    }
+
+    /// Advance the animation by `delta` seconds, scaled by its speed, unless it's finished
+    fn advance_by(&mut self, delta: f32, animation_clip: &AnimationClip) {
+        self.update_completions(animation_clip.duration);
+        if !self.is_finished() {
+            self.elapsed += delta * self.speed;
+            if let Some((loop_start, intro_end)) = animation_clip.loop_points {
+                // Loop points set through reflection aren't validated, and an empty loop would
+                // divide by zero
+                if !self.outro
+                    && self.speed > 0.0
+                    && self.elapsed >= intro_end
+                    && loop_start < intro_end
+                {
+                    self.elapsed =
+                        loop_start + (self.elapsed - loop_start) % (intro_end - loop_start);
+                }
+            }
+            let completions = self.completions;
+            self.update_completions(animation_clip.duration);
+            let loops = self.completions.saturating_sub(completions);
+            if loops > 0 && self.loop_speed_multiplier != 1.0 {
+                self.speed *= self.loop_speed_multiplier.powi(loops as i32);
+            }
+        }
+    }
+
+    fn update_completions(&mut self, duration: f32) {
+        // A clip without duration is complete as soon as it is played
+        if duration <= 0.0 {
+            self.completions = 1;
+            return;
+        }
+        let completions = (self.elapsed.abs() / duration) as u32;
+        if self.repeat == RepeatAnimation::Forever {
+            // Keep the elapsed time within a playthrough so that it doesn't grow unbounded and lose
+            // precision, counting the playthroughs that are removed from it
+            self.completions = self.completions.saturating_add(completions);
+            self.elapsed %= duration;
+        } else {
+            self.completions = completions;
+        }
+    }
+
+    /// Set the elapsed time to `elapsed`, computing the completions from it alone
+    fn drive(&mut self, elapsed: f32, animation_clip: &AnimationClip) {
+        self.follow_default_repeat(animation_clip);
+        self.elapsed = elapsed;
+        self.completions = 0;
+        self.update_completions(animation_clip.duration);
+    }
+
+    /// Use the [default repeat](AnimationClip::default_repeat) of the clip unless the repeat mode
+    /// was set since the animation started playing
+    fn follow_default_repeat(&mut self, animation_clip: &AnimationClip) {
+        if let (false, Some(repeat)) = (self.repeat_set, animation_clip.default_repeat) {
+            self.repeat = repeat;
+        }
+    }
+
+    /// Will the animation go back to the start of its clip after the current playthrough
+    fn loops(&self) -> bool {
+        match self.repeat {
+            RepeatAnimation::Forever => true,
+            RepeatAnimation::Count(count) => self.completions + 1 < count,
+            RepeatAnimation::Never | RepeatAnimation::ClampForever => false,
+        }
+    }
+
+    /// Restart the animation from its start, keeping its clip and settings
+    fn reset(&mut self) {
+        self.elapsed = 0.0;
+        self.completions = 0;
+        self.outro = false;
+    }
+
+    /// Time played since the start of the first playthrough, including the completed ones
+    fn progress(&self, duration: f32) -> f32 {
+        if self.repeat == RepeatAnimation::Forever {
+            self.completions as f32 * duration + self.elapsed
+        } else {
+            self.elapsed
+        }
+    }
+
+    /// Number of times the animation goes past `marker` in its clip when its
+    /// [progress](PlayingAnimation::progress) goes forward from `from` to `to`
+    fn marker_crossings(&self, from: f32, to: f32, marker: f32, duration: f32) -> u32 {
+        let end = match self.repeat {
+            RepeatAnimation::Forever => f32::INFINITY,
+            RepeatAnimation::Count(count) => count as f32 * duration,
+            RepeatAnimation::Never | RepeatAnimation::ClampForever => duration,
+        };
+        // Playthroughs in which the marker is after `from` and before `to`
+        let first = ((from - marker) / duration).floor() + 1.0;
+        let last = ((to.min(end) - marker) / duration).floor();
+        (last - first.max(0.0) + 1.0).max(0.0) as u32
+    }
+
+    /// Time at which the clip is sampled for the current elapsed time
+    fn sample_time(&self, animation_clip: &AnimationClip) -> f32 {
+        let mut elapsed = self.elapsed;
+        let duration = animation_clip.duration;
+        // A clip without duration is a single instant, there is nothing to wrap or remap
+        if duration > 0.0 {
+            match self.repeat {
+                RepeatAnimation::Forever => elapsed %= duration,
+                RepeatAnimation::Count(count) if self.completions < count => elapsed %= duration,
+                RepeatAnimation::ClampForever => elapsed = elapsed.clamp(-duration, duration),
+                _ if self.finished_behavior == FinishedBehavior::HoldPose && self.is_finished() => {
+                    elapsed = elapsed.clamp(-duration, duration);
+                }
+                _ => {}
+            }
+            if elapsed < 0.0 {
+                elapsed += animation_clip.duration;
+            }
+            // A finished animation freeing its entities is past the end of the clip, which the warp
+            // would clamp back to its last pose
+            match &self.time_warp {
+                Some(time_warp) if (0.0..=duration).contains(&elapsed) => {
+                    elapsed = time_warp.sample(elapsed / duration) * duration;
+                }
+                _ => {}
+            }
+        }
+        elapsed
+    }
+}
+
+/// Space the keyframes of an [`AnimationClip`] are expressed in
+#[derive(Reflect, FromReflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CurveSpace {
+    /// Keyframes are the local transform of the entities.
+    #[default]
+    Local,
+    /// Keyframes are offsets from the [`RestPose`] of the entities.
+    ///
+    /// The sampled transform is composed onto the rest pose: its translation is added to the
+    /// translation of the rest pose, without being rotated, its rotation is applied after the
+    /// rotation of the rest pose (`rest.rotation * rotation`), in the local space of the entity,
+    /// and its scale multiplies the scale of the rest pose. Entities without a [`RestPose`] use
+    /// the identity transform, as if the clip was in [`CurveSpace::Local`].
+    ///
+    /// The composed pose is then blended as usual, so a base clip in local space can be mixed
+    /// with clips of offsets authored once for all the skeletons sharing the same bones.
+    DeltaFromRest,
+}
+
+/// Rest pose of an animated entity, that the clips in [`CurveSpace::DeltaFromRest`] are
+/// composed onto
+#[derive(Component, Reflect, Clone, Copy, Debug, Default)]
+#[reflect(Component)]
+pub struct RestPose(pub Transform);
+
+/// Up axis of a right-handed coordinate system, used to convert clips with
+/// [`AnimationClip::convert_axes`]
+#[derive(Reflect, FromReflect, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UpAxis {
+    /// Y is up, as used by Bevy and glTF
+    Y,
+    /// Z is up, as used by some modeling tools
+    Z,
+}
+
+impl UpAxis {
+    /// Rotation from a Y-up coordinate system to this one
+    fn rotation_from_y_up(self) -> Quat {
+        match self {
+            UpAxis::Y => Quat::IDENTITY,
+            UpAxis::Z => Quat::from_rotation_x(std::f32::consts::FRAC_PI_2),
+        }
+    }
+}
+
+/// Animation controls
+///
+/// The player has a main animation, controlled directly through its methods, and any number of
+/// named slots playing other animations on top of it. The main animation is applied first, then
+/// the slots in the order they were added, each one blended with the result of the previous ones
+/// according to its [weight](PlayingAnimation::set_weight).
+///
+/// When the main animation is replaced with a
+/// [transition](AnimationPlayer::play_with_transition), the previous one keeps playing and is
+/// faded out on top of the new one, before the slots are applied.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct AnimationPlayer {
+    enabled: bool,
+    driver: AnimationDriver,
+    update_interval: Duration,
+    time_to_update: f32,
+    applied_last_frame: bool,
+    speed_scaled_transitions: bool,
+    synced_transitions: bool,
+    average_transition_rotations: bool,
+    min_weight: f32,
+    master_weight: f32,
+    output: AnimationOutput,
+    #[reflect(ignore)]
+    bone_matrices: Vec<Mat4>,
+    inspected_bone: Option<EntityPath>,
+    #[reflect(ignore)]
+    bone_contributions: Vec<BoneContribution>,
+    root_offset: EntityPath,
+    animation: PlayingAnimation,
+    #[reflect(ignore)]
+    transitions: Vec<AnimationTransition>,
+    slots: Vec<(String, PlayingAnimation)>,
+    markers: Vec<(f32, u32)>,
+    normalized_markers: Vec<(f32, u32)>,
+    frozen_bones: Vec<EntityPath>,
+    value_transforms: Vec<(EntityPath, ValueTransform)>,
+    hold_first_frame: bool,
+    anchor: Option<Vec3>,
+    #[reflect(ignore)]
+    blend_tree: Option<BlendTree>,
+    blend_tree_elapsed: f32,
+    #[reflect(ignore)]
+    held_clip: Option<HandleId>,
+    #[reflect(ignore)]
+    root_warned: bool,
+    #[reflect(ignore)]
+    path_cache: HashMap<EntityPath, Entity>,
+}
+
+/// Adjustment of the values sampled from the curves of an entity, set with
+/// [`AnimationPlayer::set_value_transform`]
+///
+/// The sampled translations and scales are multiplied by their factor then offset, and the sampled
+/// rotations are composed with `rotation`. The identity by default.
+#[derive(Reflect, FromReflect, Clone, Copy, Debug, PartialEq)]
+pub struct ValueTransform {
+    /// Factor of the sampled translations, `1.5` exaggerating the motion by half
+    pub translation_factor: Vec3,
+    /// Added to the sampled translations, after the factor
+    pub translation_offset: Vec3,
+    /// Applied on top of the sampled rotations
+    pub rotation: Quat,
+    /// Factor of the sampled scales
+    pub scale_factor: Vec3,
+    /// Added to the sampled scales, after the factor
+    pub scale_offset: Vec3,
+}
+
+impl Default for ValueTransform {
+    fn default() -> Self {
+        Self {
+            translation_factor: Vec3::ONE,
+            translation_offset: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+            scale_factor: Vec3::ONE,
+            scale_offset: Vec3::ZERO,
+        }
+    }
+}
+
+impl ValueTransform {
+    /// Multiply the sampled translations by `factor`
+    pub fn with_translation_factor(mut self, factor: Vec3) -> Self {
+        self.translation_factor = factor;
+        self
+    }
+
+    /// Offset the sampled translations by `offset`
+    pub fn with_translation_offset(mut self, offset: Vec3) -> Self {
+        self.translation_offset = offset;
+        self
+    }
+
+    /// Apply `rotation` on top of the sampled rotations
+    pub fn with_rotation(mut self, rotation: Quat) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Multiply the sampled scales by `factor`
+    pub fn with_scale_factor(mut self, factor: Vec3) -> Self {
+        self.scale_factor = factor;
+        self
+    }
+
+    /// Offset the sampled scales by `offset`
+    pub fn with_scale_offset(mut self, offset: Vec3) -> Self {
+        self.scale_offset = offset;
+        self
+    }
+
+    /// Adjust a sampled translation
+    pub fn transform_translation(&self, translation: Vec3) -> Vec3 {
+        translation * self.translation_factor + self.translation_offset
+    }
+
+    /// Adjust a sampled rotation
+    pub fn transform_rotation(&self, rotation: Quat) -> Quat {
+        self.rotation * rotation
+    }
+
+    /// Adjust a sampled scale
+    pub fn transform_scale(&self, scale: Vec3) -> Vec3 {
+        scale * self.scale_factor + self.scale_offset
+    }
+}
+
+/// [`ValueTransform`] of the entity at `path`, if it has one
+fn find_value_transform<'a>(
+    value_transforms: &'a [(EntityPath, ValueTransform)],
+    path: &EntityPath,
+) -> Option<&'a ValueTransform> {
+    value_transforms
+        .iter()
+        .find(|(transformed, _)| transformed == path)
+        .map(|(_, value_transform)| value_transform)
+}
+
+/// What drives the elapsed time of the animations of an [`AnimationPlayer`]
+#[derive(Reflect, FromReflect, Clone, Copy, Debug, Default, PartialEq)]
+pub enum AnimationDriver {
+    /// Animations advance with the frame delta of [`Time`], scaled by their speed.
+    #[default]
+    Time,
+    /// The elapsed time of the main animation is set to this value every frame, and the other
+    /// animations of the player don't advance.
+    ///
+    /// The pose is computed only from the clip and this value, which should be updated every
+    /// tick, for example from the position of a music track in a rhythm game. This avoids the
+    /// floating point drift of accumulating the frame delta, so a pose can be reproduced exactly
+    /// from `(clip, elapsed)` for replays or rollback networking. The value can jump forward or
+    /// backward: completions are computed from it, so repeating animations loop as expected. The
+    /// speed of the animations is ignored in this mode.
+    External(f32),
+    /// The elapsed time of the main animation is set to the elapsed time of the
+    /// [`AnimationClock`] on this entity every frame, like with
+    /// [`External`](AnimationDriver::External).
+    ///
+    /// Players bound to the same clock stay in lockstep, even when they start playing at
+    /// different times, for example the dancers of a group. The animations hold their pose while
+    /// the entity has no clock.
+    Clock(Entity),
+}
+
+/// Elapsed time shared by the [`AnimationPlayer`]s driven by [`AnimationDriver::Clock`]
+///
+/// The clock advances with the frame delta of the [`AnimationPlugin`], scaled by its speed and
+/// the [`GlobalAnimationSpeed`], before the players are updated.
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component)]
+pub struct AnimationClock {
+    elapsed: f32,
+    speed: f32,
+    paused: bool,
+}
+
+impl Default for AnimationClock {
+    fn default() -> Self {
+        Self {
+            elapsed: 0.0,
+            speed: 1.0,
+            paused: false,
+        }
+    }
+}
+
+impl AnimationClock {
+    /// Time elapsed since the clock started, in seconds
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    /// Set the elapsed time of the clock, seeking all the players bound to it
+    pub fn set_elapsed(&mut self, elapsed: f32) -> &mut Self {
+        self.elapsed = elapsed;
+        self
+    }
+
+    /// Speed of the clock
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Set the speed of the clock, `1.0` by default
+    pub fn set_speed(&mut self, speed: f32) -> &mut Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Pause the clock
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Unpause the clock
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Is the clock paused
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+}
+
+/// System that advances the [`AnimationClock`]s with the frame delta of `T`
+fn advance_animation_clocks<T: DeltaSeconds>(
+    time: Res<T>,
+    global_speed: Option<Res<GlobalAnimationSpeed>>,
+    global_pause: Option<Res<GlobalAnimationPause>>,
+    mut clocks: Query<&mut AnimationClock>,
+) {
+    let delta = time.delta_seconds() * global_speed_factor(global_speed, global_pause);
+    for mut clock in &mut clocks {
+        if !clock.paused {
+            clock.elapsed += delta * clock.speed;
+        }
+    }
+}
+
+/// An animation applied to the bone inspected with [`AnimationPlayer::set_inspected_bone`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoneContribution {
+    /// Weak handle to the clip of the animation.
+    pub clip: Handle<AnimationClip>,
+    /// Weight the animation was applied to the bone with, including its mask.
+    pub weight: f32,
+    /// Pose of the bone sampled from the clip, before being blended with the other animations.
+    ///
+    /// Attributes the clip doesn't animate are left to identity. For an additive animation,
+    /// this is the pose added to the previous ones.
+    pub value: Transform,
+}
+
+/// Where an [`AnimationPlayer`] writes the pose of its animations
+#[derive(Reflect, FromReflect, Clone, Debug, Default, PartialEq)]
+pub enum AnimationOutput {
+    /// The [`Transform`] of the entities targeted by the animations is updated.
+    #[default]
+    Transforms,
+    /// The local matrix of each bone is written to
+    /// [`AnimationPlayer::bone_matrices`], at the index of its path in this list, and no
+    /// [`Transform`] is changed.
+    ///
+    /// This avoids looking up and changing an entity per bone, for crowds skinned on the GPU
+    /// from a buffer of matrices. The poses start from the identity every update, as there is no
+    /// [`Transform`] to blend with. Clips in [`CurveSpace::DeltaFromRest`] are composed with the
+    /// identity, and the [anchor](AnimationPlayer::set_anchor) is ignored.
+    Matrices(Vec<EntityPath>),
+}
+
+/// An animation that was replaced as the main animation of a player and is being faded out
+#[derive(Clone)]
+struct AnimationTransition {
+    /// Weight of the animation before easing, decreasing linearly to `0.0` over the transition
+    current_weight: f32,
+    /// How much weight is lost each second
+    weight_decline_per_sec: f32,
+    easing: Easing,
+    /// Is the weight set with [`AnimationPlayer::set_transition_weight`] instead of declining
+    manual: bool,
+    animation: PlayingAnimation,
+}
+
+impl AnimationTransition {
+    /// Weight the animation is applied with, after easing
+    fn weight(&self) -> f32 {
+        (1.0 - self.easing.ease(1.0 - self.current_weight)) * self.animation.weight
+    }
+}
+
+/// Easing function, remapping the progress of a transition
+#[derive(Reflect, FromReflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Easing {
+    /// Constant rate of change.
+    #[default]
+    Linear,
+    /// Starts slowly and accelerates.
+    EaseIn,
+    /// Starts quickly and decelerates.
+    EaseOut,
+    /// Starts and ends slowly.
+    EaseInOut,
+}
+
+impl Easing {
+    /// Remap `t`, between `0.0` and `1.0`, keeping `0.0` and `1.0` unchanged
+    pub fn ease(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+impl Default for AnimationPlayer {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            driver: AnimationDriver::Time,
+            update_interval: Duration::ZERO,
+            time_to_update: 0.0,
+            applied_last_frame: false,
+            speed_scaled_transitions: false,
+            average_transition_rotations: false,
+            synced_transitions: false,
+            min_weight: 0.0,
+            master_weight: 1.0,
+            output: AnimationOutput::Transforms,
+            bone_matrices: Vec::new(),
+            inspected_bone: None,
+            bone_contributions: Vec::new(),
+            root_offset: EntityPath::default(),
+            animation: Default::default(),
+            transitions: Vec::new(),
+            slots: Vec::new(),
+            markers: Vec::new(),
+            normalized_markers: Vec::new(),
+            frozen_bones: Vec::new(),
+            value_transforms: Vec::new(),
+            hold_first_frame: false,
+            anchor: None,
+            blend_tree: None,
+            blend_tree_elapsed: 0.0,
+            held_clip: None,
+            root_warned: false,
+            path_cache: HashMap::default(),
+        }
+    }
+}
+
+/// Clones the whole playback state, including the animations being faded out.
+///
+/// The cache of resolved entities isn't copied, as the clone is usually inserted on an entity with
+/// a different hierarchy, for example to replay the animation on a ghost of the original entity.
+impl Clone for AnimationPlayer {
+    fn clone(&self) -> Self {
+        Self {
+            enabled: self.enabled,
+            driver: self.driver,
+            update_interval: self.update_interval,
+            time_to_update: self.time_to_update,
+            applied_last_frame: self.applied_last_frame,
+            speed_scaled_transitions: self.speed_scaled_transitions,
+            average_transition_rotations: self.average_transition_rotations,
+            synced_transitions: self.synced_transitions,
+            min_weight: self.min_weight,
+            master_weight: self.master_weight,
+            output: self.output.clone(),
+            bone_matrices: self.bone_matrices.clone(),
+            inspected_bone: self.inspected_bone.clone(),
+            bone_contributions: self.bone_contributions.clone(),
+            root_offset: self.root_offset.clone(),
+            animation: self.animation.clone(),
+            transitions: self.transitions.clone(),
+            slots: self.slots.clone(),
+            markers: self.markers.clone(),
+            normalized_markers: self.normalized_markers.clone(),
+            frozen_bones: self.frozen_bones.clone(),
+            value_transforms: self.value_transforms.clone(),
+            hold_first_frame: self.hold_first_frame,
+            anchor: self.anchor,
+            blend_tree: self.blend_tree.clone(),
+            blend_tree_elapsed: self.blend_tree_elapsed,
+            held_clip: self.held_clip,
+            root_warned: false,
+            path_cache: HashMap::default(),
+        }
+    }
+}
+
+/// Target pose that the animated pose of an entity is blended toward, so that procedural changes
+/// and animations coexist
+///
+/// A procedural system such as inverse kinematics writing the [`Transform`] of a bone after the
+/// animations fights with the [`AnimationPlayer`], which overwrites it again the next frame. It
+/// can instead set the target of this component between [`AnimationSystem::Sample`] and
+/// [`AnimationSystem::Apply`], reading the sampled pose, and the pose is blended toward it with
+/// `weight` every frame.
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component)]
+pub struct AnimationOverride {
+    /// Pose blended into the animated pose
+    pub target: Transform,
+    /// Weight of the target, `1.0` replacing the animated pose
+    pub weight: f32,
+    /// Last pose sampled by the animations, and the pose it was blended to
+    #[reflect(ignore)]
+    blended: Option<(Transform, Transform)>,
+}
+
+impl Default for AnimationOverride {
+    fn default() -> Self {
+        Self::new(Transform::IDENTITY, 1.0)
+    }
+}
+
+impl AnimationOverride {
+    /// Create an override blending toward `target` with `weight`
+    pub fn new(target: Transform, weight: f32) -> Self {
+        Self {
+            target,
+            weight,
+            blended: None,
+        }
+    }
+}
+
+/// System that blends the [`Transform`]s of the entities with an [`AnimationOverride`] toward its
+/// target
+fn apply_animation_overrides(mut overrides: Query<(&mut AnimationOverride, &mut Transform)>) {
+    for (mut animation_override, mut transform) in &mut overrides {
+        // The pose isn't written again when the animation doesn't move, and blending the previous
+        // result again would drift toward the target
+        let sampled = match animation_override.blended {
+            Some((sampled, blended)) if pose_approx_eq(&blended, &transform) => sampled,
+            _ => *transform,
+        };
+        let mut pose = sampled;
+        blend_transform(
+            &mut pose,
+            animation_override.target,
+            animation_override.weight,
+        );
+        animation_override.blended = Some((sampled, pose));
+        if !pose_approx_eq(&pose, &transform) {
+            *transform = pose;
+        }
+    }
+}
+
+/// An [`AnimationPlayer`] with the [`Handle`] of the clip it plays
+///
+/// The player plays the clip of the handle when the bundle is spawned, and again each time the
+/// handle component is replaced, so that the clip of a character can be set without going through
+/// the player. A handle to a clip that isn't loaded is reported by [`AnimationStrictMode`].
+#[derive(Bundle, Default)]
+pub struct AnimationBundle {
+    /// The player of the clip
+    pub player: AnimationPlayer,
+    /// The clip the player plays
+    pub clip: Handle<AnimationClip>,
+}
+
+impl AnimationBundle {
+    /// Create a bundle playing `clip`
+    pub fn new(clip: Handle<AnimationClip>) -> Self {
+        Self {
+            player: AnimationPlayer::default(),
+            clip,
+        }
+    }
+}
+
+/// System that plays the clip of the [`Handle`] components added or changed next to an
+/// [`AnimationPlayer`], as in an [`AnimationBundle`]
+fn play_clip_handles(
+    mut players: Query<
+        (&mut AnimationPlayer, &Handle<AnimationClip>),
+        Changed<Handle<AnimationClip>>,
+    >,
+) {
+    for (mut player, clip) in &mut players {
+        if *clip != Handle::default() && !player.is_playing_asset(clip) {
+            player.play(clip.clone());
+        }
+    }
+}
+
+/// A one-line summary of the main animation of the player, for logging
+///
+/// The clip is shown as the id of its handle, so this doesn't need the [`Assets`] of the clips.
+impl fmt::Display for AnimationPlayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let animation = &self.animation;
+        write!(
+            f,
+            "{:?} at {:.3}s, speed {}, {}, {} completions, {} transitions, {} slots",
+            animation.animation_clip.id(),
+            animation.elapsed,
+            animation.speed,
+            if !self.enabled {
+                "disabled"
+            } else if animation.paused {
+                "paused"
+            } else {
+                "playing"
+            },
+            animation.completions,
+            self.transitions.len(),
+            self.slots.len(),
+        )
+    }
+}
+
+impl AnimationPlayer {
+    /// Start playing an animation, resetting state of the main animation
+    ///
+    /// This is a hard cut: any ongoing transition is dropped.
+    pub fn play(&mut self, handle: Handle<AnimationClip>) -> &mut Self {
+        self.transitions.clear();
+        self.blend_tree = None;
+        self.animation.play(handle);
+        self
+    }
+
+    /// Start playing an animation from a random time between `0.0` and `max_jitter`, so that
+    /// players started together, like the members of a crowd, don't move in sync
+    ///
+    /// The time is derived from `seed`, for example the index of the entity in its crowd, so a
+    /// seed always gives the same start time. Like [`play`](AnimationPlayer::play), this is a
+    /// hard cut.
+    pub fn start_with_jitter(
+        &mut self,
+        handle: Handle<AnimationClip>,
+        max_jitter: f32,
+        seed: u64,
+    ) -> &mut Self {
+        let mut hasher = Fnv1aHasher::default();
+        hasher.write(&seed.to_le_bytes());
+        // The 24 high bits fit exactly in the mantissa of a `f32`
+        let random = (hasher.0 >> 40) as f32 / (1 << 24) as f32;
+        self.play(handle).set_elapsed(random * max_jitter)
+    }
+
+    /// Start playing an animation, resetting the state of the main animation but keeping the
+    /// ongoing transitions
+    ///
+    /// Unlike [`play`](AnimationPlayer::play), this is not a hard cut: the previous animations
+    /// being faded out keep fading out on top of the new one.
+    pub fn start_keep_transitions(&mut self, handle: Handle<AnimationClip>) -> &mut Self {
+        self.blend_tree = None;
+        self.animation.play(handle);
+        self
+    }
+
+    /// Start playing a blend tree as the main animation, from its start
+    ///
+    /// The tree advances with the [speed](AnimationPlayer::set_speed) of the main animation, is
+    /// paused with it, and is applied with its [weight](PlayingAnimation::set_weight) before the
+    /// transitions and the slots. With an [external driver](AnimationDriver::External), the tree
+    /// is sampled at the time of the driver. Playing a clip as the main animation stops the tree.
+    pub fn play_blend_tree(&mut self, blend_tree: BlendTree) -> &mut Self {
+        self.transitions.clear();
+        self.animation.play(Handle::default());
+        self.blend_tree = Some(blend_tree);
+        self.blend_tree_elapsed = 0.0;
+        self
+    }
+
+    /// Start playing an [`AnimationSource`], such as a [`ProceduralClip`], as the main animation
+    ///
+    /// This plays a [`BlendTree`] with the source as its only node, see
+    /// [`play_blend_tree`](AnimationPlayer::play_blend_tree).
+    pub fn play_source(&mut self, source: impl AnimationSource) -> &mut Self {
+        self.play_blend_tree(BlendTree::new(BlendNode::source(source)))
+    }
+
+    /// Blend tree played as the main animation, if any
+    pub fn blend_tree(&self) -> Option<&BlendTree> {
+        self.blend_tree.as_ref()
+    }
+
+    /// Mutable blend tree played as the main animation, if any, to set its parameters
+    pub fn blend_tree_mut(&mut self) -> Option<&mut BlendTree> {
+        self.blend_tree.as_mut()
+    }
+
+    /// Time elapsed playing the blend tree
+    pub fn blend_tree_elapsed(&self) -> f32 {
+        self.blend_tree_elapsed
+    }
+
+    /// Start playing an animation, fading out the current one over `transition_duration`
+    ///
+    /// The previous main animation keeps playing while its weight declines linearly to `0.0`.
+    pub fn play_with_transition(
+        &mut self,
+        handle: Handle<AnimationClip>,
+        transition_duration: Duration,
+    ) -> &mut Self {
+        self.play_with_transition_eased(handle, transition_duration, Easing::Linear)
+    }
+
+    /// Same as [`play_with_transition`](AnimationPlayer::play_with_transition), with the weight of
+    /// the previous animation declining along `easing`
+    ///
+    /// The weight still reaches `0.0` exactly at the end of the transition.
+    pub fn play_with_transition_eased(
+        &mut self,
+        handle: Handle<AnimationClip>,
+        transition_duration: Duration,
+        easing: Easing,
+    ) -> &mut Self {
+        // Keep the same settings as when playing without a transition
+        let mut animation = self.animation.clone();
+        animation.play(handle);
+        self.blend_tree = None;
+        std::mem::swap(&mut animation, &mut self.animation);
+        self.transitions.push(AnimationTransition {
+            current_weight: 1.0,
+            weight_decline_per_sec: 1.0 / transition_duration.as_secs_f32(),
+            easing,
+            manual: false,
+            animation,
+        });
+        self
+    }
+
+    /// Blend into a static pose over `over`, then hold it
+    ///
+    /// `pose` is a clip with a single keyframe per curve. The current animation is faded out toward
+    /// it as with [`play_with_transition`](AnimationPlayer::play_with_transition), and playing
+    /// another animation with a transition blends back out of the pose.
+    pub fn blend_to_pose(&mut self, pose: Handle<AnimationClip>, over: Duration) -> &mut Self {
+        self.play_with_transition(pose, over)
+    }
+
+    /// Start playing the same animation on many players in one pass
+    ///
+    /// `time_offset` is called once per player to get the elapsed time it starts at, which can be
+    /// used to desynchronize a crowd with random offsets. Players only get a weak handle to the
+    /// clip, so the strong `handle` must be kept alive elsewhere.
+    ///
+    /// ```
+    /// # use bevy_animation::prelude::*;
+    /// # use bevy_asset::Handle;
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Crowd;
+    ///
+    /// fn start_crowd(
+    ///     mut players: Query<&mut AnimationPlayer, With<Crowd>>,
+    ///     walk: Res<Walk>,
+    /// ) {
+    ///     let mut offset = 0.0;
+    ///     AnimationPlayer::play_all(&mut players, &walk.0, || {
+    ///         offset += 0.1;
+    ///         offset
+    ///     });
+    /// }
+    /// # #[derive(Resource)]
+    /// # struct Walk(Handle<AnimationClip>);
+    /// # bevy_ecs::system::assert_is_system(start_crowd);
+    /// ```
+    pub fn play_all<'a>(
+        players: impl IntoIterator<Item = Mut<'a, AnimationPlayer>>,
+        handle: &Handle<AnimationClip>,
+        mut time_offset: impl FnMut() -> f32,
+    ) {
+        for mut player in players {
+            player.play(handle.clone_weak()).set_elapsed(time_offset());
+        }
+    }
+
+    /// Start playing the animation registered as `name` in `clips`, resetting state of the main
+    /// animation
+    ///
+    /// Does nothing if there is no animation with this name.
+    pub fn play_named(&mut self, clips: &AnimationClips, name: &str) -> &mut Self {
+        match clips.get(name) {
+            Some(handle) => self.play(handle.clone_weak()),
+            None => {
+                warn!("no animation clip named {name:?}");
+                self
+            }
+        }
+    }
+
+    /// Start playing an animation backward from its end, resetting state of the main animation
+    ///
+    /// See [`PlayingAnimation::play_reversed`].
+    pub fn play_reversed(&mut self, handle: Handle<AnimationClip>) -> &mut Self {
+        self.animation.play_reversed(handle);
+        self
+    }
+
+    /// Stop the animation, clearing the clip and resetting the playback state of the player
+    ///
+    /// A stopped player is idle and will not modify any [`Transform`] until a new animation is
+    /// played. All the transitions, slots and the blend tree are removed, while the configuration
+    /// of the player, like its root offset or master weight, is kept.
+    pub fn stop(&mut self) -> &mut Self {
+        self.animation = PlayingAnimation::default();
+        self.transitions.clear();
+        self.slots.clear();
+        self.blend_tree = None;
+        self.blend_tree_elapsed = 0.0;
+        self.held_clip = None;
+        self
+    }
+
+    /// Is the clip of the main animation the asset `id`
+    ///
+    /// The clip is compared by its asset id, so any strong or weak handle to it, or its asset
+    /// path, can be used.
+    pub fn is_playing_asset(&self, id: impl Into<HandleId>) -> bool {
+        self.animation.animation_clip.id() == id.into()
+    }
+
+    /// Restart the main animation and the slots from their start, ending the transitions
+    ///
+    /// Unlike [`stop`](AnimationPlayer::stop), the clips and settings of the animations are kept,
+    /// so the player applies the first frame of its clips on its next update, for example when
+    /// respawning a character. See [`ResetAnimationPlayers`] to reset all the players at once.
+    pub fn reset(&mut self) -> &mut Self {
+        self.transitions.clear();
+        self.animation.reset();
+        for (_, animation) in &mut self.slots {
+            animation.reset();
+        }
+        self
+    }
+
+    /// Set the animation to repeat forever
+    pub fn repeat(&mut self) -> &mut Self {
+        self.animation.repeat();
+        self
+    }
+
+    /// Stop the animation from repeating
+    pub fn stop_repeating(&mut self) -> &mut Self {
+        self.animation.stop_repeating();
+        self
+    }
+
+    /// How many times the animation is played
+    pub fn repeat_mode(&self) -> RepeatAnimation {
+        self.animation.repeat_mode()
+    }
+
+    /// Set how many times the animation is played
+    pub fn set_repeat(&mut self, repeat: RepeatAnimation) -> &mut Self {
+        self.animation.set_repeat(repeat);
+        self
+    }
+
+    /// What the main animation does once it's finished
+    pub fn finished_behavior(&self) -> FinishedBehavior {
+        self.animation.finished_behavior()
+    }
+
+    /// How the scale of the main animation is combined with the scale of the entities
+    pub fn scale_blend(&self) -> ScaleBlend {
+        self.animation.scale_blend()
+    }
+
+    /// Set how the scale of the main animation is combined with the scale of the entities
+    ///
+    /// See [`PlayingAnimation::set_scale_blend`].
+    pub fn set_scale_blend(&mut self, scale_blend: ScaleBlend) -> &mut Self {
+        self.animation.set_scale_blend(scale_blend);
+        self
+    }
+
+    /// Set what the main animation does once it's finished
+    ///
+    /// See [`FinishedBehavior`].
+    pub fn set_finished_behavior(&mut self, finished_behavior: FinishedBehavior) -> &mut Self {
+        self.animation.set_finished_behavior(finished_behavior);
+        self
+    }
+
+    /// Factor the speed of the main animation is multiplied by each time it completes
+    pub fn loop_speed_multiplier(&self) -> f32 {
+        self.animation.loop_speed_multiplier()
+    }
+
+    /// Set the factor the speed of the main animation is multiplied by each time it completes
+    ///
+    /// See [`PlayingAnimation::set_loop_speed_multiplier`].
+    pub fn set_loop_speed_multiplier(&mut self, multiplier: f32) -> &mut Self {
+        self.animation.set_loop_speed_multiplier(multiplier);
+        self
+    }
+
+    /// Number of times the animation completed
+    ///
+    /// See [`PlayingAnimation::completions`].
+    pub fn completions(&self) -> u32 {
+        self.animation.completions()
+    }
+
+    /// Has the animation been played as many times as its [`RepeatAnimation`] requires
+    pub fn is_finished(&self) -> bool {
+        self.animation.is_finished()
+    }
+
+    /// Time until the main animation next completes
+    ///
+    /// See [`PlayingAnimation::time_to_next_completion`].
+    pub fn time_to_next_completion(&self, animation_clip: &AnimationClip) -> Option<Duration> {
+        self.animation.time_to_next_completion(animation_clip)
+    }
+
+    /// Advance the main animation by `delta`, scaled by its speed, even if it's paused
+    ///
+    /// To step an animation by exact amounts of time, for example when rendering to a video
+    /// frame by frame, pause the player and call this method every frame: as the player is
+    /// changed, [`animation_player`] applies the pose at the new elapsed time without advancing it
+    /// by the frame delta of [`Time`].
+    pub fn advance(&mut self, delta: Duration, animation_clip: &AnimationClip) -> &mut Self {
+        self.animation.advance(delta, animation_clip);
+        self
+    }
+
+    /// Skip the main animation forward by `delta` seconds
+    ///
+    /// See [`PlayingAnimation::seek_by`].
+    pub fn advance_by(&mut self, delta: f32, animation_clip: &AnimationClip) -> &mut Self {
+        self.animation.seek_by(delta, animation_clip);
+        self
+    }
+
+    /// Skip the main animation backward by `delta` seconds
+    ///
+    /// See [`PlayingAnimation::seek_by`].
+    pub fn rewind_by(&mut self, delta: f32, animation_clip: &AnimationClip) -> &mut Self {
+        self.animation.seek_by(-delta, animation_clip);
+        self
+    }
+
+    /// Step the main animation by `delta`, scaled by its speed, for frame-by-frame debugging
+    ///
+    /// This is meant to be called on a paused player: as the player is changed,
+    /// [`animation_player`] applies the pose at the new elapsed time on its next run. The elapsed
+    /// time is wrapped, the completions counted and the
+    /// [loop points](AnimationClip::set_loop_points) of the clip followed as during normal
+    /// playback, like with [`advance`](AnimationPlayer::advance).
+    pub fn step(&mut self, delta: Duration, animation_clip: &AnimationClip) -> &mut Self {
+        self.advance(delta, animation_clip)
+    }
+
+    /// Leave the loop of the main animation, playing the rest of its clip to its end
+    ///
+    /// See [`AnimationClip::set_loop_points`].
+    pub fn play_outro(&mut self) -> &mut Self {
+        self.animation.play_outro();
+        self
+    }
+
+    /// Is the player enabled
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enable or disable the player
+    ///
+    /// A disabled player neither advances nor applies its animation, but keeps its clip, speed and
+    /// elapsed time so that it resumes exactly where it was once enabled again. Unlike
+    /// [`pause`](AnimationPlayer::pause), the pose is not re-applied when the player is changed.
+    pub fn set_enabled(&mut self, enabled: bool) -> &mut Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Pause the main animation
+    ///
+    /// Only the main animation is paused: the animations being faded out by a
+    /// [transition](AnimationPlayer::play_with_transition) keep advancing until their fade ends,
+    /// and the slots are paused separately. This holds the first frame of the new animation while
+    /// the old one finishes fading out. Once all the animations of the player are paused, the pose
+    /// is only applied again on the frames the player is changed, for example by
+    /// [`set_elapsed`](AnimationPlayer::set_elapsed).
+    pub fn pause(&mut self) {
+        self.animation.pause();
+    }
+
+    /// Unpause the animation
+    pub fn resume(&mut self) {
+        self.animation.resume();
+    }
+
+    /// Is the animation paused
+    pub fn is_paused(&self) -> bool {
+        self.animation.is_paused()
+    }
+
+    /// Speed of the animation playback
+    pub fn speed(&self) -> f32 {
+        self.animation.speed()
+    }
+
+    /// Set the speed of the animation playback
+    pub fn set_speed(&mut self, speed: f32) -> &mut Self {
+        self.animation.set_speed(speed);
+        self
+    }
+
+    /// Time elapsed playing the animation
+    pub fn elapsed(&self) -> f32 {
+        self.animation.elapsed()
+    }
+
+    /// Seek to a specific time in the animation
+    ///
+    /// The [markers](AnimationPlayer::add_marker) and
+    /// [intervals](AnimationClip::add_interval_event) between the previous and the new elapsed
+    /// time don't send events, as when scrubbing a timeline. See
+    /// [`set_elapsed_with_events`](AnimationPlayer::set_elapsed_with_events) to send them.
+    pub fn set_elapsed(&mut self, elapsed: f32) -> &mut Self {
+        self.set_elapsed_with_events(elapsed, false)
+    }
+
+    /// Seek to a specific time in the animation, sending the events of the markers and intervals
+    /// skipped over if `fire` is `true`
+    ///
+    /// Firing suits gameplay skips, like jumping to a checkpoint, which should still trigger the
+    /// skipped footsteps or hitboxes. The events are sent on the next run of [`animation_player`],
+    /// as if the animation had played from the elapsed time before the first seek since its last
+    /// run to the new one, across as many loops as needed. Seeking backward sends no events.
+    pub fn set_elapsed_with_events(&mut self, elapsed: f32, fire: bool) -> &mut Self {
+        let animation = &mut self.animation;
+        animation.seek_from = match animation.seek_from {
+            Some(seek_from) if fire => Some(seek_from),
+            _ if fire => Some((animation.elapsed, animation.completions)),
+            _ => None,
+        };
+        animation.set_elapsed(elapsed);
+        self
+    }
+
+    /// Seek to a specific time in the animation, kept within the time it plays for, see
+    /// [`PlayingAnimation::set_elapsed_clamped`]
+    pub fn set_elapsed_clamped(
+        &mut self,
+        elapsed: f32,
+        animation_clip: &AnimationClip,
+    ) -> &mut Self {
+        self.animation.set_elapsed_clamped(elapsed, animation_clip);
+        self
+    }
+
+    /// Time in `animation_clip` at which the animation is sampled, see
+    /// [`PlayingAnimation::elapsed_wrapped`]
+    pub fn elapsed_wrapped(&self, animation_clip: &AnimationClip) -> f32 {
+        self.animation.elapsed_wrapped(animation_clip)
+    }
+
+    /// Position of the main animation within its current loop of `animation_clip`, see
+    /// [`PlayingAnimation::loop_progress`]
+    pub fn loop_progress(&self, animation_clip: &AnimationClip) -> f32 {
+        self.animation.loop_progress(animation_clip)
+    }
+
+    /// Time warp remapping the time of the animation, if any
+    pub fn time_warp(&self) -> Option<&TimeWarp> {
+        self.animation.time_warp()
+    }
+
+    /// Set a [`TimeWarp`] remapping the time of the animation before sampling it
+    ///
+    /// The elapsed time of the player is not remapped, only the time used to sample the clip.
+    pub fn set_time_warp(&mut self, time_warp: Option<TimeWarp>) -> &mut Self {
+        self.animation.set_time_warp(time_warp);
+        self
+    }
+
+    /// What drives the elapsed time of the animations of the player
+    pub fn driver(&self) -> AnimationDriver {
+        self.driver
+    }
+
+    /// Set what drives the elapsed time of the animations of the player
+    ///
+    /// See [`AnimationDriver`].
+    pub fn set_driver(&mut self, driver: AnimationDriver) -> &mut Self {
+        self.driver = driver;
+        self
+    }
+
+    /// Minimum time between two applications of the pose of the player
+    pub fn update_interval(&self) -> Duration {
+        self.update_interval
+    }
+
+    /// Set the minimum time between two applications of the pose of the player
+    ///
+    /// The animations keep advancing every frame, but their pose is only sampled and applied once
+    /// per interval and held in between. This can be used to animate distant characters at a
+    /// lower rate. With an interval of [`Duration::ZERO`], the default, the pose is applied every
+    /// frame.
+    pub fn set_update_interval(&mut self, update_interval: Duration) -> &mut Self {
+        self.update_interval = update_interval;
+        self
+    }
+
+    /// Count down to the next application of the pose by `delta` seconds, returning whether the
+    /// pose should be applied this frame
+    fn is_update_due(&mut self, delta: f32) -> bool {
+        self.time_to_update -= delta;
+        if self.time_to_update > 0.0 {
+            return false;
+        }
+        // Don't try to catch up with the updates missed during a long frame
+        self.time_to_update = (self.time_to_update + self.update_interval.as_secs_f32()).max(0.0);
+        true
+    }
+
+    /// Is a previous main animation still being faded out after a
+    /// [transition](AnimationPlayer::play_with_transition)
+    pub fn is_transitioning(&self) -> bool {
+        !self.transitions.is_empty()
+    }
+
+    /// Number of previous main animations being faded out
+    pub fn transition_count(&self) -> usize {
+        self.transitions.len()
+    }
+
+    /// Weight of the previous main animation faded out by the transition at `index`, before
+    /// easing
+    ///
+    /// Transitions are ordered from the oldest to the most recent. Returns `None` if there is no
+    /// transition at `index`.
+    pub fn transition_weight(&self, index: usize) -> Option<f32> {
+        self.transitions
+            .get(index)
+            .map(|transition| transition.current_weight)
+    }
+
+    /// Set the weight of the previous main animation faded out by the transition at `index`,
+    /// taking control of its fade
+    ///
+    /// Transitions are ordered from the oldest to the most recent. The weight, between `0.0` and
+    /// `1.0`, isn't eased and stops declining with time: it stays as set until it's set again, for
+    /// example every frame from a cutscene, and the transition ends once it's set to `0.0`. Does
+    /// nothing if there is no transition at `index`.
+    pub fn set_transition_weight(&mut self, index: usize, weight: f32) -> &mut Self {
+        if let Some(transition) = self.transitions.get_mut(index) {
+            transition.current_weight = weight.clamp(0.0, 1.0);
+            transition.easing = Easing::Linear;
+            transition.manual = true;
+        }
+        self
+    }
+
+    /// Are the animations faded out by transitions synced to the phase of the main animation
+    pub fn synced_transitions(&self) -> bool {
+        self.synced_transitions
+    }
+
+    /// Set whether the animations faded out by transitions are synced to the phase of the main
+    /// animation
+    ///
+    /// By default, each animation advances with time during a transition, so crossfading cycles of
+    /// different durations, such as a walk and a run, blends poses at different phases. When
+    /// enabled, the animations being faded out are sampled at the same normalized time as the
+    /// main animation, keeping the feet of locomotion cycles aligned during the crossfade.
+    pub fn set_synced_transitions(&mut self, synced_transitions: bool) -> &mut Self {
+        self.synced_transitions = synced_transitions;
+        self
+    }
+
+    /// Is the pose of a paused main animation applied as soon as its clip is available
+    pub fn hold_first_frame(&self) -> bool {
+        self.hold_first_frame
+    }
+
+    /// Set whether the pose of a paused main animation is applied as soon as its clip is available
+    ///
+    /// A paused player is only applied when it's changed, so if its clip is still loading at that
+    /// point, its entities keep their previous transform, like a T-pose, until it's resumed. When
+    /// enabled, the player keeps trying until the pose of its main animation has been applied
+    /// once, which is the first keyframe of the clip for an animation that was never advanced.
+    pub fn set_hold_first_frame(&mut self, hold_first_frame: bool) -> &mut Self {
+        self.hold_first_frame = hold_first_frame;
+        self
+    }
+
+    /// World space position the translation of the root is relative to
+    pub fn anchor(&self) -> Option<Vec3> {
+        self.anchor
+    }
+
+    /// Set the world space position the translation of the root is relative to
+    ///
+    /// This is for props that follow a path keyed in world space, like a falling leaf, but aren't
+    /// children of an entity at that position. The translation `t` sampled for the root is
+    /// offset to the world position `anchor + t`, then transformed into the space of the parent
+    /// of the root with the inverse of the parent's [`GlobalTransform`], so that the root ends up
+    /// at `anchor + t` in world space. A root without parent is directly placed there. The
+    /// [`GlobalTransform`] of the parent is the one from the previous frame, as transforms are
+    /// propagated after the animations are applied. Only applies to frames where the root has an
+    /// animated translation.
+    pub fn set_anchor(&mut self, anchor: Option<Vec3>) -> &mut Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// Weight below which the animations of the player are not applied
+    pub fn min_weight(&self) -> f32 {
+        self.min_weight
+    }
+
+    /// Set the weight below which the animations of the player are not applied, `0.0` by default
+    ///
+    /// Animations whose weight, after easing for the transitions, is below `min_weight` still
+    /// advance but are skipped when applying the pose, and transitions fading out below it end
+    /// early. This saves the work of sampling animations that barely contribute to the pose.
+    /// Transitions with a [manual weight](AnimationPlayer::set_transition_weight) are skipped but
+    /// don't end.
+    pub fn set_min_weight(&mut self, min_weight: f32) -> &mut Self {
+        self.min_weight = min_weight;
+        self
+    }
+
+    /// Weight all the animations of the player are applied with
+    pub fn master_weight(&self) -> f32 {
+        self.master_weight
+    }
+
+    /// Set the weight all the animations of the player are applied with, `1.0` by default
+    ///
+    /// It multiplies the weight of the main animation, the transitions, the slots and the blend
+    /// tree, to fade the influence of the whole player, for example when a character goes limp
+    /// into a ragdoll. At `0.0`, the player leaves the transforms and the
+    /// [animated components](AnimationPlugin::register_animated) as they are. The animations
+    /// still advance whatever the master weight.
+    pub fn set_master_weight(&mut self, master_weight: f32) -> &mut Self {
+        self.master_weight = master_weight;
+        self
+    }
+
+    /// Where the pose of the animations is written
+    pub fn output(&self) -> &AnimationOutput {
+        &self.output
+    }
+
+    /// Set where the pose of the animations is written, [`AnimationOutput::Transforms`] by default
+    pub fn set_output(&mut self, output: AnimationOutput) -> &mut Self {
+        self.output = output;
+        self
+    }
+
+    /// Local matrices of the bones computed at the last update, when the output is
+    /// [`AnimationOutput::Matrices`]
+    ///
+    /// The matrix of a bone is at the index of its path in the output.
+    pub fn bone_matrices(&self) -> &[Mat4] {
+        &self.bone_matrices
+    }
+
+    /// Bone whose blending is recorded, if any
+    pub fn inspected_bone(&self) -> Option<&EntityPath> {
+        self.inspected_bone.as_ref()
+    }
+
+    /// Record how the animations are blended for the bone at `path`, to debug a blend
+    ///
+    /// Each update applying the animations, the
+    /// [contributions](AnimationPlayer::bone_contributions) of the animations with curves for
+    /// `path` are recorded, in the order they are applied. Nothing is recorded when this is
+    /// `None`, the default, so it has no cost outside of debugging. Blend trees are not recorded.
+    pub fn set_inspected_bone(&mut self, path: Option<EntityPath>) -> &mut Self {
+        self.inspected_bone = path;
+        self.bone_contributions.clear();
+        self
+    }
+
+    /// Animations applied to the [inspected bone](AnimationPlayer::set_inspected_bone) during the
+    /// last update, in the order they were applied
+    pub fn bone_contributions(&self) -> &[BoneContribution] {
+        &self.bone_contributions
+    }
+
+    /// Are transitions faded out at the speed of the main animation
+    pub fn speed_scaled_transitions(&self) -> bool {
+        self.speed_scaled_transitions
+    }
+
+    /// Set whether transitions are faded out at the speed of the main animation
+    ///
+    /// By default, a transition lasts its duration in real time whatever the speed of the
+    /// animations. When enabled, the fade is stretched by the speed of the main animation, so a
+    /// crossfade slowed down for a slow-motion effect stays in sync with the animations.
+    pub fn set_speed_scaled_transitions(&mut self, speed_scaled_transitions: bool) -> &mut Self {
+        self.speed_scaled_transitions = speed_scaled_transitions;
+        self
+    }
+
+    /// Are the rotations of the main animation and its transitions averaged in a single blend
+    pub fn average_transition_rotations(&self) -> bool {
+        self.average_transition_rotations
+    }
+
+    /// Set whether the rotations of the main animation and its transitions are averaged in a
+    /// single blend
+    ///
+    /// By default, each transition is slerped on top of the result of the previous ones, which
+    /// can wobble when several crossfades overlap. When enabled, the rotations are instead
+    /// combined with [`average_rotations`], weighted by how much each animation contributes to
+    /// the final pose. Translations and scales are blended as usual, and additive animations
+    /// fall back to the sequential blend.
+    pub fn set_average_transition_rotations(
+        &mut self,
+        average_transition_rotations: bool,
+    ) -> &mut Self {
+        self.average_transition_rotations = average_transition_rotations;
+        self
+    }
+
+    /// Did the player modify any [`Transform`] during the last run of [`animation_player`]
+    ///
+    /// This is `false` when the player is disabled or paused, when its clips are not loaded yet,
+    /// when it's waiting for its [update interval](AnimationPlayer::set_update_interval), or when
+    /// none of the paths of its clips could be found from its entity.
+    pub fn applied_last_frame(&self) -> bool {
+        self.applied_last_frame
+    }
+
+    /// Does this player animate `path` when playing `clip`
+    ///
+    /// This is `false` if the player is disabled or if the clip has no curves for `path`.
+    pub fn animates(&self, clip: &AnimationClip, path: &EntityPath) -> bool {
+        self.enabled && clip.curves.contains_key(path)
+    }
+
+    /// The animation playing in the slot `name`, creating an empty slot if it doesn't exist
+    ///
+    /// New slots have a priority of `0`, and are applied after all the existing slots with the
+    /// same or a lower priority.
+    pub fn slot(&mut self, name: impl Into<String>) -> &mut PlayingAnimation {
+        let name = name.into();
+        let index = match self.slots.iter().position(|(slot, _)| *slot == name) {
+            Some(index) => index,
+            None => {
+                let index = self
+                    .slots
+                    .partition_point(|(_, animation)| animation.priority <= 0);
+                self.slots
+                    .insert(index, (name, PlayingAnimation::default()));
+                index
+            }
+        };
+        &mut self.slots[index].1
+    }
+
+    /// Set the priority of the slot `name`, creating an empty slot if it doesn't exist
+    ///
+    /// Slots are applied by increasing priority, after the main animation and the animations
+    /// being faded out, and slots with the same priority in the order they were added. This
+    /// matters for slots that are [additive](BlendMode::Additive) or
+    /// [multiplicative](ScaleBlend::Multiplicative), which are composed with the slots applied
+    /// before them.
+    pub fn set_slot_priority(&mut self, name: impl Into<String>, priority: i32) -> &mut Self {
+        self.slot(name).priority = priority;
+        self.slots.sort_by_key(|(_, animation)| animation.priority);
+        self
+    }
+
+    /// Name of the slot used by [`set_crossfade`](AnimationPlayer::set_crossfade) for the second
+    /// clip
+    pub const CROSSFADE_SLOT: &'static str = "crossfade";
+
+    /// Blend between two clips, playing `a` when `t` is `0.0` and `b` when `t` is `1.0`
+    ///
+    /// `a` is played as the main animation and `b` in the [`CROSSFADE_SLOT`] slot with a weight
+    /// of `t`, so this is meant to be called every frame with a changing `t`. A clip that was
+    /// already playing keeps its elapsed time, while a new one starts from the beginning, with the
+    /// repeat mode of the main animation. The clips are not synchronized: each one advances on
+    /// its own.
+    ///
+    /// [`CROSSFADE_SLOT`]: AnimationPlayer::CROSSFADE_SLOT
+    pub fn set_crossfade(
+        &mut self,
+        a: Handle<AnimationClip>,
+        b: Handle<AnimationClip>,
+        t: f32,
+    ) -> &mut Self {
+        if self.animation.animation_clip != a {
+            self.play(a);
+        }
+        let repeat = self.animation.repeat;
+        let slot = self.slot(Self::CROSSFADE_SLOT);
+        if slot.animation_clip != b {
+            slot.play(b).set_repeat(repeat);
+        }
+        slot.set_weight(t);
+        self
+    }
+
+    /// The animation playing in the slot `name`, if it exists
+    pub fn get_slot(&self, name: &str) -> Option<&PlayingAnimation> {
+        self.slots
+            .iter()
+            .find(|(slot, _)| slot == name)
+            .map(|(_, animation)| animation)
+    }
+
+    /// Remove the slot `name`, returning its animation if it existed
+    pub fn remove_slot(&mut self, name: &str) -> Option<PlayingAnimation> {
+        let index = self.slots.iter().position(|(slot, _)| slot == name)?;
+        Some(self.slots.remove(index).1)
+    }
+
+    /// Iterator over the slots of the player and their animation, in the order they are applied
+    pub fn slots(&self) -> impl Iterator<Item = (&str, &PlayingAnimation)> {
+        self.slots
+            .iter()
+            .map(|(name, animation)| (name.as_str(), animation))
+    }
+
+    /// Path from the entity of this player to the animation root, the entity the paths of the
+    /// clips start from
+    pub fn root_offset(&self) -> &EntityPath {
+        &self.root_offset
+    }
+
+    /// Set the path from the entity of this player to the animation root
+    ///
+    /// The first part is the name of a descendant of the player, and the last part the name of
+    /// the animation root. Entities without a [`Name`] are gone through when looking for a part,
+    /// so that clips can be played on scenes whose root is wrapped in unnamed entities. An empty
+    /// path, the default, makes the player entity the animation root.
+    pub fn set_root_offset(&mut self, root_offset: EntityPath) -> &mut Self {
+        self.root_offset = root_offset;
+        self.root_warned = false;
+        self.path_cache.clear();
+        self
+    }
+
+    /// Add a marker at `time` in the clip of the main animation
+    ///
+    /// An [`AnimationMarkerReached`] event with `id` is sent each time the main animation plays
+    /// forward past `time`, on every playthrough. If a frame goes past the marker several times,
+    /// for example across the end of a loop, an event is sent for each of them.
+    pub fn add_marker(&mut self, time: f32, id: u32) -> &mut Self {
+        // Keep the markers sorted by time, after the markers added before at the same time
+        let index = self
+            .markers
+            .partition_point(|&(marker_time, _)| marker_time <= time);
+        self.markers.insert(index, (time, id));
+        self
+    }
+
+    /// Add a marker at `fraction` of the duration of the clip of the main animation, `0.5`
+    /// being halfway through it
+    ///
+    /// Same as [`add_marker`](AnimationPlayer::add_marker), with the time of the marker computed
+    /// from the duration of the clip each frame, so the marker stays at the same relative moment
+    /// when the clip is retimed or replaced with a clip of another duration.
+    pub fn add_marker_normalized(&mut self, fraction: f32, id: u32) -> &mut Self {
+        let index = self
+            .normalized_markers
+            .partition_point(|&(marker_fraction, _)| marker_fraction <= fraction);
+        self.normalized_markers.insert(index, (fraction, id));
+        self
+    }
+
+    /// Iterator over the markers of the player, as `(time, id)`, sorted by time
+    ///
+    /// Markers at the same time are in the order they were added.
+    pub fn markers(&self) -> impl Iterator<Item = (f32, u32)> + '_ {
+        self.markers.iter().copied()
+    }
+
+    /// Iterator over the [normalized](AnimationPlayer::add_marker_normalized) markers of the
+    /// player, as `(fraction, id)`, sorted by fraction
+    pub fn normalized_markers(&self) -> impl Iterator<Item = (f32, u32)> + '_ {
+        self.normalized_markers.iter().copied()
+    }
+
+    /// Remove the markers added with `id`, normalized or not
+    pub fn remove_marker(&mut self, id: u32) -> &mut Self {
+        self.markers.retain(|&(_, marker_id)| marker_id != id);
+        self.normalized_markers
+            .retain(|&(_, marker_id)| marker_id != id);
+        self
+    }
+
+    /// Freeze the entity at `path`, so that no animation of this player moves it
+    ///
+    /// The entity keeps its current [`Transform`] and
+    /// [animated components](AnimationPlugin::register_animated), which can still be set by other
+    /// systems, for example to hold a weapon steady. Unlike an [`AnimationMask`], this applies to
+    /// all the animations of the player, including transitions and slots.
+    pub fn freeze(&mut self, path: EntityPath) -> &mut Self {
+        if !self.frozen_bones.contains(&path) {
+            self.frozen_bones.push(path);
+        }
+        self
+    }
+
+    /// Let the animations of this player move the entity at `path` again
+    pub fn unfreeze(&mut self, path: &EntityPath) -> &mut Self {
+        self.frozen_bones.retain(|frozen| frozen != path);
+        self
+    }
+
+    /// Is the entity at `path` [frozen](AnimationPlayer::freeze)
+    pub fn is_frozen(&self, path: &EntityPath) -> bool {
+        self.frozen_bones.contains(path)
+    }
+
+    /// Paths of the [frozen](AnimationPlayer::freeze) entities
+    pub fn frozen_bones(&self) -> &[EntityPath] {
+        &self.frozen_bones
+    }
+
+    /// Adjust the values sampled from the curves of the entity at `path` by all the animations of
+    /// this player, replacing its previous [`ValueTransform`]
+    ///
+    /// This tunes a clip at runtime without modifying the asset, for example to exaggerate a
+    /// bounce. It applies to the main animation, transitions and slots, but not to the
+    /// [`BlendTree`].
+    pub fn set_value_transform(
+        &mut self,
+        path: EntityPath,
+        value_transform: ValueTransform,
+    ) -> &mut Self {
+        match self
+            .value_transforms
+            .iter_mut()
+            .find(|(transformed, _)| *transformed == path)
+        {
+            Some((_, current)) => *current = value_transform,
+            None => self.value_transforms.push((path, value_transform)),
+        }
+        self
+    }
+
+    /// Remove the [`ValueTransform`] of the entity at `path`
+    pub fn remove_value_transform(&mut self, path: &EntityPath) -> &mut Self {
+        self.value_transforms
+            .retain(|(transformed, _)| transformed != path);
+        self
+    }
+
+    /// [`ValueTransform`] of the entity at `path`, if it has one
+    pub fn value_transform(&self, path: &EntityPath) -> Option<&ValueTransform> {
+        find_value_transform(&self.value_transforms, path)
+    }
+
+    /// Entity `path` was last resolved to, if it was
+    ///
+    /// Paths are resolved and cached the first time they are animated, or when
+    /// [warming the cache](AnimationPlayer::warm_cache). This is meant for debugging which
+    /// entities a clip targets, as the cached entity is only checked again when it's animated.
+    pub fn resolved_target(&self, path: &EntityPath) -> Option<Entity> {
+        self.path_cache.get(path).copied()
+    }
+
+    /// Resolve all the paths animated by `animation_clip` from `root`, so that they don't have to
+    /// be found during the first frame the clip is played
+    ///
+    /// `root` should be the entity of this player.
+    pub fn warm_cache(
+        &mut self,
+        root: Entity,
+        children: &Query<&Children>,
+        names: &Query<&Name>,
+        animation_clip: &AnimationClip,
+    ) {
+        let root = match descendant_from_names(root, &self.root_offset, children, names) {
+            Some(root) => root,
+            None => return,
+        };
+        for path in animation_clip.paths() {
+            cached_entity_from_path(&mut self.path_cache, root, path, children, names);
+        }
+    }
+
+    /// Pose of each path this player applies at the current elapsed times and weights, computed
+    /// without the ECS
+    ///
+    /// The [`BlendTree`], main animation, transitions and slots are composed as by
+    /// [`animation_player`], starting from the identity as if the animated entities had no
+    /// [`Transform`] before, with the same masks, weights, frozen bones and
+    /// [value transforms](AnimationPlayer::set_value_transform). The animations aren't advanced,
+    /// so this is the pose of the last update. Clips in [`CurveSpace::DeltaFromRest`] are
+    /// composed with the identity, as [`RestPose`]s are not looked for, and rotations are not
+    /// [averaged](AnimationPlayer::set_average_transition_rotations).
+    pub fn evaluate(&self, animations: &Assets<AnimationClip>) -> HashMap<EntityPath, Transform> {
+        let mut poses = HashMap::<EntityPath, Transform>::default();
+        if let Some(blend_tree) = &self.blend_tree {
+            for path in blend_tree.paths(animations) {
+                if self.frozen_bones.contains(path) {
+                    continue;
+                }
+                if let Some(target) = blend_tree.evaluate(self.blend_tree_elapsed, animations, path)
+                {
+                    let pose = poses.entry(path.clone()).or_insert(Transform::IDENTITY);
+                    blend_transform(pose, target, self.animation.weight * self.master_weight);
+                }
+            }
+        }
+        for (weight, animation) in self.animations() {
+            let weight = weight * self.master_weight;
+            let animation_clip = match animations.get(&animation.animation_clip) {
+                Some(animation_clip) if weight >= self.min_weight => animation_clip,
+                _ => continue,
+            };
+            let reference = match &animation.blend_mode {
+                BlendMode::AdditiveFrom(handle) => match animations.get(handle) {
+                    Some(reference) => Some(reference),
+                    None => continue,
+                },
+                _ => None,
+            };
+            let rest = match animation_clip.space {
+                CurveSpace::Local => None,
+                CurveSpace::DeltaFromRest => Some(Transform::IDENTITY),
+            };
+            for path in &animation_clip.paths {
+                let weight = weight * animation.mask_weight(path);
+                if weight == 0.0 || self.frozen_bones.contains(path) {
+                    continue;
+                }
+                blend_curves(
+                    poses.entry(path.clone()).or_insert(Transform::IDENTITY),
+                    weight,
+                    animation,
+                    animation_clip,
+                    reference,
+                    path,
+                    rest,
+                    self.value_transform(path),
+                );
+            }
+        }
+        poses
+    }
+
+    /// Iterator over the main animation, the animations being transitioned out and the
+    /// animations of the slots, in the order they are applied, along with the weight each
+    /// animation is applied with
+    fn animations(&self) -> impl DoubleEndedIterator<Item = (f32, &PlayingAnimation)> {
+        std::iter::once((self.animation.weight, &self.animation))
+            .chain(
+                self.transitions
+                    .iter()
+                    .map(|transition| (transition.weight(), &transition.animation)),
+            )
+            .chain(
+                self.slots
+                    .iter()
+                    .map(|(_, animation)| (animation.weight, animation)),
+            )
+    }
+
+    /// Is the player fading out a previous animation, or playing an animation neither paused nor
+    /// finished
+    fn is_animating(&self) -> bool {
+        self.enabled
+            && (self.is_transitioning()
+                || (self.blend_tree.is_some() && !self.animation.paused)
+                || self.animations().any(|(_, animation)| {
+                    animation.animation_clip != Handle::default()
+                        && !animation.paused
+                        && !animation.is_finished()
+                }))
+    }
+
+    /// Same as [`animations`](AnimationPlayer::animations), with mutable animations
+    fn animations_mut(&mut self) -> impl Iterator<Item = (f32, &mut PlayingAnimation)> {
+        std::iter::once((self.animation.weight, &mut self.animation))
+            .chain(
+                self.transitions
+                    .iter_mut()
+                    .map(|transition| (transition.weight(), &mut transition.animation)),
+            )
+            .chain(
+                self.slots
+                    .iter_mut()
+                    .map(|(_, animation)| (animation.weight, animation)),
+            )
+    }
+}
+
+/// Animation clips available to an entity, by name
+///
+/// Usually added next to an [`AnimationPlayer`] to play animations with
+/// [`AnimationPlayer::play_named`] instead of keeping track of the handles.
+#[derive(Component, Reflect, Default, Clone, Debug)]
+#[reflect(Component)]
+pub struct AnimationClips {
+    clips: HashMap<String, Handle<AnimationClip>>,
+}
+
+impl AnimationClips {
+    /// Register `handle` as `name`, returning the handle previously registered with this name
+    pub fn insert(
+        &mut self,
+        name: impl Into<String>,
+        handle: Handle<AnimationClip>,
+    ) -> Option<Handle<AnimationClip>> {
+        self.clips.insert(name.into(), handle)
+    }
+
+    /// The clip registered as `name`
+    pub fn get(&self, name: &str) -> Option<&Handle<AnimationClip>> {
+        self.clips.get(name)
+    }
+
+    /// Unregister the clip `name`, returning its handle if it existed
+    pub fn remove(&mut self, name: &str) -> Option<Handle<AnimationClip>> {
+        self.clips.remove(name)
+    }
+
+    /// Iterator over the names of the clips and their handle
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Handle<AnimationClip>)> {
+        self.clips
+            .iter()
+            .map(|(name, handle)| (name.as_str(), handle))
+    }
+}
+
+/// Speed multiplier applied to every [`AnimationPlayer`]
+///
+/// It composes with the speed of each player, so a player with a speed of `2.0` will play at
+/// normal speed with a global speed of `0.5`. When this resource is absent, animations play as if
+/// it was set to `1.0`.
+#[derive(Resource, Reflect, Debug, Clone, Copy)]
+#[reflect(Resource)]
+pub struct GlobalAnimationSpeed(pub f32);
+
+impl Default for GlobalAnimationSpeed {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+/// Freezes every [`AnimationPlayer`] when set to `true`, for example while the game is paused
+///
+/// The animations and transitions of the players stop advancing, but unlike
+/// [`AnimationPlayer::pause`] the players are not marked as paused, so they resume in the state
+/// they were in once this is set back to `false`. When this resource is absent, animations play
+/// as if it was set to `false`.
+#[derive(Resource, Reflect, Debug, Clone, Copy, Default)]
+#[reflect(Resource)]
+pub struct GlobalAnimationPause(pub bool);
+
+/// Factor of the frame delta the animations advance with, from the [`GlobalAnimationSpeed`] and
+/// [`GlobalAnimationPause`]
+fn global_speed_factor(
+    speed: Option<Res<GlobalAnimationSpeed>>,
+    pause: Option<Res<GlobalAnimationPause>>,
+) -> f32 {
+    match (speed, pause) {
+        (_, Some(pause)) if pause.0 => 0.0,
+        (Some(speed), _) => speed.0,
+        (None, _) => 1.0,
+    }
+}
+
+/// Animations played by enabled [`AnimationPlayer`]s during the last run of [`animation_player`]
+///
+/// This is rebuilt every frame, for example to list the animations playing in a debug overlay.
+/// Animations whose clip is not loaded are not included.
+#[derive(Resource, Reflect, Default, Debug)]
+#[reflect(Resource)]
+pub struct ActiveAnimations {
+    animations: Vec<ActiveAnimation>,
+}
+
+/// Is any enabled [`AnimationPlayer`] still animating, as of the last run of
+/// [`any_animation_active`]
+///
+/// A player is animating if it's fading out a previous animation, or if its main animation or one
+/// of its slots plays a clip and is neither paused nor finished. This can be used to wait for the
+/// animations to end before leaving a loading screen or a cutscene.
+#[derive(Resource, Reflect, Default, Clone, Copy, Debug, PartialEq, Eq)]
+#[reflect(Resource)]
+pub struct AnyAnimationActive(pub bool);
+
+/// An animation listed in [`ActiveAnimations`]
+#[derive(Reflect, FromReflect, Clone, Debug)]
+pub struct ActiveAnimation {
+    /// Entity of the [`AnimationPlayer`] playing the animation
+    pub entity: Entity,
+    /// Weak handle to the clip being played
+    pub animation_clip: Handle<AnimationClip>,
+    /// Time elapsed playing the animation
+    pub elapsed: f32,
+    /// Is the animation finished
+    pub finished: bool,
+    /// Weight the animation contributes to the final pose, between `0.0` and `1.0`
+    ///
+    /// Animations are blended in order, so an animation blended with [`BlendMode::Replace`]
+    /// reduces the contribution of the ones applied before it: with a main animation and a
+    /// slot both at a weight of `0.5`, the main animation contributes `0.25` and the slot `0.5`,
+    /// the remaining `0.25` being the pose the entities had before the animations were applied.
+    /// Additive animations don't reduce the contribution of the previous ones.
+    pub weight: f32,
+}
+
+impl ActiveAnimations {
+    /// Iterator over the active animations, grouped by player
+    pub fn iter(&self) -> impl Iterator<Item = &ActiveAnimation> {
+        self.animations.iter()
+    }
+
+    fn record(
+        &mut self,
+        entity: Entity,
+        player: &AnimationPlayer,
+        animations: &Assets<AnimationClip>,
+    ) {
+        let start = self.animations.len();
+        // Visit the animations from the last one applied to know how much of the pose is left to
+        // the previous ones
+        let mut remaining = 1.0;
+        for (weight, animation) in player.animations().rev() {
+            if animations.contains(&animation.animation_clip) {
+                let weight = weight.clamp(0.0, 1.0);
+                self.animations.push(ActiveAnimation {
+                    entity,
+                    animation_clip: animation.animation_clip.clone_weak(),
+                    elapsed: animation.elapsed,
+                    finished: animation.is_finished(),
+                    weight: weight * remaining,
+                });
+                if animation.blend_mode == BlendMode::Replace {
+                    remaining *= 1.0 - weight;
+                }
+            }
+        }
+        self.animations[start..].reverse();
+    }
+}
+
+/// Event sent by [`animation_player`] each time the main animation of a player goes past one of
+/// its [markers](AnimationPlayer::add_marker)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnimationMarkerReached {
+    /// Entity of the [`AnimationPlayer`].
+    pub entity: Entity,
+    /// Id the marker was added with.
+    pub id: u32,
+}
+
+/// Event sent by [`animation_player`] each time the main animation of a player enters one of the
+/// [intervals](AnimationClip::add_interval_event) of its clip
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnimationIntervalEntered {
+    /// Entity of the [`AnimationPlayer`].
+    pub entity: Entity,
+    /// Id the interval was added with.
+    pub id: u32,
+}
+
+/// Event sent by [`animation_player`] each time the main animation of a player leaves one of the
+/// [intervals](AnimationClip::add_interval_event) of its clip
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnimationIntervalExited {
+    /// Entity of the [`AnimationPlayer`].
+    pub entity: Entity,
+    /// Id the interval was added with.
+    pub id: u32,
+}
+
+/// Event to [reset](AnimationPlayer::reset) all the [`AnimationPlayer`]s, handled by
+/// [`reset_animation_players`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResetAnimationPlayers;
+
+/// Problems reported by [`animation_player`] when the [`AnimationStrictMode`] resource exists
+///
+/// Without strict mode, these problems are skipped, with a warning for some of them.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AnimationStrictError {
+    /// No entity was found for a path of a clip.
+    #[error("no entity found for path {path:?} of the animation of {player:?}")]
+    PathNotFound {
+        /// Entity of the [`AnimationPlayer`].
+        player: Entity,
+        /// Path without an entity.
+        path: EntityPath,
+    },
+    /// A clip played by the player, or the reference clip of an additive animation, is not
+    /// loaded.
+    #[error("clip {clip:?} played by {player:?} is not loaded")]
+    ClipNotLoaded {
+        /// Entity of the [`AnimationPlayer`].
+        player: Entity,
+        /// Handle of the clip.
+        clip: Handle<AnimationClip>,
+    },
+    /// The [root offset](AnimationPlayer::set_root_offset) of the player doesn't lead to any
+    /// entity.
+    #[error("no animation root found for offset {root_offset:?} of {player:?}")]
+    RootNotFound {
+        /// Entity of the [`AnimationPlayer`].
+        player: Entity,
+        /// Root offset of the player.
+        root_offset: EntityPath,
+    },
+}
+
+/// Any error of the animation crate
+///
+/// This lets code combining the operations of the crate propagate their errors with `?`.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum AnimationError {
+    /// A problem of an [`AnimationClip`].
+    #[error(transparent)]
+    Clip(#[from] AnimationClipError),
+    /// A problem found while playing an animation.
+    #[error(transparent)]
+    Strict(#[from] AnimationStrictError),
+}
+
+/// Reports the problems the [`animation_player`] system would otherwise skip
+///
+/// Players are forgiving by default, to not stop a game because of a missing bone. Inserting this
+/// resource, for example in development builds or in CI, surfaces these problems: they are
+/// collected in [`errors`](AnimationStrictMode::errors), or panic if
+/// [`panic`](AnimationStrictMode::panic) is set. Clips still loading are reported too, so
+/// panicking works best when clips are loaded before they are played.
+#[derive(Resource, Default, Debug)]
+pub struct AnimationStrictMode {
+    /// Panic on the first problem instead of collecting it
+    pub panic: bool,
+    errors: Vec<AnimationStrictError>,
+}
+
+impl AnimationStrictMode {
+    /// Problems found during the last run of [`animation_player`]
+    pub fn errors(&self) -> &[AnimationStrictError] {
+        &self.errors
+    }
+
+    fn report(&mut self, error: AnimationStrictError) {
+        if self.panic {
+            panic!("{}", error);
+        }
+        self.errors.push(error);
+    }
+}
+
+/// Detects the [`AnimationPlayer`]s whose main animation should be playing but doesn't advance
+///
+/// A player with a clip that isn't paused, finished or stopped by a speed of `0.0`, and driven by
+/// [time](AnimationDriver::Time), is stuck when its elapsed time stays the same for
+/// [`frames`](StuckAnimationPlayers::frames) frames in a row, for example because its clip never
+/// loaded or [`animation_player`] isn't scheduled. A warning is logged when a player gets stuck.
+/// Frames in which the animations don't advance, because of the [`GlobalAnimationPause`] or a
+/// delta of `0.0`, aren't counted. Like [`AnimationStrictMode`], this is only checked when the
+/// resource is inserted.
+#[derive(Resource, Debug)]
+pub struct StuckAnimationPlayers {
+    /// Frames without progress after which a player is stuck
+    pub frames: u32,
+    frames_unchanged: HashMap<Entity, (f32, u32)>,
+    stuck: Vec<Entity>,
+}
+
+impl Default for StuckAnimationPlayers {
+    fn default() -> Self {
+        Self::new(60)
+    }
+}
+
+impl StuckAnimationPlayers {
+    /// Detect the players stuck for `frames` frames
+    pub fn new(frames: u32) -> Self {
+        Self {
+            frames,
+            frames_unchanged: HashMap::default(),
+            stuck: Vec::new(),
+        }
+    }
+
+    /// Players that are currently stuck
+    pub fn stuck(&self) -> &[Entity] {
+        &self.stuck
+    }
+
+    /// Is `player` currently stuck
+    pub fn is_stuck(&self, player: Entity) -> bool {
+        self.stuck.contains(&player)
+    }
+}
+
+/// System that tracks the elapsed time of the players for [`StuckAnimationPlayers`]
+fn detect_stuck_animation_players<T: DeltaSeconds>(
+    time: Res<T>,
+    global_speed: Option<Res<GlobalAnimationSpeed>>,
+    global_pause: Option<Res<GlobalAnimationPause>>,
+    detector: Option<ResMut<StuckAnimationPlayers>>,
+    animation_players: Query<(Entity, &AnimationPlayer)>,
+) {
+    let mut detector = match detector {
+        Some(detector) => detector,
+        None => return,
+    };
+    if time.delta_seconds() * global_speed_factor(global_speed, global_pause) == 0.0 {
+        return;
+    }
+    let detector = detector.as_mut();
+    let mut frames_unchanged = HashMap::default();
+    detector.stuck.clear();
+    for (entity, player) in &animation_players {
+        let animation = &player.animation;
+        if !player.enabled
+            || player.driver != AnimationDriver::Time
+            || animation.animation_clip == Handle::default()
+            || animation.paused
+            || animation.speed == 0.0
+            || animation.is_finished()
+        {
+            continue;
+        }
+        let frames = match detector.frames_unchanged.get(&entity) {
+            Some(&(elapsed, frames)) if elapsed == animation.elapsed => frames + 1,
+            _ => 0,
+        };
+        if frames >= detector.frames {
+            if frames == detector.frames {
+                warn!(
+                    "Animation player {:?} hasn't advanced for {} frames",
+                    entity, frames
+                );
+            }
+            detector.stuck.push(entity);
+        }
+        frames_unchanged.insert(entity, (animation.elapsed, frames));
+    }
+    detector.frames_unchanged = frames_unchanged;
+}
+
+/// Find the entity targeted by `path`, starting from the `root` of the animation
+///
+/// If several siblings share the [`Name`] of a part of the path, the first one in [`Children`]
+/// order is chosen and a warning is emitted.
+fn entity_from_path(
+    root: Entity,
+    path: &EntityPath,
+    children: &Query<&Children>,
+    names: &Query<&Name>,
+) -> Option<Entity> {
+    // PERF: finding the target entity can be optimised
+    let mut current_entity = root;
+    // Ignore the first name, it is the root node which we already have
+    for part in path.parts.iter().skip(1) {
+        let mut found = None;
+        if let Ok(children) = children.get(current_entity) {
+            for child in children.deref() {
+                if let Ok(name) = names.get(*child) {
+                    if name == part {
+                        if found.is_some() {
+                            warn!(
+                                "Multiple entities found for path {:?} on part {:?}, using the first one",
+                                path, part
+                            );
+                            break;
+                        }
+                        // Found a children with the right name, continue to the next part
+                        found = Some(*child);
+                    }
+                }
+            }
+        }
+        match found {
+            Some(child) => current_entity = child,
+            None => {
+                warn!("Entity not found for path {:?} on part {:?}", path, part);
+                return None;
+            }
+        }
+    }
+    Some(current_entity)
+}
+
+/// Find the descendant of `root` reached by following the names of `path`
+///
+/// Unlike [`entity_from_path`], all the parts of `path` are looked for, and the descendants
+/// without a [`Name`] are searched through.
+fn descendant_from_names(
+    root: Entity,
+    path: &EntityPath,
+    children: &Query<&Children>,
+    names: &Query<&Name>,
+) -> Option<Entity> {
+    let mut current_entity = root;
+    for part in &path.parts {
+        current_entity = child_from_name(current_entity, part, children, names)?;
+    }
+    Some(current_entity)
+}
+
+/// Find the first child of `parent` named `part`, going through unnamed children
+fn child_from_name(
+    parent: Entity,
+    part: &Name,
+    children: &Query<&Children>,
+    names: &Query<&Name>,
+) -> Option<Entity> {
+    for child in children.get(parent).ok()?.deref() {
+        match names.get(*child) {
+            Ok(name) if name == part => return Some(*child),
+            Ok(_) => {}
+            Err(_) => {
+                if let Some(found) = child_from_name(*child, part, children, names) {
+                    return Some(found);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Same as [`entity_from_path`], reusing the entity found previously if its [`Name`] still
+/// matches the last part of the path
+fn cached_entity_from_path(
+    path_cache: &mut HashMap<EntityPath, Entity>,
+    root: Entity,
+    path: &EntityPath,
+    children: &Query<&Children>,
+    names: &Query<&Name>,
+) -> Option<Entity> {
+    if let Some(&entity) = path_cache.get(path) {
+        if path.parts.len() == 1 || names.get(entity).ok() == path.parts.last() {
+            return Some(entity);
+        }
+    }
+    let entity = entity_from_path(root, path, children, names)?;
+    path_cache.insert(path.clone(), entity);
+    Some(entity)
+}
+
+/// System that will play all animations, using any entity with a [`AnimationPlayer`]
+/// and a [`Handle<AnimationClip>`] as an animation root
+///
+/// It is added by [`AnimationPlugin`], labeled [`AnimationSystem::Sample`] and ordered before
+/// [`TransformSystem::TransformPropagate`], so that the pose is propagated in the same frame.
+#[allow(clippy::too_many_arguments)]
+pub fn animation_player<T: DeltaSeconds>(
+    time: Res<T>,
+    global: (
+        Option<Res<GlobalAnimationSpeed>>,
+        Option<Res<GlobalAnimationPause>>,
+    ),
+    animations: Res<Assets<AnimationClip>>,
+    mut active_animations: ResMut<ActiveAnimations>,
+    mut strict_mode: Option<ResMut<AnimationStrictMode>>,
+    mut animation_players: Query<(Entity, &mut AnimationPlayer)>,
+    targets: Query<(Entity, &AnimationTarget, ChangeTrackers<AnimationTarget>)>,
+    names: Query<&Name>,
+    rest_poses: Query<&RestPose>,
+    mut transforms: Query<&mut Transform>,
+    children: Query<&Children>,
+    parents: Query<&Parent>,
+    global_transforms: Query<&GlobalTransform>,
+    mut marker_events: EventWriter<AnimationMarkerReached>,
+    mut interval_events: (
+        EventWriter<AnimationIntervalEntered>,
+        EventWriter<AnimationIntervalExited>,
+    ),
+    clocks: Query<&AnimationClock>,
+) {
+    let global_speed = global_speed_factor(global.0, global.1);
+    active_animations.animations.clear();
+    if let Some(strict_mode) = &mut strict_mode {
+        strict_mode.errors.clear();
+    }
+    for (entity, target, tracker) in &targets {
+        if let Ok((_, mut player)) = animation_players.get_mut(target.root) {
+            // Targets are added back after the cache of the player is cleared
+            if tracker.is_changed() || !player.path_cache.contains_key(&target.path) {
+                player
+                    .bypass_change_detection()
+                    .path_cache
+                    .insert(target.path.clone(), entity);
+            }
+        }
+    }
+    for (entity, mut player) in &mut animation_players {
+        if !player.enabled {
+            player.bypass_change_detection().applied_last_frame = false;
+            continue;
+        }
+        // Continue if paused unless the `AnimationPlayer` was changed
+        // This allow the animation to still be updated if the player.elapsed field was manually updated in pause
+        let main_clip = player.animation.animation_clip.id();
+        let first_frame_pending = player.hold_first_frame && player.held_clip != Some(main_clip);
+        if player.animations().all(|(_, animation)| animation.paused)
+            && !player.is_changed()
+            && !first_frame_pending
+        {
+            player.bypass_change_detection().applied_last_frame = false;
+            active_animations.record(entity, &player, &animations);
+            continue;
+        }
+        let player = player.into_inner();
+        let main_duration = animations
+            .get(&player.animation.animation_clip)
+            .map(|animation_clip| animation_clip.duration)
+            .filter(|&duration| duration > 0.0);
+        let seek_from = player.animation.seek_from.take();
+        let main_progress = main_duration.map(|duration| match seek_from {
+            // Progress before the seek, to send the events of the skipped range
+            Some((elapsed, completions)) if player.animation.repeat == RepeatAnimation::Forever => {
+                completions as f32 * duration + elapsed
+            }
+            Some((elapsed, _)) => elapsed,
+            None => player.animation.progress(duration),
+        });
+        let driven_elapsed = match player.driver {
+            AnimationDriver::Time => None,
+            AnimationDriver::External(elapsed) => Some(elapsed),
+            AnimationDriver::Clock(clock) => Some(
+                clocks
+                    .get(clock)
+                    .map_or(player.animation.elapsed, |clock| clock.elapsed),
+            ),
+        };
+        let delta = match driven_elapsed {
+            None => time.delta_seconds() * global_speed,
+            Some(elapsed) => {
+                if let Some(animation_clip) = animations.get(&player.animation.animation_clip) {
+                    player.animation.drive(elapsed, animation_clip);
+                }
+                0.0
+            }
+        };
+        // Transitions fade out with time even when the player is externally driven
+        let fade_delta = time.delta_seconds() * global_speed;
+        let update_due = player.is_update_due(fade_delta);
+        let fade_delta = if player.speed_scaled_transitions {
+            fade_delta * player.animation.speed.abs()
+        } else {
+            fade_delta
+        };
+        let min_weight = player.min_weight;
+        let master_weight = player.master_weight;
+        player.transitions.retain_mut(|transition| {
+            if !transition.manual {
+                transition.current_weight -= transition.weight_decline_per_sec * fade_delta;
+            }
+            transition.current_weight > 0.0
+                && (transition.manual || transition.weight() >= min_weight)
+        });
+        let root = descendant_from_names(entity, &player.root_offset, &children, &names);
+        if root.is_none() {
+            // Warn once until the root offset changes, the failure is reported every frame in
+            // strict mode
+            if !player.root_warned {
+                warn!(
+                    "Animation root not found for offset {:?}",
+                    player.root_offset
+                );
+                player.root_warned = true;
+            }
+            if let Some(strict_mode) = &mut strict_mode {
+                strict_mode.report(AnimationStrictError::RootNotFound {
+                    player: entity,
+                    root_offset: player.root_offset.clone(),
+                });
+            }
+        }
+        let mut path_cache = std::mem::take(&mut player.path_cache);
+        let frozen_bones = std::mem::take(&mut player.frozen_bones);
+        let value_transforms = std::mem::take(&mut player.value_transforms);
+        let inspected_bone = player.inspected_bone.clone();
+        let mut bone_contributions = std::mem::take(&mut player.bone_contributions);
+        if update_due {
+            bone_contributions.clear();
+        }
+        let output = std::mem::take(&mut player.output);
+        let bones = match &output {
+            AnimationOutput::Transforms => None,
+            AnimationOutput::Matrices(bones) => Some(bones.as_slice()),
+        };
+        let mut bone_poses = bones.map(|bones| vec![Transform::IDENTITY; bones.len()]);
+        let mut applied = false;
+        if let Some(blend_tree) = &player.blend_tree {
+            player.blend_tree_elapsed = match driven_elapsed {
+                Some(elapsed) => elapsed,
+                None if player.animation.paused => player.blend_tree_elapsed,
+                None => player.blend_tree_elapsed + delta * player.animation.speed,
+            };
+            if let (Some(bones), Some(poses), true) = (bones, &mut bone_poses, update_due) {
+                for (path, pose) in bones.iter().zip(poses) {
+                    if frozen_bones.contains(path) {
+                        continue;
+                    }
+                    let time = player.blend_tree_elapsed;
+                    if let Some(target) = blend_tree.evaluate(time, &animations, path) {
+                        blend_transform(
+                            pose,
+                            target,
+                            player.animation.weight * player.master_weight,
+                        );
+                        applied = true;
+                    }
+                }
+            } else if let (Some(root), true) = (root, update_due) {
+                applied |= apply_blend_tree(
+                    blend_tree,
+                    player.blend_tree_elapsed,
+                    player.animation.weight * player.master_weight,
+                    &animations,
+                    root,
+                    &mut path_cache,
+                    &frozen_bones,
+                    &children,
+                    &names,
+                    &mut transforms,
+                );
+            }
+        }
+        let synced_transitions = player
+            .synced_transitions
+            .then_some(player.transitions.len());
+        let mut averaged_transitions = (player.average_transition_rotations && bones.is_none())
+            .then_some(player.transitions.len())
+            .filter(|&transitions| transitions > 0);
+        let mut rotation_samples = RotationSamples::default();
+        let mut main_phase = None;
+        for (index, (weight, animation)) in player.animations_mut().enumerate() {
+            let weight = weight * master_weight;
+            // The slots come after the transitions, and are blended on top of the averaged result
+            if matches!(averaged_transitions, Some(transitions) if index > transitions) {
+                apply_averaged_rotations(&mut rotation_samples, &mut transforms);
+            }
+            let animation_clip = match animations.get(&animation.animation_clip) {
+                Some(animation_clip) => animation_clip,
+                None => {
+                    // Players and slots that never played anything have the default handle
+                    if animation.animation_clip != Handle::default() {
+                        if let Some(strict_mode) = &mut strict_mode {
+                            strict_mode.report(AnimationStrictError::ClipNotLoaded {
+                                player: entity,
+                                clip: animation.animation_clip.clone_weak(),
+                            });
+                        }
+                    }
+                    continue;
+                }
+            };
+            match (synced_transitions, main_phase) {
+                // The transitions come right after the main animation
+                (Some(transitions), Some(phase)) if index <= transitions => {
+                    animation.drive(phase * animation_clip.duration, animation_clip);
+                }
+                _ => animation.update(delta, animation_clip),
+            }
+            if animation.finished_behavior == FinishedBehavior::Stop && animation.is_finished() {
+                animation.animation_clip = Handle::default();
+                continue;
+            }
+            if index == 0 && animation_clip.duration > 0.0 {
+                main_phase =
+                    Some(animation.elapsed_wrapped(animation_clip) / animation_clip.duration);
+            }
+            if !update_due || weight < min_weight || (root.is_none() && bones.is_none()) {
+                continue;
+            }
+            let reference = match &animation.blend_mode {
+                BlendMode::AdditiveFrom(handle) => match animations.get(handle) {
+                    Some(reference) => Some(reference),
+                    None => {
+                        if let Some(strict_mode) = &mut strict_mode {
+                            strict_mode.report(AnimationStrictError::ClipNotLoaded {
+                                player: entity,
+                                clip: handle.clone_weak(),
+                            });
+                        }
+                        continue;
+                    }
+                },
+                _ => None,
+            };
+            if let Some(path) = &inspected_bone {
+                if animation_clip.curves.contains_key(path) {
+                    let mut value = Transform::IDENTITY;
+                    blend_curves(
+                        &mut value,
+                        1.0,
+                        animation,
+                        animation_clip,
+                        reference,
+                        path,
+                        None,
+                        find_value_transform(&value_transforms, path),
+                    );
+                    bone_contributions.push(BoneContribution {
+                        clip: animation.animation_clip.clone_weak(),
+                        weight: weight * animation.mask_weight(path),
+                        value,
+                    });
+                }
+            }
+            if let (Some(bones), Some(poses)) = (bones, &mut bone_poses) {
+                applied |= blend_bone_poses(
+                    poses,
+                    bones,
+                    weight,
+                    animation,
+                    animation_clip,
+                    reference,
+                    &frozen_bones,
+                    &value_transforms,
+                );
+                continue;
+            }
+            let root = match root {
+                Some(root) => root,
+                None => continue,
+            };
+            if matches!(averaged_transitions, Some(transitions) if index <= transitions) {
+                if animation.blend_mode == BlendMode::Replace {
+                    record_rotations(
+                        &mut rotation_samples,
+                        weight,
+                        animation,
+                        animation_clip,
+                        root,
+                        &mut path_cache,
+                        &frozen_bones,
+                        &value_transforms,
+                        &children,
+                        &names,
+                        &rest_poses,
+                        &transforms,
+                    );
+                } else {
+                    averaged_transitions = None;
+                    rotation_samples.clear();
+                }
+            }
+            let (animation_applied, missing_paths) = apply_animation(
+                weight,
+                animation,
+                animation_clip,
+                reference,
+                root,
+                &mut path_cache,
+                &frozen_bones,
+                &value_transforms,
+                &children,
+                &names,
+                Some(&rest_poses),
+                &mut transforms,
+            );
+            applied |= animation_applied;
+            if let Some(strict_mode) = &mut strict_mode {
+                for path in missing_paths {
+                    strict_mode.report(AnimationStrictError::PathNotFound {
+                        player: entity,
+                        path: path.clone(),
+                    });
+                }
+            }
+        }
+        apply_averaged_rotations(&mut rotation_samples, &mut transforms);
+        player.path_cache = path_cache;
+        player.frozen_bones = frozen_bones;
+        player.value_transforms = value_transforms;
+        player.bone_contributions = bone_contributions;
+        if let (Some(poses), true) = (bone_poses, update_due) {
+            player.bone_matrices.clear();
+            player
+                .bone_matrices
+                .extend(poses.iter().map(Transform::compute_matrix));
+        }
+        let anchor = player.anchor.filter(|_| bones.is_none());
+        player.output = output;
+        if let (Some(anchor), Some(root), true) = (anchor, root, applied) {
+            let root_path = names.get(root).ok().map(|name| EntityPath {
+                parts: vec![name.clone()],
+            });
+            let root_translated = player.animations().any(|(_, animation)| {
+                let curves = match (animations.get(&animation.animation_clip), &root_path) {
+                    (Some(animation_clip), Some(path)) => animation_clip.curves.get(path),
+                    _ => None,
+                };
+                curves.into_iter().flatten().any(|curve| {
+                    matches!(
+                        curve.keyframes,
+                        Keyframes::Translation(_)
+                            | Keyframes::QuantizedTranslation(_)
+                            | Keyframes::Trs(_)
+                    )
+                })
+            });
+            if let (true, Ok(mut transform)) = (root_translated, transforms.get_mut(root)) {
+                let world = anchor + transform.translation;
+                transform.translation = match parents
+                    .get(root)
+                    .and_then(|parent| global_transforms.get(parent.get()))
+                {
+                    Ok(parent) => parent.affine().inverse().transform_point3(world),
+                    Err(_) => world,
+                };
+            }
+        }
+        if let (Some(duration), Some(from)) = (main_duration, main_progress) {
+            let to = player.animation.progress(duration);
+            let normalized_markers = player
+                .normalized_markers
+                .iter()
+                .map(|&(fraction, id)| (fraction * duration, id));
+            for (time, id) in player.markers.iter().copied().chain(normalized_markers) {
+                for _ in 0..player.animation.marker_crossings(from, to, time, duration) {
+                    marker_events.send(AnimationMarkerReached { entity, id });
+                }
+            }
+            if let Some(animation_clip) = animations.get(&Handle::weak(main_clip)) {
+                for &(start, end, id) in &animation_clip.interval_events {
+                    for _ in 0..player.animation.marker_crossings(from, to, start, duration) {
+                        interval_events
+                            .0
+                            .send(AnimationIntervalEntered { entity, id });
+                    }
+                    for _ in 0..player.animation.marker_crossings(from, to, end, duration) {
+                        interval_events
+                            .1
+                            .send(AnimationIntervalExited { entity, id });
+                    }
+                }
+            }
+        }
+        player.applied_last_frame = applied;
+        if applied {
+            player.held_clip = Some(main_clip);
+        }
+        active_animations.record(entity, player, &animations);
+    }
+}
+
+/// System that [repairs](AnimationClip::repair_lengths) the clips with mismatched lengths when
+/// they are loaded or modified
+pub fn repair_animation_clips(
+    mut events: EventReader<AssetEvent<AnimationClip>>,
+    mut animations: ResMut<Assets<AnimationClip>>,
+) {
+    for event in events.iter() {
+        let handle = match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => handle,
+            AssetEvent::Removed { .. } => continue,
+        };
+        // Only borrow the clip mutably when needed, as it sends a new modification event
+        let needs_repair = matches!(
+            animations.get(handle),
+            Some(animation_clip) if animation_clip.has_mismatched_lengths()
+        );
+        if needs_repair {
+            if let Some(animation_clip) = animations.get_mut(handle) {
+                animation_clip.repair_lengths();
+            }
+        }
+    }
+}
+
+/// System that [resets](AnimationPlayer::reset) all the [`AnimationPlayer`]s when a
+/// [`ResetAnimationPlayers`] event is sent
+pub fn reset_animation_players(
+    mut events: EventReader<ResetAnimationPlayers>,
+    mut animation_players: Query<&mut AnimationPlayer>,
+) {
+    if events.iter().count() > 0 {
+        for mut player in &mut animation_players {
+            player.reset();
+        }
+    }
+}
+
+/// System that updates [`AnyAnimationActive`] from the [`AnimationPlayer`]s
+pub fn any_animation_active(
+    animation_players: Query<&AnimationPlayer>,
+    mut any_active: ResMut<AnyAnimationActive>,
+) {
+    let active = animation_players.iter().any(AnimationPlayer::is_animating);
+    if any_active.0 != active {
+        any_active.0 = active;
+    }
+}
+
+/// Apply the pose of `animation_clip` at `time` to the entities animated from `root`
+///
+/// This doesn't need an [`AnimationPlayer`] or a [`Time`] resource, so that an editor can scrub
+/// through a clip outside of the game loop. `time` is not wrapped, and the clip is sampled the
+/// same way as an animation played once. The curves of a clip in [`CurveSpace::DeltaFromRest`]
+/// are composed with the identity, as [`RestPose`]s are not looked for.
+///
+/// Returns `true` if any [`Transform`] was found for the paths of the clip.
+pub fn apply_clip_pose(
+    animation_clip: &AnimationClip,
+    time: f32,
+    root: Entity,
+    children: &Query<&Children>,
+    names: &Query<&Name>,
+    transforms: &mut Query<&mut Transform>,
+) -> bool {
+    let mut animation = PlayingAnimation::default();
+    animation.set_elapsed(time);
+    let (applied, _) = apply_animation(
+        1.0,
+        &animation,
+        animation_clip,
+        None,
+        root,
+        &mut HashMap::default(),
+        &[],
+        &[],
+        children,
+        names,
+        None,
+        transforms,
+    );
+    applied
+}
+
+/// Blend the poses of the entity at `path` in `clips` at `time`, without any ECS
+///
+/// The clips are blended in order with their weight, starting from the identity, the same way the
+/// animations of an [`AnimationPlayer`] are: a clip with a weight of `1.0` overrides the ones
+/// before it, and the weights are not normalized. `time` is not wrapped, as in
+/// [`apply_clip_pose`]. Returns `None` if none of the clips animates `path`.
+pub fn blend_clips(
+    clips: &[(&AnimationClip, f32)],
+    time: f32,
+    path: &EntityPath,
+) -> Option<Transform> {
+    let mut animation = PlayingAnimation::default();
+    animation.set_elapsed(time);
+    let mut pose = Transform::IDENTITY;
+    let mut animated = false;
+    for &(animation_clip, weight) in clips {
+        if animation_clip.curves.contains_key(path) {
+            animated = true;
+            let rest = match animation_clip.space {
+                CurveSpace::Local => None,
+                CurveSpace::DeltaFromRest => Some(Transform::IDENTITY),
+            };
+            blend_curves(
+                &mut pose,
+                weight,
+                &animation,
+                animation_clip,
+                None,
+                path,
+                rest,
+                None,
+            );
+        }
+    }
+    animated.then_some(pose)
+}
+
+/// Poses computed for any time, for each of the paths they animate
+///
+/// Sources are played by adding them to a [`BlendTree`] as a [`BlendNode::Source`], or with
+/// [`AnimationPlayer::play_source`]. This lets poses come from something else than stored
+/// keyframes, such as a [`ProceduralClip`].
+pub trait AnimationSource: fmt::Debug + Send + Sync + 'static {
+    /// Paths animated by the source
+    fn animated_paths(&self) -> &[EntityPath];
+
+    /// Sample the pose of `path` at `time`, as [`Keyframes`] holding a single keyframe
+    ///
+    /// Rotation, translation, scale or [`Keyframes::Trs`] keyframes are applied, others are
+    /// ignored. Returns `None` if `path` is not animated at `time`.
+    fn sample(&self, time: f32, path: &EntityPath) -> Option<Keyframes>;
+}
+
+/// Samples the pose of a path as [`Keyframes::Trs`], attributes without curves being left to
+/// identity
+///
+/// The clip is sampled at `time` without looping, according to the
+/// [edge behavior](EdgeBehavior) of its curves.
+impl AnimationSource for AnimationClip {
+    fn animated_paths(&self) -> &[EntityPath] {
+        &self.paths
+    }
+
+    fn sample(&self, time: f32, path: &EntityPath) -> Option<Keyframes> {
+        if !self.curves.contains_key(path) {
+            return None;
+        }
+        let mut animation = PlayingAnimation::default();
+        animation.set_elapsed(time);
+        let rest = match self.space {
+            CurveSpace::Local => None,
+            CurveSpace::DeltaFromRest => Some(Transform::IDENTITY),
+        };
+        let mut pose = Transform::IDENTITY;
+        blend_curves(&mut pose, 1.0, &animation, self, None, path, rest, None);
+        Some(Keyframes::Trs(vec![pose]))
+    }
+}
+
+/// Function computing the pose of a path at a given time, for a [`ProceduralClip`]
+type ProceduralSampler = dyn Fn(f32, &EntityPath) -> Option<Keyframes> + Send + Sync;
+
+/// An [`AnimationSource`] computing its poses with a function instead of stored keyframes
+///
+/// ```
+/// # use bevy_animation::{BlendNode, EntityPath, Keyframes, ProceduralClip};
+/// # use bevy_core::Name;
+/// # use bevy_math::Vec3;
+/// let bob = ProceduralClip::new(
+///     [EntityPath {
+///         parts: vec![Name::new("body")],
+///     }],
+///     |time, _| Some(Keyframes::Translation(vec![Vec3::Y * (time * 4.0).sin() * 0.1])),
+/// );
+/// let node = BlendNode::source(bob);
+/// ```
+#[derive(Clone)]
+pub struct ProceduralClip {
+    paths: Vec<EntityPath>,
+    sampler: Arc<ProceduralSampler>,
+}
+
+impl ProceduralClip {
+    /// Create a clip animating `paths` with the poses computed by `sampler`
+    ///
+    /// `sampler` is called with the time and the path to sample, and returns the pose as in
+    /// [`AnimationSource::sample`].
+    pub fn new(
+        paths: impl IntoIterator<Item = EntityPath>,
+        sampler: impl Fn(f32, &EntityPath) -> Option<Keyframes> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            paths: paths.into_iter().collect(),
+            sampler: Arc::new(sampler),
+        }
+    }
+}
+
+impl fmt::Debug for ProceduralClip {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProceduralClip")
+            .field("paths", &self.paths)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AnimationSource for ProceduralClip {
+    fn animated_paths(&self) -> &[EntityPath] {
+        &self.paths
+    }
+
+    fn sample(&self, time: f32, path: &EntityPath) -> Option<Keyframes> {
+        (self.sampler)(time, path)
+    }
+}
+
+/// Apply the pose of a [`BlendTree`] at `time`, blended with `weight`
+///
+/// Returns `true` if any [`Transform`] was found for the paths of the tree.
+#[allow(clippy::too_many_arguments)]
+fn apply_blend_tree(
+    blend_tree: &BlendTree,
+    time: f32,
+    weight: f32,
+    animations: &Assets<AnimationClip>,
+    root: Entity,
+    path_cache: &mut HashMap<EntityPath, Entity>,
+    frozen_bones: &[EntityPath],
+    children: &Query<&Children>,
+    names: &Query<&Name>,
+    transforms: &mut Query<&mut Transform>,
+) -> bool {
+    let mut applied = false;
+    for path in blend_tree.paths(animations) {
+        if weight == 0.0 || frozen_bones.contains(path) {
+            continue;
+        }
+        let entity = match cached_entity_from_path(path_cache, root, path, children, names) {
+            Some(entity) => entity,
+            None => continue,
+        };
+        if let Ok(mut transform) = transforms.get_mut(entity) {
+            applied = true;
+            if let Some(target) = blend_tree.evaluate(time, animations, path) {
+                let mut pose = *transform;
+                blend_transform(&mut pose, target, weight);
+                if !pose_approx_eq(&pose, &transform) {
+                    *transform = pose;
+                }
+            }
+        }
+    }
+    applied
+}
+
+/// Blend `target` into `pose` with `weight`, component by component
+fn blend_transform(pose: &mut Transform, target: Transform, weight: f32) {
+    blend(
+        &mut pose.translation,
+        target.translation,
+        weight,
+        Vec3::lerp,
+    );
+    blend(&mut pose.rotation, target.rotation, weight, Quat::slerp);
+    blend(&mut pose.scale, target.scale, weight, Vec3::lerp);
+}
+
+/// Blend an animation into the poses of `bones`, for [`AnimationOutput::Matrices`]
+///
+/// Returns `true` if the clip has curves for any of the bones.
+#[allow(clippy::too_many_arguments)]
+fn blend_bone_poses(
+    poses: &mut [Transform],
+    bones: &[EntityPath],
+    weight: f32,
+    animation: &PlayingAnimation,
+    animation_clip: &AnimationClip,
+    reference: Option<&AnimationClip>,
+    frozen_bones: &[EntityPath],
+    value_transforms: &[(EntityPath, ValueTransform)],
+) -> bool {
+    let mut applied = false;
+    for (path, pose) in bones.iter().zip(poses) {
+        let weight = weight * animation.mask_weight(path);
+        if weight == 0.0 || frozen_bones.contains(path) || !animation_clip.curves.contains_key(path)
+        {
+            continue;
+        }
+        applied = true;
+        let rest = match animation_clip.space {
+            CurveSpace::Local => None,
+            CurveSpace::DeltaFromRest => Some(Transform::IDENTITY),
+        };
+        blend_curves(
+            pose,
+            weight,
+            animation,
+            animation_clip,
+            reference,
+            path,
+            rest,
+            find_value_transform(value_transforms, path),
+        );
+    }
+    applied
+}
+
+/// Apply the pose of an animation, blended with `weight`
+///
+/// Returns `true` if any [`Transform`] was found for the paths of the clip, along with the paths
+/// for which no entity was found.
+#[allow(clippy::too_many_arguments)]
+fn apply_animation<'a>(
+    weight: f32,
+    animation: &PlayingAnimation,
+    animation_clip: &'a AnimationClip,
+    reference: Option<&AnimationClip>,
+    root: Entity,
+    path_cache: &mut HashMap<EntityPath, Entity>,
+    frozen_bones: &[EntityPath],
+    value_transforms: &[(EntityPath, ValueTransform)],
+    children: &Query<&Children>,
+    names: &Query<&Name>,
+    rest_poses: Option<&Query<&RestPose>>,
+    transforms: &mut Query<&mut Transform>,
+) -> (bool, Vec<&'a EntityPath>) {
+    let mut applied = false;
+    let mut missing_paths = Vec::new();
+    for path in &animation_clip.paths {
+        let weight = weight * animation.mask_weight(path);
+        if weight == 0.0 || frozen_bones.contains(path) {
+            continue;
+        }
+        let current_entity = match cached_entity_from_path(path_cache, root, path, children, names)
+        {
+            Some(entity) => entity,
+            None => {
+                missing_paths.push(path);
+                continue;
+            }
+        };
+        if let Ok(mut transform) = transforms.get_mut(current_entity) {
+            applied = true;
+            let mut pose = *transform;
+            let rest = match animation_clip.space {
+                CurveSpace::Local => None,
+                CurveSpace::DeltaFromRest => Some(
+                    rest_poses
+                        .and_then(|rest_poses| rest_poses.get(current_entity).ok())
+                        .map_or(Transform::IDENTITY, |rest| rest.0),
+                ),
+            };
+            blend_curves(
+                &mut pose,
+                weight,
+                animation,
+                animation_clip,
+                reference,
+                path,
+                rest,
+                find_value_transform(value_transforms, path),
+            );
+            // Only write the transform if it changed, to not trigger change detection
+            if !pose_approx_eq(&pose, &transform) {
+                *transform = pose;
+            }
+        }
+    }
+    (applied, missing_paths)
+}
+
+/// Rotation of each entity before the animations were applied, with the rotations the animations
+/// blended into it and their weights, in the order they were applied
+type RotationSamples = HashMap<Entity, (Quat, Vec<(Quat, f32)>)>;
+
+/// Record the rotations an animation would replace, to average them with
+/// [`apply_averaged_rotations`]
+#[allow(clippy::too_many_arguments)]
+fn record_rotations(
+    samples: &mut RotationSamples,
+    weight: f32,
+    animation: &PlayingAnimation,
+    animation_clip: &AnimationClip,
+    root: Entity,
+    path_cache: &mut HashMap<EntityPath, Entity>,
+    frozen_bones: &[EntityPath],
+    value_transforms: &[(EntityPath, ValueTransform)],
+    children: &Query<&Children>,
+    names: &Query<&Name>,
+    rest_poses: &Query<&RestPose>,
+    transforms: &Query<&mut Transform>,
+) {
+    for path in &animation_clip.paths {
+        let weight = weight * animation.mask_weight(path);
+        if weight == 0.0 || frozen_bones.contains(path) {
+            continue;
+        }
+        let rotates = animation_clip.curves[path].iter().any(|curve| {
+            matches!(
+                curve.keyframes,
+                Keyframes::Rotation(_) | Keyframes::QuantizedRotation(_) | Keyframes::Trs(_)
+            )
+        });
+        if !rotates {
+            continue;
+        }
+        let entity = match cached_entity_from_path(path_cache, root, path, children, names) {
+            Some(entity) => entity,
+            None => continue,
+        };
+        if let Ok(transform) = transforms.get(entity) {
+            let rest = match animation_clip.space {
+                CurveSpace::Local => None,
+                CurveSpace::DeltaFromRest => Some(
+                    rest_poses
+                        .get(entity)
+                        .map_or(Transform::IDENTITY, |rest| rest.0),
+                ),
+            };
+            let mut pose = *transform;
+            let value_transform = find_value_transform(value_transforms, path);
+            blend_curves(
+                &mut pose,
+                1.0,
+                animation,
+                animation_clip,
+                None,
+                path,
+                rest,
+                value_transform,
+            );
+            samples
+                .entry(entity)
+                .or_insert((transform.rotation, Vec::new()))
+                .1
+                .push((pose.rotation, weight));
+        }
+    }
+}
+
+/// Replace the rotations recorded by [`record_rotations`] with their weighted average, leaving
+/// `samples` empty
+fn apply_averaged_rotations(samples: &mut RotationSamples, transforms: &mut Query<&mut Transform>) {
+    for (entity, (initial, rotations)) in samples.drain() {
+        // Each animation is blended over the previous ones, so the last applied keeps its whole
+        // weight and the earlier ones only what the later ones left
+        let mut remaining = 1.0;
+        let mut weighted = Vec::with_capacity(rotations.len() + 1);
+        for (rotation, weight) in rotations.into_iter().rev() {
+            let weight = weight.clamp(0.0, 1.0);
+            weighted.push((rotation, weight * remaining));
+            remaining *= 1.0 - weight;
+        }
+        weighted.push((initial, remaining));
+        if let (Ok(mut transform), Some(rotation)) =
+            (transforms.get_mut(entity), average_rotations(&weighted))
+        {
+            if !transform.rotation.abs_diff_eq(rotation, f32::EPSILON) {
+                transform.rotation = rotation;
+            }
+        }
+    }
+}
+
+/// Blend the curves of `path` in an animation into `pose`, with `weight`
+///
+/// `rest` is the pose the curves are composed with when the clip is in
+/// [`CurveSpace::DeltaFromRest`], and `value_transform` adjusts the sampled values, including the
+/// ones of the additive reference.
+#[allow(clippy::too_many_arguments)]
+fn blend_curves(
+    pose: &mut Transform,
+    weight: f32,
+    animation: &PlayingAnimation,
+    animation_clip: &AnimationClip,
+    reference: Option<&AnimationClip>,
+    path: &EntityPath,
+    rest: Option<Transform>,
+    value_transform: Option<&ValueTransform>,
+) {
+    let curves = match animation_clip.curves.get(path) {
+        Some(curves) => curves,
+        None => return,
+    };
+    let elapsed = animation.sample_time(animation_clip);
+    let additive = animation.blend_mode != BlendMode::Replace;
+    let loop_duration = if animation_clip.interpolate_loop && animation.loops() {
+        Some(animation_clip.duration)
+    } else {
+        None
+    };
+    // The reference clip is sampled at the same phase as the animation
+    let reference_time = match reference {
+        Some(reference) if animation_clip.duration > 0.0 => {
+            elapsed / animation_clip.duration * reference.duration
+        }
+        _ => 0.0,
+    };
+    for curve in curves.iter().filter(|curve| curve.enabled) {
+        let weight = weight * curve.weight;
+        let reference_curve = reference
+            .and_then(|reference| reference.curves.get(path))
+            .and_then(|reference_curves| {
+                reference_curves.iter().find(|reference_curve| {
+                    reference_curve
+                        .keyframes
+                        .animates_same_attribute(&curve.keyframes)
+                })
+            });
+        let take_shortest_path =
+            curve.take_shortest_path && !animation_clip.preserve_rotation_winding;
+        let (rotation, translation, scale) = match &curve.keyframes {
+            Keyframes::Rotation(_) | Keyframes::QuantizedRotation(_) => (
+                curve.sample_rotation_looping(elapsed, loop_duration, take_shortest_path, None),
+                None,
+                None,
+            ),
+            Keyframes::Translation(_) | Keyframes::QuantizedTranslation(_) => (
+                None,
+                curve.sample_translation_looping(elapsed, loop_duration, None),
+                None,
+            ),
+            Keyframes::Scale(_) | Keyframes::QuantizedScale(_) => (
+                None,
+                None,
+                curve.sample_scale_looping(elapsed, loop_duration, None),
+            ),
+            Keyframes::Trs(_) => {
+                match curve.sample_trs_looping(elapsed, loop_duration, take_shortest_path, None) {
+                    Some(trs) => (Some(trs.rotation), Some(trs.translation), Some(trs.scale)),
+                    None => (None, None, None),
+                }
+            }
+            // Applied by the systems added with `AnimationPlugin::register_animated`
+            Keyframes::Scalar(_) | Keyframes::IkGoal { .. } | Keyframes::SpriteIndex(_) => {
+                (None, None, None)
+            }
+        };
+        let value_transform = value_transform.copied().unwrap_or_default();
+        let rotation = rotation.map(|rotation| value_transform.transform_rotation(rotation));
+        let translation =
+            translation.map(|translation| value_transform.transform_translation(translation));
+        let scale = scale.map(|scale| value_transform.transform_scale(scale));
+        if let Some(rotation) = rotation {
+            let previous_rotation = pose.rotation;
+            if !additive {
+                let rotation = rest.map_or(rotation, |rest| rest.rotation * rotation);
+                blend(&mut pose.rotation, rotation, weight, Quat::slerp);
+            } else if let Some(reference) = additive_reference(
+                &animation.blend_mode,
+                curve,
+                reference_curve,
+                reference_time,
+                |curve, time| {
+                    let trs = || Some(curve.sample_trs(time)?.rotation);
+                    curve.sample_rotation(time).or_else(trs)
+                },
+            )
+            .map(|reference| value_transform.transform_rotation(reference))
+            {
+                pose.rotation *= Quat::IDENTITY.slerp(reference.inverse() * rotation, weight);
+            }
+            if let Some(pivot) = curve.pivot {
+                // Move the entity so that the pivot is where the previous rotation put it
+                pose.translation += previous_rotation * pivot - pose.rotation * pivot;
+            }
+        }
+        if let Some(translation) = translation {
+            if !additive {
+                let translation = rest.map_or(translation, |rest| rest.translation + translation);
+                blend(&mut pose.translation, translation, weight, Vec3::lerp);
+            } else if let Some(reference) = additive_reference(
+                &animation.blend_mode,
+                curve,
+                reference_curve,
+                reference_time,
+                |curve, time| {
+                    let trs = || Some(curve.sample_trs(time)?.translation);
+                    curve.sample_translation(time).or_else(trs)
+                },
+            )
+            .map(|reference| value_transform.transform_translation(reference))
+            {
+                pose.translation += (translation - reference) * weight;
+            }
+        }
+        if let Some(scale) = scale {
+            if !additive {
+                let scale = rest.map_or(scale, |rest| rest.scale * scale);
+                match curve.scale_blend.unwrap_or(animation.scale_blend) {
+                    ScaleBlend::Linear => {
+                        blend(&mut pose.scale, scale, weight, Vec3::lerp);
+                    }
+                    ScaleBlend::Multiplicative => {
+                        pose.scale *= Vec3::ONE.lerp(scale, weight);
+                    }
+                }
+            } else if let Some(reference) = additive_reference(
+                &animation.blend_mode,
+                curve,
+                reference_curve,
+                reference_time,
+                |curve, time| {
+                    let trs = || Some(curve.sample_trs(time)?.scale);
+                    curve.sample_scale(time).or_else(trs)
+                },
+            )
+            .map(|reference| value_transform.transform_scale(reference))
+            {
+                pose.scale *= Vec3::ONE.lerp(scale / reference, weight);
+            }
+        }
+    }
+}
+
+/// Are two transforms equal, within a margin small enough not to be visible
+fn pose_approx_eq(a: &Transform, b: &Transform) -> bool {
+    const EPSILON: f32 = 1e-6;
+    a.translation.abs_diff_eq(b.translation, EPSILON)
+        && a.rotation.abs_diff_eq(b.rotation, EPSILON)
+        && a.scale.abs_diff_eq(b.scale, EPSILON)
+}
+
+/// Value of the reference pose a curve is measured against when blending additively
+fn additive_reference<T>(
+    blend_mode: &BlendMode,
+    curve: &VariableCurve,
+    reference_curve: Option<&VariableCurve>,
+    reference_time: f32,
+    sample: impl Fn(&VariableCurve, f32) -> Option<T>,
+) -> Option<T> {
+    match blend_mode {
+        BlendMode::Replace => None,
+        BlendMode::Additive { reference_time } => {
+            let first = *curve.keyframe_timestamps.first()?;
+            let last = *curve.keyframe_timestamps.last()?;
+            sample(curve, reference_time.clamp(first, last))
+        }
+        BlendMode::AdditiveFrom(_) => sample(reference_curve?, reference_time),
+    }
+}
+
+/// Blend `value` into `current`, overriding it with a weight of `1.0`
+fn blend<T: Copy>(current: &mut T, value: T, weight: f32, interpolation: impl Fn(T, T, f32) -> T) {
+    *current = if weight == 1.0 {
+        value
+    } else {
+        interpolation(*current, value, weight)
+    };
+}
+
+/// Label enum for the systems relating to animations
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
+pub enum AnimationSystem {
+    /// Advances the [`AnimationPlayer`]s and writes the pose sampled from their animations to the
+    /// animated [`Transform`]s
+    ///
+    /// Systems setting the target of [`AnimationOverride`]s from the sampled pose, such as inverse
+    /// kinematics, can run after it and before [`AnimationSystem::Apply`].
+    Sample,
+    /// Blends the sampled pose toward the [`AnimationOverride`]s, after
+    /// [`AnimationSystem::Sample`]
+    ///
+    /// Systems post-processing the final pose, such as look-at, can run after it and before
+    /// [`TransformSystem::TransformPropagate`].
+    Apply,
+}
+
+/// Source of the frame delta used to advance animations
+///
+/// This is implemented for [`Time`], and can be implemented for a custom simulation clock to
+/// animate with [`AnimationPlugin::with_clock`].
+pub trait DeltaSeconds: Resource {
+    /// Time elapsed since the previous frame, in seconds
+    fn delta_seconds(&self) -> f32;
+}
+
+impl DeltaSeconds for Time {
+    fn delta_seconds(&self) -> f32 {
+        Time::delta_seconds(self)
+    }
+}
+
+/// Adds animation support to an app
+///
+/// The systems of the plugin run in [`CoreStage::PostUpdate`] by default, or in the stage set
+/// with [`AnimationPlugin::in_stage`].
+pub struct AnimationPlugin {
+    stage: StageLabelId,
+    add_animation_player: fn(&mut App, StageLabelId),
+    animated_components: Vec<Box<AddAnimatedComponent>>,
+}
+
+type AddAnimatedComponent = dyn Fn(&mut App, StageLabelId) + Send + Sync;
+
+type AnimatedComponentFn<C> = dyn Fn(&mut C, &VariableCurve, f32, f32) + Send + Sync;
+
+/// Value of a component animated by the scalar curve at an index, registered with
+/// [`AnimationPlugin::register_animated_scalar`]
+pub type ScalarField<C> = for<'a> fn(&'a mut C, usize) -> Option<&'a mut f32>;
+
+impl Default for AnimationPlugin {
+    fn default() -> Self {
+        Self::with_clock::<Time>()
+    }
+}
+
+impl AnimationPlugin {
+    /// Animate with the frame delta of the resource `T` instead of [`Time`]
+    pub fn with_clock<T: DeltaSeconds>() -> Self {
+        Self {
+            stage: CoreStage::PostUpdate.as_label(),
+            add_animation_player: |app, stage| {
+                app.add_system_to_stage(
+                    stage,
+                    advance_animation_clocks::<T>.before(AnimationSystem::Sample),
+                );
+                app.add_system_to_stage(stage, play_clip_handles.before(AnimationSystem::Sample));
+                app.add_system_to_stage(
+                    stage,
+                    animation_player::<T>
+                        .label(AnimationSystem::Sample)
+                        .before(TransformSystem::TransformPropagate),
+                );
+                app.add_system_to_stage(
+                    stage,
+                    detect_stuck_animation_players::<T>.after(AnimationSystem::Sample),
+                );
+            },
+            animated_components: Vec::new(),
+        }
+    }
+
+    /// Run the systems of the plugin in `stage` instead of [`CoreStage::PostUpdate`]
+    ///
+    /// This is meant for apps post-processing the pose in another stage, for example with an
+    /// inverse kinematics pass in [`CoreStage::Update`], which can then be ordered after
+    /// [`AnimationSystem::Apply`]. [`animation_player`] is only ordered before
+    /// [`TransformSystem::TransformPropagate`] when they are in the same stage, so `stage`
+    /// should run before it for the pose to be propagated in the same frame.
+    pub fn in_stage(mut self, stage: impl StageLabel) -> Self {
+        self.stage = stage.as_label();
+        self
+    }
+
+    /// Animate the component `C` with the [scalar](Keyframes::Scalar),
+    /// [inverse kinematics goal](Keyframes::IkGoal) and [sprite index](Keyframes::SpriteIndex)
+    /// curves of the clips
+    ///
+    /// For each entity with a `C` targeted by a playing animation, `apply` is called with every
+    /// such curve of its path, the time at which to [sample](VariableCurve::sample_scalar) it,
+    /// and the weight of the animation. The animations are given in blending order, so a weight
+    /// of `1.0` is expected to overwrite what was applied before.
+    ///
+    /// `C` can be registered several times, for example to animate different fields with
+    /// [`animate_reflected_scalar`]: each curve is then given to every `apply` in the order they
+    /// were registered.
+    pub fn register_animated<C: Component>(
+        mut self,
+        apply: impl Fn(&mut C, &VariableCurve, f32, f32) + Send + Sync + 'static,
+    ) -> Self {
+        let apply: Arc<AnimatedComponentFn<C>> = Arc::new(apply);
+        self.animated_components.push(Box::new(move |app, stage| {
+            match app.world.get_resource_mut::<AnimatedComponent<C>>() {
+                Some(mut animated) => animated.applies.push(apply.clone()),
+                None => {
+                    app.insert_resource(AnimatedComponent {
+                        applies: vec![apply.clone()],
+                    })
+                    .add_system_to_stage(
+                        stage,
+                        animate_component::<C>.after(AnimationSystem::Apply),
+                    );
+                }
+            }
+        }));
+        self
+    }
+
+    /// Animate values of the component `C`, like morph target weights, with the
+    /// [scalar](Keyframes::Scalar) curves of the clips
+    ///
+    /// `field` returns the value animated by the scalar curve at an index among the scalar
+    /// curves of its path, or `None` to skip the curve. This is the layout of
+    /// [morph target weights](GltfProperty::MorphTargetWeights) imported with
+    /// [`AnimationClip::from_gltf_channels`]. Unlike
+    /// [`register_animated`](AnimationPlugin::register_animated), the values are blended
+    /// following the [`BlendMode`] of the animations: additive animations add their difference
+    /// to the reference pose, scaled by their weight, so that a blink can be layered over the
+    /// weights of a speaking animation. The blended value is clamped between `0.0` and `1.0` for
+    /// curves with [`clamp_scalar`](VariableCurve::clamp_scalar).
+    ///
+    /// As with [`register_animated`](AnimationPlugin::register_animated), `C` can be registered
+    /// several times: each curve is then blended into the value returned by every `field`.
+    pub fn register_animated_scalar<C: Component>(mut self, field: ScalarField<C>) -> Self {
+        self.animated_components.push(Box::new(move |app, stage| {
+            match app.world.get_resource_mut::<AnimatedScalar<C>>() {
+                Some(mut animated) => animated.fields.push(field),
+                None => {
+                    app.insert_resource(AnimatedScalar {
+                        fields: vec![field],
+                    })
+                    .add_system_to_stage(stage, animate_scalar::<C>.after(AnimationSystem::Apply));
+                }
+            }
+        }));
+        self
+    }
+}
+
+/// Values of the component `C` animated by scalar curves, registered with
+/// [`AnimationPlugin::register_animated_scalar`]
+#[derive(Resource)]
+struct AnimatedScalar<C: Component> {
+    fields: Vec<ScalarField<C>>,
+}
+
+/// System that blends the [scalar](Keyframes::Scalar) curves into the values of the component
+/// `C`, following the blend mode of the animations
+fn animate_scalar<C: Component>(
+    animated: Res<AnimatedScalar<C>>,
+    animations: Res<Assets<AnimationClip>>,
+    animation_players: Query<(Entity, ChangeTrackers<AnimationPlayer>, &AnimationPlayer)>,
+    names: Query<&Name>,
+    children: Query<&Children>,
+    mut components: Query<&mut C>,
+) {
+    fn scalar_curves(curves: &[VariableCurve]) -> impl Iterator<Item = &VariableCurve> {
+        curves
+            .iter()
+            .filter(|curve| matches!(curve.keyframes, Keyframes::Scalar(_)))
+    }
+
+    for (entity, tracker, player) in &animation_players {
+        if !player.enabled
+            || player.animations().all(|(_, animation)| animation.paused) && !tracker.is_changed()
+        {
+            continue;
+        }
+        let root = match descendant_from_names(entity, &player.root_offset, &children, &names) {
+            Some(root) => root,
+            None => continue,
+        };
+        for (weight, animation) in player.animations() {
+            let weight = weight * player.master_weight;
+            let animation_clip = match animations.get(&animation.animation_clip) {
+                Some(animation_clip) if weight >= player.min_weight => animation_clip,
+                _ => continue,
+            };
+            let reference = match &animation.blend_mode {
+                BlendMode::AdditiveFrom(handle) => match animations.get(handle) {
+                    Some(reference) => Some(reference),
+                    None => continue,
+                },
+                _ => None,
+            };
+            let time = animation.sample_time(animation_clip);
+            // The reference clip is sampled at the same phase as the animation
+            let reference_time = match reference {
+                Some(reference) if animation_clip.duration > 0.0 => {
+                    time / animation_clip.duration * reference.duration
+                }
+                _ => 0.0,
+            };
+            for (path, curves) in &animation_clip.curves {
+                let weight = weight * animation.mask_weight(path);
+                if weight == 0.0 || player.frozen_bones.contains(path) {
+                    continue;
+                }
+                let target = match player.path_cache.get(path) {
+                    Some(&target) => Some(target),
+                    None => entity_from_path(root, path, &children, &names),
+                };
+                let mut component = match target.and_then(|target| components.get_mut(target).ok())
+                {
+                    Some(component) => component,
+                    None => continue,
+                };
+                let reference_curves = reference.and_then(|reference| reference.curves.get(path));
+                for (index, curve) in scalar_curves(curves).enumerate() {
+                    let value = match curve.sample_scalar(time) {
+                        Some(value) if curve.enabled => value,
+                        _ => continue,
+                    };
+                    let weight = weight * curve.weight;
+                    let reference = match animation.blend_mode {
+                        BlendMode::Replace => None,
+                        _ => {
+                            // Curves are matched with the scalar curve at the same index in the
+                            // reference
+                            let reference_curve = reference_curves
+                                .and_then(|curves| scalar_curves(curves).nth(index));
+                            match additive_reference(
+                                &animation.blend_mode,
+                                curve,
+                                reference_curve,
+                                reference_time,
+                                VariableCurve::sample_scalar,
+                            ) {
+                                Some(reference) => Some(reference),
+                                None => continue,
+                            }
+                        }
+                    };
+                    for field in &animated.fields {
+                        let current = match field(&mut component, index) {
+                            Some(current) => current,
+                            None => continue,
+                        };
+                        match reference {
+                            Some(reference) => *current += (value - reference) * weight,
+                            None => blend(current, value, weight, |a, b, t| a + (b - a) * t),
+                        }
+                        if curve.clamp_scalar {
+                            *current = current.clamp(0.0, 1.0);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// How to animate the component `C`, registered with [`AnimationPlugin::register_animated`]
+#[derive(Resource)]
+struct AnimatedComponent<C: Component> {
+    applies: Vec<Arc<AnimatedComponentFn<C>>>,
+}
+
+/// System that applies the curves that don't animate a [`Transform`] to the component `C`
+fn animate_component<C: Component>(
+    animated: Res<AnimatedComponent<C>>,
+    animations: Res<Assets<AnimationClip>>,
+    animation_players: Query<(Entity, ChangeTrackers<AnimationPlayer>, &AnimationPlayer)>,
+    names: Query<&Name>,
+    children: Query<&Children>,
+    mut components: Query<&mut C>,
+) {
+    for (entity, tracker, player) in &animation_players {
+        if !player.enabled
+            || player.animations().all(|(_, animation)| animation.paused) && !tracker.is_changed()
+        {
+            continue;
+        }
+        let root = match descendant_from_names(entity, &player.root_offset, &children, &names) {
+            Some(root) => root,
+            None => continue,
+        };
+        for (weight, animation) in player.animations() {
+            let weight = weight * player.master_weight;
+            let animation_clip = match animations.get(&animation.animation_clip) {
+                Some(animation_clip) if weight >= player.min_weight => animation_clip,
+                _ => continue,
+            };
+            let time = animation.sample_time(animation_clip);
+            for (path, curves) in &animation_clip.curves {
+                let weight = weight * animation.mask_weight(path);
+                if weight == 0.0 || player.frozen_bones.contains(path) {
+                    continue;
+                }
+                let target = match player.path_cache.get(path) {
+                    Some(&target) => Some(target),
+                    None => entity_from_path(root, path, &children, &names),
+                };
+                if let Some(mut component) =
+                    target.and_then(|target| components.get_mut(target).ok())
+                {
+                    for curve in curves.iter().filter(|curve| curve.enabled) {
+                        if let Keyframes::Scalar(_)
+                        | Keyframes::IkGoal { .. }
+                        | Keyframes::SpriteIndex(_) = curve.keyframes
+                        {
+                            for apply in &animated.applies {
+                                apply(&mut component, curve, time, weight * curve.weight);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Blend a [scalar](Keyframes::Scalar) curve sampled at `time` into a reflected number
+///
+/// This is meant to be called from [`AnimationPlugin::register_animated`] to animate a field of a
+/// reflected component, like the `left` position of the `Style` of a UI node. The value can be an
+/// `f32`, or an enum variant holding a single `f32` such as `Val::Px`: only the number is
+/// interpolated, so the value keeps its unit. Other values, like `Val::Auto`, are left untouched.
+pub fn animate_reflected_scalar(
+    value: &mut dyn Reflect,
+    curve: &VariableCurve,
+    time: f32,
+    weight: f32,
+) {
+    let sampled = match curve.sample_scalar(time) {
+        Some(sampled) => sampled,
+        None => return,
+    };
+    let wraps_number =
+        matches!(value.reflect_ref(), ReflectRef::Enum(variant) if variant.field_len() == 1);
+    let number = match value.reflect_mut() {
+        ReflectMut::Enum(variant) if wraps_number => variant.field_at_mut(0),
+        _ if wraps_number => None,
+        _ => Some(value),
+    };
+    if let Some(number) = number.and_then(|number| number.downcast_mut::<f32>()) {
+        blend(number, sampled, weight, |a, b, t| a + (b - a) * t);
+    }
+}
+
+impl Plugin for AnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<AnimationClip>()
+            .init_asset_loader::<AnimationCsvLoader>()
+            .register_type::<AnimationClip>()
+            .register_type::<VariableCurve>()
+            .register_type::<Keyframes>()
+            .register_type::<QuantizedVec3s>()
+            .register_type::<EdgeBehavior>()
+            .register_type::<Interpolation>()
+            .register_type::<IkGoalSpace>()
+            .register_type::<CurveKind>()
+            .register_type::<EntityPath>()
+            .register_type::<AnimationTarget>()
+            .register_type::<TimeWarp>()
+            .register_type::<UpAxis>()
+            .register_type::<CurveSpace>()
+            .register_type::<RestPose>()
+            .register_type::<AnimationPlayer>()
+            .register_type::<PlayingAnimation>()
+            .register_type::<AnimationDriver>()
+            .register_type::<AnimationClock>()
+            .register_type::<AnimationOutput>()
+            .register_type::<RepeatAnimation>()
+            .register_type::<FinishedBehavior>()
+            .register_type::<BlendMode>()
+            .register_type::<ScaleBlend>()
+            .register_type::<AnimationMask>()
+            .register_type::<Easing>()
+            .register_type::<AnimationClips>()
+            .register_type::<GlobalAnimationSpeed>()
+            .register_type::<GlobalAnimationPause>()
+            .register_type::<ActiveAnimations>()
+            .register_type::<ActiveAnimation>()
+            .register_type::<AnyAnimationActive>()
+            .register_type::<AnimationOverride>()
+            .init_resource::<ActiveAnimations>()
+            .init_resource::<AnyAnimationActive>()
+            .add_event::<AnimationMarkerReached>()
+            .add_event::<AnimationIntervalEntered>()
+            .add_event::<AnimationIntervalExited>()
+            .add_event::<ResetAnimationPlayers>();
+        app.add_system_to_stage(
+            self.stage,
+            repair_animation_clips.before(AnimationSystem::Sample),
+        );
+        app.add_system_to_stage(
+            self.stage,
+            reset_animation_players.before(AnimationSystem::Sample),
+        );
+        app.add_system_to_stage(
+            self.stage,
+            apply_animation_overrides
+                .label(AnimationSystem::Apply)
+                .after(AnimationSystem::Sample)
+                .before(TransformSystem::TransformPropagate),
+        );
+        app.add_system_to_stage(
+            self.stage,
+            any_animation_active.after(AnimationSystem::Apply),
+        );
+        (self.add_animation_player)(app, self.stage);
+        for add_animated_component in &self.animated_components {
+            add_animated_component(app, self.stage);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::any::TypeId;
+
+    use bevy_app::{App, AppTypeRegistry, CoreStage};
+    use bevy_asset::{AddAsset, AssetPlugin, Assets, Handle, HandleId};
+    use bevy_core::{CorePlugin, Name};
+    use bevy_ecs::{
+        entity::Entity,
+        event::{Events, ManualEventReader},
+        prelude::Component,
+        query::With,
+        schedule::IntoSystemDescriptor,
+        system::{Query, Resource, SystemState},
+    };
+    use bevy_hierarchy::BuildWorldChildren;
+    use bevy_hierarchy::Children;
+    use bevy_math::{EulerRot, Mat4, Quat, Vec3};
+    use bevy_reflect::{FromReflect, Reflect};
+    use bevy_time::Time;
+    use bevy_transform::prelude::{GlobalTransform, Transform};
+    use bevy_utils::Duration;
+
+    use crate::{
+        animate_reflected_scalar, apply_clip_pose, average_rotations, blend_clips,
+        ActiveAnimations, AnimationBundle, AnimationClip, AnimationClipError, AnimationClips,
+        AnimationClock, AnimationCsvError, AnimationDriver, AnimationError,
+        AnimationIntervalEntered, AnimationIntervalExited, AnimationMarkerReached, AnimationMask,
+        AnimationOutput, AnimationOverride, AnimationPlayer, AnimationPlugin, AnimationSource,
+        AnimationStrictError, AnimationStrictMode, AnimationSystem, AnimationTarget,
+        AnyAnimationActive, BlendMode, BlendNode, BlendTree, BoneContribution, ChannelFlags,
+        ClipRecorder, CurveCursor, CurveKind, CurveSpace, DeltaSeconds, Easing, EdgeBehavior,
+        EntityPath, FinishedBehavior, GlobalAnimationPause, GlobalAnimationSpeed,
+        GltfInterpolation, GltfProperty, IkGoalSpace, Interpolation, Keyframes, PlayingAnimation,
+        ProceduralClip, RepeatAnimation, ResetAnimationPlayers, RestPose, ScaleBlend,
+        StuckAnimationPlayers, TimeWarp, UpAxis, ValueTransform, VariableCurve,
+    };
+
+    fn setup_app() -> App {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_plugin(AnimationPlugin::default())
+            .add_asset::<AnimationClip>();
+        let mut time = Time::default();
+        time.update();
+        app.insert_resource(time);
+        app
+    }
+
+    fn advance_time(app: &mut App, seconds: f32) {
+        let mut time = app.world.resource_mut::<Time>();
+        let last_update = time.last_update().unwrap();
+        time.update_with_instant(last_update + Duration::from_secs_f32(seconds));
+    }
+
+    fn translation_clip(app: &mut App, name: &str) -> Handle<AnimationClip> {
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new(name.to_string())],
+            },
+            VariableCurve::new(
+                vec![0.0, 1.0],
+                Keyframes::Translation(vec![Vec3::ZERO, Vec3::X]),
+            ),
+        );
+        app.world.resource_mut::<Assets<AnimationClip>>().add(clip)
+    }
+
+    fn spawn_player(app: &mut App, name: &str, handle: Handle<AnimationClip>) -> Entity {
+        let mut player = AnimationPlayer::default();
+        player.play(handle);
+        app.world
+            .spawn((Name::new(name.to_string()), Transform::default(), player))
+            .id()
+    }
+
+    #[test]
+    fn stopped_player_does_not_apply_pose() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let entity = spawn_player(&mut app, "root", handle);
+
+        advance_time(&mut app, 0.5);
+        app.update();
+        let translation = app.world.get::<Transform>(entity).unwrap().translation;
+        assert_eq!(translation, Vec3::new(0.5, 0.0, 0.0));
+
+        app.world.get_mut::<AnimationPlayer>(entity).unwrap().stop();
+        app.world.get_mut::<Transform>(entity).unwrap().translation = Vec3::Y;
+        advance_time(&mut app, 0.25);
+        app.update();
+        let transform = app.world.get::<Transform>(entity).unwrap();
+        assert_eq!(transform.translation, Vec3::Y);
+    }
+
+    #[test]
+    fn stop_keeps_configuration() {
+        let root_offset = EntityPath {
+            parts: vec![Name::new("scene")],
+        };
+        let mut player = AnimationPlayer::default();
+        player
+            .play(Handle::weak(HandleId::random::<AnimationClip>()))
+            .set_master_weight(0.5)
+            .set_root_offset(root_offset.clone())
+            .slot("upper_body")
+            .play(Handle::weak(HandleId::random::<AnimationClip>()));
+
+        player.stop();
+        assert_eq!(player.animation.animation_clip, Handle::default());
+        assert_eq!(player.slots().count(), 0);
+        assert_eq!(player.master_weight(), 0.5);
+        assert_eq!(player.root_offset(), &root_offset);
+    }
+
+    #[test]
+    fn global_speed_scales_all_players() {
+        let mut app = setup_app();
+        app.insert_resource(GlobalAnimationSpeed(0.5));
+        let handle = translation_clip(&mut app, "root");
+        let first = spawn_player(&mut app, "root", handle.clone());
+        let second = spawn_player(&mut app, "root", handle);
+        app.world
+            .get_mut::<AnimationPlayer>(second)
+            .unwrap()
+            .set_speed(2.0);
+
+        advance_time(&mut app, 0.5);
+        app.update();
+        assert_eq!(
+            app.world.get::<AnimationPlayer>(first).unwrap().elapsed(),
+            0.25
+        );
+        assert_eq!(
+            app.world.get::<AnimationPlayer>(second).unwrap().elapsed(),
+            0.5
+        );
+    }
+
+    #[test]
+    fn path_time_range() {
+        let path = EntityPath {
+            parts: vec![Name::new("bone")],
+        };
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(
+                vec![0.5, 1.0],
+                Keyframes::Translation(vec![Vec3::ZERO, Vec3::X]),
+            ),
+        );
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(vec![0.75, 1.5], Keyframes::Scale(vec![Vec3::ONE, Vec3::X])),
+        );
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("other")],
+            },
+            VariableCurve::new(vec![0.0, 3.0], Keyframes::Scale(vec![Vec3::ONE, Vec3::X])),
+        );
+
+        assert_eq!(clip.path_time_range(&path), Some((0.5, 1.5)));
+        assert_eq!(clip.duration(), 3.0);
+        assert_eq!(clip.path_time_range(&EntityPath::default()), None);
+    }
+
+    #[test]
+    fn duplicate_sibling_names_use_first_child() {
+        let mut app = setup_app();
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root"), Name::new("bone")],
+            },
+            VariableCurve::new(
+                vec![0.0, 1.0],
+                Keyframes::Translation(vec![Vec3::ZERO, Vec3::X]),
+            ),
+        );
+        let handle = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+        let root = spawn_player(&mut app, "root", handle);
+        let first = app
+            .world
+            .spawn((Name::new("bone"), Transform::default()))
+            .id();
+        let second = app
+            .world
+            .spawn((Name::new("bone"), Transform::default()))
+            .id();
+        app.world.entity_mut(root).push_children(&[first, second]);
+
+        advance_time(&mut app, 0.5);
+        app.update();
+        let first = app.world.get::<Transform>(first).unwrap();
+        assert_eq!(first.translation, Vec3::new(0.5, 0.0, 0.0));
+        let second = app.world.get::<Transform>(second).unwrap();
+        assert_eq!(second.translation, Vec3::ZERO);
+    }
+
+    #[test]
+    fn single_part_path_animates_root() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let root = spawn_player(&mut app, "root", handle);
+
+        advance_time(&mut app, 0.25);
+        app.update();
+        let transform = app.world.get::<Transform>(root).unwrap();
+        assert_eq!(transform.translation, Vec3::new(0.25, 0.0, 0.0));
+    }
+
+    #[test]
+    fn play_reversed_starts_from_end() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let root = spawn_player(&mut app, "root", handle.clone());
+        app.world
+            .get_mut::<AnimationPlayer>(root)
+            .unwrap()
+            .play_reversed(handle);
+
+        advance_time(&mut app, 0.25);
+        app.update();
+        let transform = app.world.get::<Transform>(root).unwrap();
+        assert_eq!(transform.translation, Vec3::new(0.75, 0.0, 0.0));
+
+        advance_time(&mut app, 0.5);
+        app.update();
+        let transform = app.world.get::<Transform>(root).unwrap();
+        assert_eq!(transform.translation, Vec3::new(0.25, 0.0, 0.0));
+    }
+
+    #[test]
+    fn externally_driven_player_does_not_advance() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let root = spawn_player(&mut app, "root", handle);
+        app.world
+            .get_mut::<AnimationPlayer>(root)
+            .unwrap()
+            .set_driver(AnimationDriver::External(0.5));
+
+        for _ in 0..3 {
+            advance_time(&mut app, 0.1);
+            app.update();
+            let player = app.world.get::<AnimationPlayer>(root).unwrap();
+            assert_eq!(player.elapsed(), 0.5);
+            let transform = app.world.get::<Transform>(root).unwrap();
+            assert_eq!(transform.translation, Vec3::new(0.5, 0.0, 0.0));
+        }
+    }
+
+    #[test]
+    fn disabled_player_resumes_where_it_was() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let root = spawn_player(&mut app, "root", handle);
+
+        advance_time(&mut app, 0.25);
+        app.update();
+        app.world
+            .get_mut::<AnimationPlayer>(root)
+            .unwrap()
+            .set_enabled(false);
+        app.world.get_mut::<Transform>(root).unwrap().translation = Vec3::Y;
+
+        advance_time(&mut app, 0.25);
+        app.update();
+        assert_eq!(
+            app.world.get::<AnimationPlayer>(root).unwrap().elapsed(),
+            0.25
+        );
+        let transform = app.world.get::<Transform>(root).unwrap();
+        assert_eq!(transform.translation, Vec3::Y);
+
+        app.world
+            .get_mut::<AnimationPlayer>(root)
+            .unwrap()
+            .set_enabled(true);
+        advance_time(&mut app, 0.25);
+        app.update();
+        assert_eq!(
+            app.world.get::<AnimationPlayer>(root).unwrap().elapsed(),
+            0.5
+        );
+        let transform = app.world.get::<Transform>(root).unwrap();
+        assert_eq!(transform.translation, Vec3::new(0.5, 0.0, 0.0));
+    }
+
+    #[test]
+    fn sample_curve() {
+        let curve = VariableCurve::new(
+            vec![1.0, 2.0, 3.0],
+            Keyframes::Translation(vec![Vec3::ZERO, Vec3::X, Vec3::Y]),
+        );
+
+        assert_eq!(curve.sample_translation(0.5), None);
+        assert_eq!(
+            curve.sample_translation(1.5),
+            Some(Vec3::new(0.5, 0.0, 0.0))
+        );
+        assert_eq!(
+            curve.sample_translation(2.5),
+            Some(Vec3::new(0.5, 0.5, 0.0))
+        );
+        assert_eq!(curve.sample_translation(3.0), Some(Vec3::Y));
+        assert_eq!(curve.sample_translation(3.5), None);
+        assert_eq!(curve.sample_rotation(1.5), None);
+        assert_eq!(curve.sample_scale(1.5), None);
+    }
+
+    #[test]
+    fn time_warp_remaps_sampled_time() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let root = spawn_player(&mut app, "root", handle);
+        // Play the first half of the clip in a quarter of the time
+        app.world
+            .get_mut::<AnimationPlayer>(root)
+            .unwrap()
+            .set_time_warp(Some(TimeWarp {
+                keyframe_timestamps: vec![0.0, 0.25, 1.0],
+                keyframes: vec![0.0, 0.5, 1.0],
+            }));
+
+        advance_time(&mut app, 0.125);
+        app.update();
+        assert_eq!(
+            app.world.get::<AnimationPlayer>(root).unwrap().elapsed(),
+            0.125
+        );
+        let transform = app.world.get::<Transform>(root).unwrap();
+        assert_eq!(transform.translation, Vec3::new(0.25, 0.0, 0.0));
+    }
+
+    #[test]
+    fn zero_duration_clip() {
+        let mut app = setup_app();
+        let mut clip = AnimationClip::default();
+        assert!(clip.is_empty());
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            VariableCurve::new(vec![0.0], Keyframes::Translation(vec![Vec3::X])),
+        );
+        assert!(!clip.is_empty());
+        assert_eq!(clip.duration(), 0.0);
+        let handle = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+        let root = spawn_player(&mut app, "root", handle);
+        app.world.get_mut::<AnimationPlayer>(root).unwrap().repeat();
+
+        advance_time(&mut app, 0.5);
+        app.update();
+        let transform = app.world.get::<Transform>(root).unwrap();
+        assert_eq!(transform.translation, Vec3::X);
+    }
+
+    #[test]
+    fn slots_blend_on_top_of_main_animation() {
+        let mut app = setup_app();
+        let base = translation_clip(&mut app, "root");
+        let mut overlay = AnimationClip::default();
+        overlay.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            VariableCurve::new(vec![0.0], Keyframes::Translation(vec![Vec3::Y])),
+        );
+        let overlay = app
+            .world
+            .resource_mut::<Assets<AnimationClip>>()
+            .add(overlay);
+        let root = spawn_player(&mut app, "root", base);
+        app.world
+            .get_mut::<AnimationPlayer>(root)
+            .unwrap()
+            .slot("overlay")
+            .set_weight(0.5)
+            .play(overlay)
+            .repeat();
+
+        advance_time(&mut app, 0.5);
+        app.update();
+        let transform = app.world.get::<Transform>(root).unwrap();
+        assert_eq!(transform.translation, Vec3::new(0.25, 0.5, 0.0));
+
+        let mut player = app.world.get_mut::<AnimationPlayer>(root).unwrap();
+        assert_eq!(player.get_slot("overlay").unwrap().elapsed(), 0.5);
+        assert!(player.remove_slot("overlay").is_some());
+        assert_eq!(player.slots().count(), 0);
+    }
+
+    #[test]
+    fn convert_axes_round_trip() {
+        let path = EntityPath {
+            parts: vec![Name::new("bone")],
+        };
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(
+                vec![0.0],
+                Keyframes::Translation(vec![Vec3::new(1.0, 2.0, 3.0)]),
+            ),
+        );
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(
+                vec![0.0],
+                Keyframes::Rotation(vec![Quat::from_rotation_y(1.0)]),
+            ),
+        );
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(vec![0.0], Keyframes::Scale(vec![Vec3::new(1.0, 2.0, 3.0)])),
+        );
+
+        let z_up = clip.convert_axes(UpAxis::Y, UpAxis::Z);
+        let curves = &z_up.curves()[&path];
+        let translation = curves[0].sample_translation(0.0).unwrap();
+        assert!(translation.abs_diff_eq(Vec3::new(1.0, -3.0, 2.0), 1e-5));
+        let rotation = curves[1].sample_rotation(0.0).unwrap();
+        assert!(rotation.abs_diff_eq(Quat::from_rotation_z(1.0), 1e-5));
+        let scale = curves[2].sample_scale(0.0).unwrap();
+        assert!(scale.abs_diff_eq(Vec3::new(1.0, 3.0, 2.0), 1e-5));
+
+        let y_up = z_up.convert_axes(UpAxis::Z, UpAxis::Y);
+        for (original, converted) in clip.curves()[&path].iter().zip(&y_up.curves()[&path]) {
+            match (&original.keyframes, &converted.keyframes) {
+                (Keyframes::Rotation(original), Keyframes::Rotation(converted)) => {
+                    assert!(original[0].abs_diff_eq(converted[0], 1e-5));
+                }
+                (Keyframes::Translation(original), Keyframes::Translation(converted))
+                | (Keyframes::Scale(original), Keyframes::Scale(converted)) => {
+                    assert!(original[0].abs_diff_eq(converted[0], 1e-5));
+                }
+                _ => panic!("curve kind changed"),
+            }
+        }
+    }
+
+    #[test]
+    fn long_rotation_path() {
+        let mut curve = VariableCurve::new(
+            vec![0.0, 1.0],
+            Keyframes::Rotation(vec![
+                Quat::IDENTITY,
+                Quat::from_rotation_y(270f32.to_radians()),
+            ]),
+        );
+        let shortest = curve.sample_rotation(0.5).unwrap();
+        assert!(shortest.abs_diff_eq(Quat::from_rotation_y(-45f32.to_radians()), 1e-5));
+
+        curve.take_shortest_path = false;
+        let longest = curve.sample_rotation(0.5).unwrap();
+        assert!(longest.abs_diff_eq(Quat::from_rotation_y(135f32.to_radians()), 1e-5));
+    }
+
+    #[test]
+    fn play_all_with_offsets() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        for _ in 0..3 {
+            app.world.spawn(AnimationPlayer::default());
+        }
+
+        let mut players = app.world.query::<&mut AnimationPlayer>();
+        let mut offset = 0.0;
+        AnimationPlayer::play_all(players.iter_mut(&mut app.world), &handle, || {
+            offset += 0.25;
+            offset
+        });
+        let mut elapsed: Vec<f32> = players
+            .iter(&app.world)
+            .map(|player| player.elapsed())
+            .collect();
+        elapsed.sort_by(f32::total_cmp);
+        assert_eq!(elapsed, vec![0.25, 0.5, 0.75]);
+        assert!(players
+            .iter(&app.world)
+            .all(|player| player.animation.animation_clip() == &handle));
+    }
+
+    #[test]
+    fn clamp_forever_holds_last_frame() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let clamped = spawn_player(&mut app, "root", handle.clone());
+        let never = spawn_player(&mut app, "root", handle.clone());
+        let counted = spawn_player(&mut app, "root", handle);
+        app.world
+            .get_mut::<AnimationPlayer>(clamped)
+            .unwrap()
+            .set_repeat(RepeatAnimation::ClampForever);
+        app.world
+            .get_mut::<AnimationPlayer>(counted)
+            .unwrap()
+            .set_repeat(RepeatAnimation::Count(2));
+
+        advance_time(&mut app, 0.75);
+        app.update();
+        for entity in [clamped, never, counted] {
+            app.world.get_mut::<Transform>(entity).unwrap().translation = Vec3::Y;
+        }
+        advance_time(&mut app, 0.5);
+        app.update();
+
+        let player = app.world.get::<AnimationPlayer>(clamped).unwrap();
+        assert!(player.is_finished());
+        let transform = app.world.get::<Transform>(clamped).unwrap();
+        assert_eq!(transform.translation, Vec3::X);
+
+        let player = app.world.get::<AnimationPlayer>(never).unwrap();
+        assert!(player.is_finished());
+        let transform = app.world.get::<Transform>(never).unwrap();
+        assert_eq!(transform.translation, Vec3::Y);
+
+        let player = app.world.get::<AnimationPlayer>(counted).unwrap();
+        assert_eq!(player.completions(), 1);
+        assert!(!player.is_finished());
+        let transform = app.world.get::<Transform>(counted).unwrap();
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(0.25, 0.0, 0.0), 1e-5));
+    }
+
+    #[test]
+    fn advance_paused_player_by_exact_delta() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let root = spawn_player(&mut app, "root", handle.clone());
+        app.world.get_mut::<AnimationPlayer>(root).unwrap().pause();
+
+        for _ in 0..2 {
+            let clips = app.world.resource::<Assets<AnimationClip>>();
+            let clip = clips.get(&handle).unwrap().clone();
+            app.world
+                .get_mut::<AnimationPlayer>(root)
+                .unwrap()
+                .advance(Duration::from_millis(250), &clip);
+            advance_time(&mut app, 1.0);
+            app.update();
+        }
+        assert_eq!(
+            app.world.get::<AnimationPlayer>(root).unwrap().elapsed(),
+            0.5
+        );
+        let transform = app.world.get::<Transform>(root).unwrap();
+        assert_eq!(transform.translation, Vec3::new(0.5, 0.0, 0.0));
+    }
+
+    #[test]
+    fn unsorted_timestamps() {
+        let sorted = VariableCurve::new(
+            vec![0.0, 0.5, 0.5, 1.0],
+            Keyframes::Scale(vec![Vec3::ONE; 4]),
+        );
+        assert!(sorted.has_sorted_timestamps());
+        let descending =
+            VariableCurve::new(vec![1.0, 0.5, 0.0], Keyframes::Scale(vec![Vec3::ONE; 3]));
+        assert!(!descending.has_sorted_timestamps());
+    }
+
+    #[test]
+    fn blend_to_pose() {
+        let mut app = setup_app();
+        let clip = translation_clip(&mut app, "root");
+        let mut pose = AnimationClip::default();
+        pose.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            VariableCurve::new(vec![0.0], Keyframes::Translation(vec![Vec3::Y])),
+        );
+        let pose = app.world.resource_mut::<Assets<AnimationClip>>().add(pose);
+        let entity = spawn_player(&mut app, "root", clip);
+        app.update();
+        advance_time(&mut app, 0.5);
+        app.update();
+        app.world
+            .get_mut::<AnimationPlayer>(entity)
+            .unwrap()
+            .blend_to_pose(pose, Duration::from_secs(1));
+
+        // Halfway through the transition, the clip has reached its end
+        advance_time(&mut app, 0.5);
+        app.update();
+        let translation = app.world.get::<Transform>(entity).unwrap().translation;
+        assert!(translation.abs_diff_eq(Vec3::new(0.5, 0.5, 0.0), 1e-5));
+
+        advance_time(&mut app, 0.5);
+        app.update();
+        let translation = app.world.get::<Transform>(entity).unwrap().translation;
+        assert!(translation.abs_diff_eq(Vec3::Y, 1e-5));
+    }
+
+    #[test]
+    fn duplicate_timestamps() {
+        let curve = VariableCurve::new(
+            vec![0.0, 1.0, 1.0, 2.0],
+            Keyframes::Translation(vec![Vec3::ZERO, Vec3::X, Vec3::Y, Vec3::Y]),
+        );
+        let translation = curve.sample_translation(1.0).unwrap();
+        assert!(translation.is_finite());
+        assert!(translation == Vec3::X || translation == Vec3::Y);
+        assert_eq!(curve.sample_translation(1.5), Some(Vec3::Y));
+    }
+
+    #[test]
+    fn play_named() {
+        let mut app = setup_app();
+        let run = translation_clip(&mut app, "root");
+        let mut clips = AnimationClips::default();
+        clips.insert("run", run.clone());
+
+        let mut player = AnimationPlayer::default();
+        player.play_named(&clips, "run");
+        assert_eq!(player.animation.animation_clip, run);
+        player.play_named(&clips, "jump");
+        assert_eq!(player.animation.animation_clip, run);
+    }
+
+    #[test]
+    fn warm_cache() {
+        let mut app = setup_app();
+        let root = app.world.spawn(Name::new("root")).id();
+        let bone = app.world.spawn(Name::new("bone")).id();
+        app.world.entity_mut(root).push_children(&[bone]);
+        let path = EntityPath {
+            parts: vec![Name::new("root"), Name::new("bone")],
+        };
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(vec![0.0], Keyframes::Translation(vec![Vec3::X])),
+        );
+
+        let mut player = AnimationPlayer::default();
+        let mut state = SystemState::<(Query<&Children>, Query<&Name>)>::new(&mut app.world);
+        let (children, names) = state.get(&app.world);
+        player.warm_cache(root, &children, &names, &clip);
+        assert_eq!(player.path_cache.get(&path), Some(&bone));
+    }
+
+    #[test]
+    fn set_duration_min() {
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            VariableCurve::new(
+                vec![0.0, 1.0],
+                Keyframes::Translation(vec![Vec3::ZERO, Vec3::X]),
+            ),
+        );
+        clip.set_duration_min(1.5);
+        assert_eq!(clip.duration(), 1.5);
+        clip.set_duration_min(0.5);
+        assert_eq!(clip.duration(), 1.0);
+    }
+
+    #[test]
+    fn average_keyframe_rate() {
+        let mut clip = AnimationClip::default();
+        assert_eq!(clip.average_keyframe_rate(), 0.0);
+        let path = EntityPath {
+            parts: vec![Name::new("root")],
+        };
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(
+                vec![0.0, 0.5, 1.0],
+                Keyframes::Translation(vec![Vec3::ZERO, Vec3::X, Vec3::Y]),
+            ),
+        );
+        clip.add_curve_to_path(
+            path,
+            VariableCurve::new(vec![0.0, 1.0], Keyframes::Scale(vec![Vec3::ONE, Vec3::X])),
+        );
+        assert_eq!(clip.average_keyframe_rate(), 2.5);
+    }
+
+    #[test]
+    fn additive_from_reference_clip() {
+        let mut app = setup_app();
+        let pose = |app: &mut App, translation| {
+            let mut clip = AnimationClip::default();
+            clip.add_curve_to_path(
+                EntityPath {
+                    parts: vec![Name::new("root")],
+                },
+                VariableCurve::new(vec![0.0], Keyframes::Translation(vec![translation])),
+            );
+            app.world.resource_mut::<Assets<AnimationClip>>().add(clip)
+        };
+        let base = pose(&mut app, Vec3::Y);
+        let reference = pose(&mut app, Vec3::X);
+        let additive = pose(&mut app, Vec3::X * 2.0);
+        let entity = spawn_player(&mut app, "root", base);
+        app.world
+            .get_mut::<AnimationPlayer>(entity)
+            .unwrap()
+            .slot("overlay")
+            .play(additive)
+            .set_blend_mode(BlendMode::AdditiveFrom(reference))
+            .set_weight(0.5);
+
+        app.update();
+        let translation = app.world.get::<Transform>(entity).unwrap().translation;
+        assert_eq!(translation, Vec3::new(0.5, 1.0, 0.0));
+    }
+
+    #[test]
+    fn applied_last_frame() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let entity = spawn_player(&mut app, "root", handle);
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root"), Name::new("missing")],
+            },
+            VariableCurve::new(vec![0.0], Keyframes::Translation(vec![Vec3::X])),
+        );
+        let missing = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+        let inert = spawn_player(&mut app, "root", missing);
+
+        app.update();
+        let player = app.world.get::<AnimationPlayer>(entity).unwrap();
+        assert!(player.applied_last_frame());
+        let player = app.world.get::<AnimationPlayer>(inert).unwrap();
+        assert!(!player.applied_last_frame());
+    }
+
+    #[test]
+    fn loop_points() {
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            VariableCurve::new(
+                vec![0.0, 3.0],
+                Keyframes::Translation(vec![Vec3::ZERO, Vec3::X * 3.0]),
+            ),
+        );
+        clip.set_loop_points(Some((1.0, 2.0)));
+        let mut animation = PlayingAnimation::default();
+
+        animation.advance(Duration::from_secs_f32(0.5), &clip);
+        assert_eq!(animation.elapsed(), 0.5);
+        animation.advance(Duration::from_secs_f32(1.0), &clip);
+        assert_eq!(animation.elapsed(), 1.5);
+        animation.advance(Duration::from_secs_f32(1.0), &clip);
+        assert_eq!(animation.elapsed(), 1.5);
+
+        animation.play_outro();
+        animation.advance(Duration::from_secs_f32(1.0), &clip);
+        assert_eq!(animation.elapsed(), 2.5);
+        assert!(!animation.is_finished());
+        animation.advance(Duration::from_secs_f32(1.0), &clip);
+        assert!(animation.is_finished());
+    }
+
+    #[test]
+    fn degenerate_loop_points_are_ignored() {
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            VariableCurve::new(
+                vec![0.0, 1.0],
+                Keyframes::Translation(vec![Vec3::ZERO, Vec3::X]),
+            ),
+        );
+        clip.set_loop_points(Some((0.5, 0.5)));
+        assert_eq!(clip.loop_points(), None);
+        clip.set_loop_points(Some((0.75, 0.25)));
+        assert_eq!(clip.loop_points(), None);
+
+        let mut animation = PlayingAnimation::default();
+        animation.advance(Duration::from_secs_f32(0.75), &clip);
+        assert_eq!(animation.elapsed(), 0.75);
+        let mut poses = Vec::new();
+        clip.sample_into(animation.elapsed(), &mut poses);
+        assert_eq!(poses.len(), 1);
+
+        // Loop points set without validation are ignored during playback too
+        clip.loop_points = Some((0.5, 0.5));
+        let mut animation = PlayingAnimation::default();
+        animation.advance(Duration::from_secs_f32(0.75), &clip);
+        assert_eq!(animation.elapsed(), 0.75);
+    }
+
+    #[test]
+    fn shift_time() {
+        let mut clip = AnimationClip::default();
+        let path = EntityPath {
+            parts: vec![Name::new("root")],
+        };
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(
+                vec![0.0, 1.0],
+                Keyframes::Translation(vec![Vec3::ZERO, Vec3::X]),
+            ),
+        );
+        clip.shift_time(1.0);
+        assert_eq!(clip.duration(), 2.0);
+        let curve = &clip.curves()[&path][0];
+        assert_eq!(curve.sample_translation(0.5), None);
+        assert_eq!(
+            curve.sample_translation(1.5),
+            Some(Vec3::new(0.5, 0.0, 0.0))
+        );
+
+        // Loop points starting before the first keyframe are clamped to the start of the clip
+        clip.set_loop_points(Some((0.25, 1.5)));
+        clip.shift_time(-2.0);
+        assert_eq!(clip.duration(), 1.0);
+        assert_eq!(clip.curves()[&path][0].keyframe_timestamps, vec![0.0, 1.0]);
+        assert_eq!(clip.loop_points(), Some((0.0, 0.5)));
+
+        // And removed if the whole loop is moved before it
+        clip.shift_time(1.0);
+        clip.set_loop_points(Some((0.25, 0.75)));
+        clip.shift_time(-1.0);
+        assert_eq!(clip.loop_points(), None);
+    }
+
+    #[test]
+    fn static_pose_does_not_change_transform() {
+        let mut app = setup_app();
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            VariableCurve::new(vec![0.0], Keyframes::Translation(vec![Vec3::X])),
+        );
+        let handle = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+        let entity = spawn_player(&mut app, "root", handle);
+        app.update();
+        assert_eq!(
+            app.world.get::<Transform>(entity).unwrap().translation,
+            Vec3::X
+        );
+
+        let last_change_tick = app.world.read_change_tick();
+        advance_time(&mut app, 0.1);
+        app.update();
+        let ticks = app.world.entity(entity).get_change_ticks::<Transform>();
+        assert!(!ticks
+            .unwrap()
+            .is_changed(last_change_tick, app.world.read_change_tick()));
+    }
+
+    #[test]
+    fn bone_and_curve_count() {
+        let mut clip = AnimationClip::default();
+        for name in ["root", "bone"] {
+            clip.add_curve_to_path(
+                EntityPath {
+                    parts: vec![Name::new(name)],
+                },
+                VariableCurve::new(vec![0.0], Keyframes::Translation(vec![Vec3::X])),
+            );
+        }
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            VariableCurve::new(vec![0.0], Keyframes::Scale(vec![Vec3::ONE])),
+        );
+        assert_eq!(clip.bone_count(), 2);
+        assert_eq!(clip.curve_count(), 3);
+    }
+
+    #[test]
+    fn interpolate_loop() {
+        let mut app = setup_app();
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            VariableCurve::new(
+                vec![0.5, 1.5],
+                Keyframes::Translation(vec![Vec3::ZERO, Vec3::X]),
+            ),
+        );
+        clip.set_duration_min(2.0);
+        clip.set_interpolate_loop(true);
+        let handle = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+        let entity = spawn_player(&mut app, "root", handle);
+        app.world
+            .get_mut::<AnimationPlayer>(entity)
+            .unwrap()
+            .repeat();
+
+        // Halfway from the last keyframe to the first one, across the end of the clip
+        advance_time(&mut app, 2.0);
+        app.update();
+        let translation = app.world.get::<Transform>(entity).unwrap().translation;
+        assert_eq!(translation, Vec3::new(0.5, 0.0, 0.0));
+
+        advance_time(&mut app, 0.25);
+        app.update();
+        let translation = app.world.get::<Transform>(entity).unwrap().translation;
+        assert_eq!(translation, Vec3::new(0.25, 0.0, 0.0));
+    }
+
+    #[test]
+    fn active_animations() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let playing = spawn_player(&mut app, "root", handle.clone());
+        let disabled = spawn_player(&mut app, "root", handle.clone());
+        app.world
+            .get_mut::<AnimationPlayer>(disabled)
+            .unwrap()
+            .set_enabled(false);
+        app.world.spawn(AnimationPlayer::default());
+
+        advance_time(&mut app, 0.5);
+        app.update();
+        let active: Vec<_> = app.world.resource::<ActiveAnimations>().iter().collect();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].entity, playing);
+        assert_eq!(active[0].animation_clip, handle);
+        assert_eq!(active[0].elapsed, 0.5);
+        assert!(!active[0].finished);
+    }
+
+    #[test]
+    fn preserve_rotation_winding() {
+        let mut app = setup_app();
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            VariableCurve::new(
+                vec![0.0, 1.0],
+                Keyframes::Rotation(vec![
+                    Quat::IDENTITY,
+                    Quat::from_rotation_z(1.5 * std::f32::consts::PI),
+                ]),
+            ),
+        );
+        clip.set_preserve_rotation_winding(true);
+        let handle = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+        let entity = spawn_player(&mut app, "root", handle);
+
+        advance_time(&mut app, 0.5);
+        app.update();
+        let rotation = app.world.get::<Transform>(entity).unwrap().rotation;
+        let expected = Quat::from_rotation_z(0.75 * std::f32::consts::PI);
+        assert!(rotation.abs_diff_eq(expected, 1e-5) || rotation.abs_diff_eq(-expected, 1e-5));
+    }
+
+    #[test]
+    fn crossfade() {
+        let mut app = setup_app();
+        let a = translation_clip(&mut app, "root");
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            VariableCurve::new(vec![0.0], Keyframes::Translation(vec![Vec3::Y])),
+        );
+        let b = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+        let entity = spawn_player(&mut app, "root", a.clone());
+
+        advance_time(&mut app, 0.5);
+        app.world
+            .get_mut::<AnimationPlayer>(entity)
+            .unwrap()
+            .set_crossfade(a, b, 0.25);
+        app.update();
+        let translation = app.world.get::<Transform>(entity).unwrap().translation;
+        assert!(translation.abs_diff_eq(Vec3::new(0.375, 0.25, 0.0), 1e-5));
+    }
+
+    #[test]
+    fn custom_clock() {
+        #[derive(Resource)]
+        struct FixedClock;
+
+        impl DeltaSeconds for FixedClock {
+            fn delta_seconds(&self) -> f32 {
+                0.25
+            }
+        }
+
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_plugin(AnimationPlugin::with_clock::<FixedClock>())
+            .insert_resource(FixedClock);
+        let handle = translation_clip(&mut app, "root");
+        let entity = spawn_player(&mut app, "root", handle);
+
+        app.update();
+        app.update();
+        let translation = app.world.get::<Transform>(entity).unwrap().translation;
+        assert_eq!(translation, Vec3::new(0.5, 0.0, 0.0));
+    }
+
+    #[test]
+    fn post_process_after_apply() {
+        fn raise(mut transforms: Query<&mut Transform, With<AnimationPlayer>>) {
+            for mut transform in &mut transforms {
+                transform.translation.y += 1.0;
+            }
+        }
+
+        let mut app = setup_app();
+        app.add_system_to_stage(CoreStage::PostUpdate, raise.after(AnimationSystem::Apply));
+        let handle = translation_clip(&mut app, "root");
+        let entity = spawn_player(&mut app, "root", handle);
+
+        advance_time(&mut app, 0.5);
+        app.update();
+        let translation = app.world.get::<Transform>(entity).unwrap().translation;
+        assert_eq!(translation, Vec3::new(0.5, 1.0, 0.0));
+    }
+
+    #[test]
+    fn forever_elapsed_is_bounded() {
+        let clip = {
+            let mut clip = AnimationClip::default();
+            clip.add_curve_to_path(
+                EntityPath {
+                    parts: vec![Name::new("root")],
+                },
+                VariableCurve::new(
+                    vec![0.0, 1.0],
+                    Keyframes::Translation(vec![Vec3::ZERO, Vec3::X]),
+                ),
+            );
+            clip
+        };
+        let mut animation = PlayingAnimation::default();
+        animation.repeat().set_speed(100.0);
+
+        // An hour at 60 frames per second
+        for _ in 0..60 * 60 * 60 {
+            animation.advance(Duration::from_secs_f32(1.0 / 60.0), &clip);
+            assert!((0.0..1.0).contains(&animation.elapsed()));
+        }
+        assert!((animation.completions() as f32 - 360_000.0).abs() <= 1.0);
+    }
+
+    #[test]
+    fn curve_weight() {
+        let mut app = setup_app();
+        let mut clip = AnimationClip::default();
+        let mut curve = VariableCurve::new(
+            vec![0.0, 1.0],
+            Keyframes::Translation(vec![Vec3::ZERO, Vec3::X]),
+        );
+        curve.weight = 0.5;
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            curve,
+        );
+        let handle = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+        let entity = spawn_player(&mut app, "root", handle);
+        app.world.get_mut::<Transform>(entity).unwrap().translation = Vec3::Y;
+
+        advance_time(&mut app, 1.0);
+        app.update();
+        let translation = app.world.get::<Transform>(entity).unwrap().translation;
+        assert_eq!(translation, Vec3::new(0.5, 0.5, 0.0));
+    }
+
+    #[test]
+    fn types_are_registered() {
+        let app = setup_app();
+        let registry = app.world.resource::<AppTypeRegistry>().0.read();
+        for type_id in [
+            TypeId::of::<AnimationClip>(),
+            TypeId::of::<VariableCurve>(),
+            TypeId::of::<Keyframes>(),
+            TypeId::of::<EntityPath>(),
+            TypeId::of::<TimeWarp>(),
+            TypeId::of::<AnimationClips>(),
+            TypeId::of::<ActiveAnimations>(),
+        ] {
+            assert!(registry.get(type_id).is_some());
+        }
+    }
+
+    #[test]
+    fn external_driver_loops() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let root = spawn_player(&mut app, "root", handle);
+        app.world
+            .get_mut::<AnimationPlayer>(root)
+            .unwrap()
+            .repeat()
+            .set_driver(AnimationDriver::External(2.25));
+        app.update();
+        let player = app.world.get::<AnimationPlayer>(root).unwrap();
+        assert_eq!(player.elapsed(), 0.25);
+        assert_eq!(player.completions(), 2);
+        let transform = app.world.get::<Transform>(root).unwrap();
+        assert_eq!(transform.translation, Vec3::new(0.25, 0.0, 0.0));
+
+        // Jumping back in time
+        app.world
+            .get_mut::<AnimationPlayer>(root)
+            .unwrap()
+            .set_driver(AnimationDriver::External(0.5));
+        app.update();
+        let player = app.world.get::<AnimationPlayer>(root).unwrap();
+        assert_eq!(player.elapsed(), 0.5);
+        assert_eq!(player.completions(), 0);
+    }
+
+    #[test]
+    fn validate() {
+        let mut clip = AnimationClip::default();
+        let path = EntityPath {
+            parts: vec![Name::new("root")],
+        };
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(
+                vec![0.0, 1.0],
+                Keyframes::Translation(vec![Vec3::ZERO, Vec3::X]),
+            ),
+        );
+        assert_eq!(clip.validate(), vec![]);
+
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(
+                vec![0.0, 0.0, 1.0],
+                Keyframes::Scale(vec![Vec3::ONE, Vec3::NAN]),
+            ),
+        );
+        assert_eq!(
+            clip.validate(),
+            vec![
+                AnimationClipError::MismatchedLengths {
+                    path: path.clone(),
+                    curve: 1,
+                    timestamps: 3,
+                    keyframes: 2,
+                },
+                AnimationClipError::ZeroLengthSegment {
+                    path: path.clone(),
+                    curve: 1,
+                    time: 0.0,
+                },
+                AnimationClipError::NonFiniteValue { path, curve: 1 },
+            ]
+        );
+    }
+
+    #[test]
+    fn eased_transition() {
+        let mut app = setup_app();
+        let pose = |app: &mut App, translation| {
+            let mut clip = AnimationClip::default();
+            clip.add_curve_to_path(
+                EntityPath {
+                    parts: vec![Name::new("root")],
+                },
+                VariableCurve::new(vec![0.0], Keyframes::Translation(vec![translation])),
+            );
+            app.world.resource_mut::<Assets<AnimationClip>>().add(clip)
+        };
+        let from = pose(&mut app, Vec3::X);
+        let to = pose(&mut app, Vec3::ZERO);
+        let entity = spawn_player(&mut app, "root", from);
+        app.update();
+        let mut player = app.world.get_mut::<AnimationPlayer>(entity).unwrap();
+        player
+            .set_finished_behavior(FinishedBehavior::HoldPose)
+            .play_with_transition_eased(to, Duration::from_secs(1), Easing::EaseInOut);
+        assert_eq!(player.finished_behavior(), FinishedBehavior::HoldPose);
+
+        advance_time(&mut app, 0.25);
+        app.update();
+        let translation = app.world.get::<Transform>(entity).unwrap().translation;
+        assert!(translation.abs_diff_eq(Vec3::new(0.84375, 0.0, 0.0), 1e-5));
+
+        advance_time(&mut app, 0.75);
+        app.update();
+        let translation = app.world.get::<Transform>(entity).unwrap().translation;
+        assert_eq!(translation, Vec3::ZERO);
+    }
+
+    #[test]
+    fn pose_delta() {
+        let mut clip = AnimationClip::default();
+        let path = EntityPath {
+            parts: vec![Name::new("root")],
+        };
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(
+                vec![0.0, 1.0],
+                Keyframes::Translation(vec![Vec3::ZERO, Vec3::X]),
+            ),
+        );
+        for t0 in [0.0, 0.25, 0.5] {
+            let delta = clip.pose_delta(&path, t0, t0 + 0.5).unwrap();
+            assert!(delta
+                .translation
+                .abs_diff_eq(Vec3::new(0.5, 0.0, 0.0), 1e-6));
+            assert_eq!(delta.rotation, Quat::IDENTITY);
+        }
+        let other = EntityPath {
+            parts: vec![Name::new("other")],
+        };
+        assert_eq!(clip.pose_delta(&other, 0.0, 1.0), None);
+    }
+
+    #[test]
+    fn update_interval() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let entity = spawn_player(&mut app, "root", handle);
+        app.world
+            .get_mut::<AnimationPlayer>(entity)
+            .unwrap()
+            .repeat()
+            .set_update_interval(Duration::from_millis(100));
+
+        let mut updates = 0;
+        for _ in 0..60 {
+            advance_time(&mut app, 1.0 / 60.0);
+            app.update();
+            if app
+                .world
+                .get::<AnimationPlayer>(entity)
+                .unwrap()
+                .applied_last_frame()
+            {
+                updates += 1;
+            }
+        }
+        assert!((9..=11).contains(&updates));
+        let player = app.world.get::<AnimationPlayer>(entity).unwrap();
+        assert!((player.elapsed() - 1.0).abs() < 1e-3 || player.elapsed() < 1e-3);
+    }
+
+    #[test]
+    fn euler_curves() {
+        let curve = VariableCurve::from_euler_curves(
+            EulerRot::XYZ,
+            [
+                (&[0.0, 1.0], &[0.0, std::f32::consts::PI]),
+                (&[0.5], &[0.0]),
+                (&[], &[]),
+            ],
+        );
+        assert_eq!(curve.keyframe_timestamps, vec![0.0, 0.5, 1.0]);
+        let rotation = curve.sample_rotation(0.25).unwrap();
+        let expected = Quat::from_rotation_x(std::f32::consts::FRAC_PI_4);
+        assert!(rotation.abs_diff_eq(expected, 1e-5));
+
+        // NaN timestamps are skipped instead of panicking while sorting
+        let curve = VariableCurve::from_euler_curves(
+            EulerRot::XYZ,
+            [(&[1.0, 0.0], &[0.0, 0.0]), (&[], &[]), (&[f32::NAN], &[])],
+        );
+        assert_eq!(curve.keyframe_timestamps, vec![0.0, 1.0]);
+    }
+
+    #[test]
+    fn clone_player() {
+        let mut player = AnimationPlayer::default();
+        player
+            .play(Handle::weak(HandleId::random::<AnimationClip>()))
+            .set_speed(2.0);
+        player.play_with_transition(
+            Handle::weak(HandleId::random::<AnimationClip>()),
+            Duration::from_secs(1),
+        );
+        player.path_cache.insert(
+            EntityPath {
+                parts: vec![Name::new("bone")],
+            },
+            Entity::from_raw(0),
+        );
+        let clone = player.clone();
+        assert_eq!(clone.transitions.len(), 1);
+        assert_eq!(clone.transitions[0].animation.speed, 2.0);
+        assert_eq!(
+            clone.animation.animation_clip,
+            player.animation.animation_clip
+        );
+        assert!(clone.path_cache.is_empty());
+    }
+
+    #[test]
+    fn clamp_curve_edges() {
+        let mut curve = VariableCurve::new(
+            vec![1.0, 2.0],
+            Keyframes::Translation(vec![Vec3::X, Vec3::Y]),
+        );
+        assert_eq!(curve.sample_translation(0.5), None);
+        curve.edge_behavior = EdgeBehavior::Clamp;
+        assert_eq!(curve.sample_translation(0.5), Some(Vec3::X));
+        assert_eq!(curve.sample_translation(3.0), Some(Vec3::Y));
+
+        let mut app = setup_app();
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            curve,
+        );
+        let handle = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+        let entity = spawn_player(&mut app, "root", handle);
+        app.update();
+        let transform = app.world.get::<Transform>(entity).unwrap();
+        assert_eq!(transform.translation, Vec3::X);
+    }
+
+    #[test]
+    fn active_animation_weights() {
+        let mut app = setup_app();
+        let main = translation_clip(&mut app, "root");
+        let slot = translation_clip(&mut app, "root");
+        let additive = translation_clip(&mut app, "root");
+        let mut player = AnimationPlayer::default();
+        player.play(main);
+        player.animation.set_weight(0.5);
+        player.slot("upper").play(slot).set_weight(0.5);
+        player
+            .slot("breathing")
+            .play(additive)
+            .set_weight(0.5)
+            .set_blend_mode(BlendMode::Additive {
+                reference_time: 0.0,
+            });
+        app.world
+            .spawn((Name::new("root"), Transform::default(), player));
+        app.update();
+
+        let weights: Vec<f32> = app
+            .world
+            .resource::<ActiveAnimations>()
+            .iter()
+            .map(|animation| animation.weight)
+            .collect();
+        assert_eq!(weights, vec![0.25, 0.5, 0.5]);
+    }
+
+    #[test]
+    fn quantized_keyframes() {
+        let mut clip = AnimationClip::default();
+        let path = EntityPath {
+            parts: vec![Name::new("root")],
+        };
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(
+                vec![0.0, 1.0],
+                Keyframes::Rotation(vec![Quat::IDENTITY, Quat::from_rotation_y(1.0)]),
+            ),
+        );
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(
+                vec![0.0, 1.0],
+                Keyframes::Translation(vec![Vec3::new(-2.0, 0.0, 1.0), Vec3::new(8.0, 0.0, 3.0)]),
+            ),
+        );
+        let quantized = clip.quantize();
+        let curves = quantized.curves.get(&path).unwrap();
+        assert!(matches!(
+            curves[0].keyframes,
+            Keyframes::QuantizedRotation(_)
+        ));
+        assert!(matches!(
+            curves[1].keyframes,
+            Keyframes::QuantizedTranslation(_)
+        ));
+        assert!(quantized.validate().is_empty());
+
+        let original = clip.curves.get(&path).unwrap();
+        for time in [0.0, 0.3, 1.0] {
+            let rotation = curves[0].sample_rotation(time).unwrap();
+            assert!(rotation.abs_diff_eq(original[0].sample_rotation(time).unwrap(), 1e-4));
+            let translation = curves[1].sample_translation(time).unwrap();
+            assert!(translation.abs_diff_eq(original[1].sample_translation(time).unwrap(), 1e-3));
+        }
+        assert!(curves[1].sample_rotation(0.5).is_none());
+    }
+
+    #[test]
+    fn root_offset() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let player = spawn_player(&mut app, "scene", handle);
+        let wrapper = app.world.spawn(Transform::default()).id();
+        let root = app
+            .world
+            .spawn((Name::new("root"), Transform::default()))
+            .id();
+        app.world.entity_mut(wrapper).push_children(&[root]);
+        app.world.entity_mut(player).push_children(&[wrapper]);
+        app.world
+            .get_mut::<AnimationPlayer>(player)
+            .unwrap()
+            .set_root_offset(EntityPath {
+                parts: vec![Name::new("root")],
+            });
+
+        advance_time(&mut app, 0.5);
+        app.update();
+        let translation = app.world.get::<Transform>(root).unwrap().translation;
+        assert_eq!(translation, Vec3::new(0.5, 0.0, 0.0));
+        let translation = app.world.get::<Transform>(player).unwrap().translation;
+        assert_eq!(translation, Vec3::ZERO);
+
+        // A missing root is warned about once until the root offset changes
+        let missing = EntityPath {
+            parts: vec![Name::new("missing")],
+        };
+        app.world
+            .get_mut::<AnimationPlayer>(player)
+            .unwrap()
+            .set_root_offset(missing.clone());
+        app.update();
+        assert!(
+            app.world
+                .get::<AnimationPlayer>(player)
+                .unwrap()
+                .root_warned
+        );
+        app.world
+            .get_mut::<AnimationPlayer>(player)
+            .unwrap()
+            .set_root_offset(missing);
+        assert!(
+            !app.world
+                .get::<AnimationPlayer>(player)
+                .unwrap()
+                .root_warned
+        );
+    }
+
+    #[test]
+    fn keyframe_navigation() {
+        let mut clip = AnimationClip::default();
+        let path = EntityPath {
+            parts: vec![Name::new("root")],
+        };
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(
+                vec![0.0, 1.0, 2.0],
+                Keyframes::Translation(vec![Vec3::ZERO; 3]),
+            ),
+        );
+        clip.add_curve_to_path(
+            path,
+            VariableCurve::new(vec![0.5, 1.5], Keyframes::Scale(vec![Vec3::ONE; 2])),
+        );
+        assert_eq!(clip.next_keyframe_time(0.0), Some(0.5));
+        assert_eq!(clip.next_keyframe_time(0.5), Some(1.0));
+        assert_eq!(clip.next_keyframe_time(2.0), None);
+        assert_eq!(clip.prev_keyframe_time(1.5), Some(1.0));
+        assert_eq!(clip.prev_keyframe_time(1.2), Some(1.0));
+        assert_eq!(clip.prev_keyframe_time(0.0), None);
+    }
+
+    #[test]
+    fn delta_from_rest() {
+        let mut app = setup_app();
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            VariableCurve::new(vec![0.0], Keyframes::Translation(vec![Vec3::X])),
+        );
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            VariableCurve::new(
+                vec![0.0],
+                Keyframes::Rotation(vec![Quat::from_rotation_x(0.5)]),
+            ),
+        );
+        clip.set_space(CurveSpace::DeltaFromRest);
+        let handle = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+        let entity = spawn_player(&mut app, "root", handle);
+        let rest = Transform::from_xyz(0.0, 2.0, 0.0).with_rotation(Quat::from_rotation_y(1.0));
+        app.world.entity_mut(entity).insert(RestPose(rest));
+
+        app.update();
+        let transform = app.world.get::<Transform>(entity).unwrap();
+        assert_eq!(transform.translation, Vec3::new(1.0, 2.0, 0.0));
+        let expected = Quat::from_rotation_y(1.0) * Quat::from_rotation_x(0.5);
+        assert!(transform.rotation.abs_diff_eq(expected, 1e-6));
+    }
+
+    #[test]
+    fn trim_static_tail() {
+        let mut clip = AnimationClip::default();
+        let path = EntityPath {
+            parts: vec![Name::new("root")],
+        };
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(
+                vec![0.0, 1.0, 1.5, 2.0],
+                Keyframes::Translation(vec![Vec3::ZERO, Vec3::X, Vec3::X, Vec3::X]),
+            ),
+        );
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(
+                vec![0.0, 0.5, 2.0],
+                Keyframes::Rotation(vec![
+                    Quat::IDENTITY,
+                    Quat::from_rotation_y(1.0),
+                    -Quat::from_rotation_y(1.0),
+                ]),
+            ),
+        );
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(vec![2.0], Keyframes::Scale(vec![Vec3::ONE])),
+        );
+        clip.trim_static_tail(1e-4);
+        assert_eq!(clip.duration, 1.0);
+        let curves = clip.curves.get(&path).unwrap();
+        assert_eq!(curves[0].keyframe_timestamps, vec![0.0, 1.0]);
+        assert_eq!(curves[1].keyframe_timestamps, vec![0.0, 0.5]);
+        assert_eq!(curves[2].keyframe_timestamps, vec![2.0]);
+    }
+
+    #[test]
+    fn animation_target() {
+        let mut app = setup_app();
+        let mut clip = AnimationClip::default();
+        let path = EntityPath {
+            parts: vec![Name::new("root"), Name::new("bone")],
+        };
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(vec![0.0], Keyframes::Translation(vec![Vec3::X])),
+        );
+        let handle = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+        let root = spawn_player(&mut app, "root", handle);
+        let first = app
+            .world
+            .spawn((Name::new("bone"), Transform::default()))
+            .id();
+        let second = app
+            .world
+            .spawn((
+                Name::new("bone"),
+                Transform::default(),
+                AnimationTarget { root, path },
+            ))
+            .id();
+        app.world.entity_mut(root).push_children(&[first, second]);
+
+        for _ in 0..2 {
+            app.update();
+            assert_eq!(
+                app.world.get::<Transform>(first).unwrap().translation,
+                Vec3::ZERO
+            );
+            assert_eq!(
+                app.world.get::<Transform>(second).unwrap().translation,
+                Vec3::X
+            );
+
+            // Clearing the cache doesn't lose the target
+            app.world
+                .get_mut::<AnimationPlayer>(root)
+                .unwrap()
+                .set_root_offset(EntityPath::default());
+            app.world.get_mut::<Transform>(second).unwrap().translation = Vec3::ZERO;
+        }
+    }
+
+    #[test]
+    fn mask_from_chain() {
+        let chain: Vec<EntityPath> = ["hips", "spine", "spine1", "spine2", "chest"]
+            .iter()
+            .scan(Vec::new(), |parts, name| {
+                parts.push(Name::new(name.to_string()));
+                Some(EntityPath {
+                    parts: parts.clone(),
+                })
+            })
+            .collect();
+        let mask = AnimationMask::from_chain(&chain, |t| Easing::EaseInOut.ease(t));
+        let weights: Vec<f32> = chain.iter().map(|path| mask.weight(path)).collect();
+        assert_eq!(weights[0], 0.0);
+        assert_eq!(weights[4], 1.0);
+        assert!(weights.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(
+            mask.weight(&EntityPath {
+                parts: vec![Name::new("head")]
+            }),
+            0.0
+        );
+    }
+
+    #[test]
+    fn masked_animation() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let entity = spawn_player(&mut app, "root", handle);
+        let mut mask = AnimationMask::default();
+        mask.set_weight(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            0.5,
+        );
+        app.world
+            .get_mut::<AnimationPlayer>(entity)
+            .unwrap()
+            .animation
+            .set_mask(Some(mask));
+
+        advance_time(&mut app, 1.0);
+        app.update();
+        let translation = app.world.get::<Transform>(entity).unwrap().translation;
+        assert_eq!(translation, Vec3::new(0.5, 0.0, 0.0));
+    }
+
+    #[test]
+    fn is_transitioning() {
+        let mut app = setup_app();
+        let first = translation_clip(&mut app, "root");
+        let second = translation_clip(&mut app, "root");
+        let entity = spawn_player(&mut app, "root", first);
+        app.world
+            .get_mut::<AnimationPlayer>(entity)
+            .unwrap()
+            .play_with_transition(second, Duration::from_secs_f32(0.5));
+        assert!(app
+            .world
+            .get::<AnimationPlayer>(entity)
+            .unwrap()
+            .is_transitioning());
+
+        advance_time(&mut app, 0.6);
+        app.update();
+        assert!(!app
+            .world
+            .get::<AnimationPlayer>(entity)
+            .unwrap()
+            .is_transitioning());
+    }
+
+    #[test]
+    fn multiplicative_scale() {
+        let mut app = setup_app();
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            VariableCurve::new(vec![0.0], Keyframes::Scale(vec![Vec3::splat(2.0)])),
+        );
+        let squash = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+        let base = translation_clip(&mut app, "root");
+        let entity = spawn_player(&mut app, "root", base);
+        let mut player = app.world.get_mut::<AnimationPlayer>(entity).unwrap();
+        for slot in ["first", "second"] {
+            player
+                .slot(slot)
+                .play(squash.clone())
+                .set_scale_blend(ScaleBlend::Multiplicative);
+        }
+
+        app.update();
+        let scale = app.world.get::<Transform>(entity).unwrap().scale;
+        assert_eq!(scale, Vec3::splat(4.0));
+    }
+
+    #[test]
+    fn keyframe_at_index() {
+        let curve = VariableCurve::new(
+            vec![0.0, 1.0],
+            Keyframes::Translation(vec![Vec3::ZERO, Vec3::X]),
+        );
+        match curve.keyframe(1) {
+            Some((1.0, Keyframes::Translation(keyframes))) => assert_eq!(keyframes, vec![Vec3::X]),
+            keyframe => panic!("unexpected keyframe {:?}", keyframe),
+        }
+        assert!(curve.keyframe(2).is_none());
+
+        let quantized = VariableCurve::new(vec![0.0, 1.0], curve.keyframes.quantize());
+        match quantized.keyframe(1) {
+            Some((1.0, Keyframes::QuantizedTranslation(keyframes))) => {
+                assert!(keyframes.get(0).unwrap().abs_diff_eq(Vec3::X, 1e-4));
+            }
+            keyframe => panic!("unexpected keyframe {:?}", keyframe),
+        }
+    }
+
+    #[test]
+    fn resolved_target() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let entity = spawn_player(&mut app, "root", handle);
+        let path = EntityPath {
+            parts: vec![Name::new("root")],
+        };
+        let player = app.world.get::<AnimationPlayer>(entity).unwrap();
+        assert_eq!(player.resolved_target(&path), None);
+
+        app.update();
+        let player = app.world.get::<AnimationPlayer>(entity).unwrap();
+        assert_eq!(player.resolved_target(&path), Some(entity));
+    }
+
+    #[test]
+    fn strict_mode() {
+        let mut app = setup_app();
+        app.init_resource::<AnimationStrictMode>();
+        let path = EntityPath {
+            parts: vec![Name::new("root"), Name::new("missing")],
+        };
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(vec![0.0], Keyframes::Translation(vec![Vec3::X])),
+        );
+        let handle = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+        let entity = spawn_player(&mut app, "root", handle);
+        let mut player = app.world.get_mut::<AnimationPlayer>(entity).unwrap();
+        let unloaded = Handle::weak(HandleId::random::<AnimationClip>());
+        player.slot("unloaded").play(unloaded.clone());
+
+        app.update();
+        let errors = app.world.resource::<AnimationStrictMode>().errors();
+        assert_eq!(
+            errors,
+            &[
+                AnimationStrictError::PathNotFound {
+                    player: entity,
+                    path,
+                },
+                AnimationStrictError::ClipNotLoaded {
+                    player: entity,
+                    clip: unloaded,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn fingerprint() {
+        let root = EntityPath {
+            parts: vec![Name::new("root")],
+        };
+        let bone = EntityPath {
+            parts: vec![Name::new("root"), Name::new("bone")],
+        };
+        let translation = VariableCurve::new(
+            vec![0.0, 1.0],
+            Keyframes::Translation(vec![Vec3::ZERO, Vec3::X]),
+        );
+        let rotation = VariableCurve::new(vec![0.0], Keyframes::Rotation(vec![Quat::IDENTITY]));
+
+        let mut a = AnimationClip::default();
+        a.add_curve_to_path(root.clone(), translation.clone());
+        a.add_curve_to_path(bone.clone(), rotation.clone());
+        let mut b = AnimationClip::default();
+        b.add_curve_to_path(bone, rotation);
+        b.add_curve_to_path(root.clone(), translation.clone());
+        assert_eq!(a.fingerprint(), b.fingerprint());
+
+        let mut changed = translation.clone();
+        changed.keyframes = Keyframes::Translation(vec![Vec3::ZERO, Vec3::Y]);
+        b.add_curve_to_path(root, changed);
+        assert_ne!(a.fingerprint(), b.fingerprint());
+
+        let curve_fingerprint = |curve: &VariableCurve| {
+            let mut clip = AnimationClip::default();
+            clip.add_curve_to_path(
+                EntityPath {
+                    parts: vec![Name::new("root")],
+                },
+                curve.clone(),
+            );
+            clip.fingerprint()
+        };
+        let mut pivoted = translation.clone();
+        pivoted.pivot = Some(Vec3::ZERO);
+        let mut disabled = translation.clone();
+        disabled.enabled = false;
+        let mut clamped = translation.clone();
+        clamped.clamp_scalar = true;
+        let fingerprints = [&translation, &pivoted, &disabled, &clamped].map(curve_fingerprint);
+        for (i, a) in fingerprints.iter().enumerate() {
+            assert!(fingerprints[i + 1..].iter().all(|b| a != b));
+        }
+
+        let mut repeating = a.clone();
+        repeating.set_default_repeat(Some(RepeatAnimation::Never));
+        assert_ne!(repeating.fingerprint(), a.fingerprint());
+    }
+
+    #[test]
+    fn curve_interpolation() {
+        let keyframes = Keyframes::Translation(vec![Vec3::ZERO, Vec3::ZERO, Vec3::X, Vec3::X]);
+        let curve = |interpolation| {
+            VariableCurve::new(vec![0.0, 1.0, 2.0, 3.0], keyframes.clone())
+                .with_interpolation(interpolation)
+        };
+        let linear = curve(Interpolation::Linear);
+        let step = curve(Interpolation::Step);
+        let cubic = curve(Interpolation::Cubic);
+        assert_eq!(linear.sample_translation(1.25), Some(Vec3::X * 0.25));
+        assert_eq!(step.sample_translation(1.25), Some(Vec3::ZERO));
+        assert_eq!(step.sample_translation(3.0), Some(Vec3::X));
+        assert_eq!(cubic.sample_translation(1.25), Some(Vec3::X * 0.203125));
+        assert_eq!(cubic.sample_translation(2.0), Some(Vec3::X));
+
+        let rotation = VariableCurve::new(
+            vec![0.0, 1.0, 2.0, 3.0],
+            Keyframes::Rotation(vec![
+                Quat::IDENTITY,
+                Quat::from_rotation_y(1.0),
+                -Quat::from_rotation_y(2.0),
+                Quat::from_rotation_y(3.0),
+            ]),
+        )
+        .with_interpolation(Interpolation::Cubic);
+        let sampled = rotation.sample_rotation(1.5).unwrap();
+        assert!(sampled.abs_diff_eq(Quat::from_rotation_y(1.5), 1e-2));
+    }
+
+    #[derive(Component)]
+    struct Health(f32);
+
+    #[test]
+    fn registered_component_is_animated() {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_plugin(AnimationPlugin::default().register_animated::<Health>(
+                |health, curve, time, weight| {
+                    if let Some(value) = curve.sample_scalar(time) {
+                        health.0 += (value - health.0) * weight;
+                    }
+                },
+            ));
+        let mut time = Time::default();
+        time.update();
+        app.insert_resource(time);
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            VariableCurve::new(vec![0.0, 1.0], Keyframes::Scalar(vec![0.0, 100.0])),
+        );
+        let handle = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+        let entity = spawn_player(&mut app, "root", handle);
+        app.world.entity_mut(entity).insert(Health(0.0));
+
+        advance_time(&mut app, 0.5);
+        app.update();
+        let health = app.world.get::<Health>(entity).unwrap().0;
+        assert!((health - 50.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn slice_rebases_take() {
+        let path = EntityPath {
+            parts: vec![Name::new("root")],
+        };
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(
+                vec![0.0, 1.0, 2.0, 3.0],
+                Keyframes::Translation(vec![Vec3::ZERO, Vec3::X, Vec3::Y, Vec3::Z]),
+            ),
+        );
+
+        let first = clip.slice(0.0, 1.0);
+        assert_eq!(first.duration(), 1.0);
+        let curve = &first.curves.get(&path).unwrap()[0];
+        assert_eq!(curve.keyframe_timestamps, vec![0.0, 1.0]);
+        assert_eq!(curve.sample_translation(1.0), Some(Vec3::X));
+
+        let second = clip.slice(1.5, 3.0);
+        assert_eq!(second.duration(), 1.5);
+        let curve = &second.curves.get(&path).unwrap()[0];
+        assert_eq!(curve.keyframe_timestamps, vec![0.0, 0.5, 1.5]);
+        assert_eq!(
+            curve.sample_translation(0.0),
+            Some(Vec3::new(0.5, 0.5, 0.0))
+        );
+        assert_eq!(curve.sample_translation(0.5), Some(Vec3::Y));
+        assert_eq!(curve.sample_translation(1.5), Some(Vec3::Z));
+    }
+
+    #[test]
+    fn elapsed_wrapped_stays_within_clip() {
+        let mut clip = AnimationClip::default();
+        clip.set_duration_min(2.0);
+        let mut player = AnimationPlayer::default();
+        player.play(Handle::default()).repeat();
+        player.set_elapsed(5.0);
+        assert_eq!(player.elapsed(), 5.0);
+        assert_eq!(player.elapsed_wrapped(&clip), 1.0);
+
+        player.set_elapsed(-0.5);
+        assert_eq!(player.elapsed_wrapped(&clip), 1.5);
+
+        assert_eq!(player.elapsed_wrapped(&AnimationClip::default()), 0.0);
+    }
+
+    #[test]
+    fn ik_goal_is_sampled_without_moving_target() {
+        let mut app = setup_app();
+        let curve = VariableCurve::new(
+            vec![0.0, 1.0],
+            Keyframes::IkGoal {
+                space: IkGoalSpace::World,
+                positions: vec![Vec3::ZERO, Vec3::X],
+            },
+        );
+        assert_eq!(curve.sample_ik_goal(0.5), Some(Vec3::new(0.5, 0.0, 0.0)));
+        assert_eq!(curve.sample_translation(0.5), None);
+
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            curve,
+        );
+        let handle = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+        let entity = spawn_player(&mut app, "root", handle);
+        advance_time(&mut app, 0.5);
+        app.update();
+        let transform = app.world.get::<Transform>(entity).unwrap();
+        assert_eq!(transform.translation, Vec3::ZERO);
+    }
+
+    #[test]
+    fn additive_reference_time() {
+        let mut app = setup_app();
+        let base = translation_clip(&mut app, "root");
+        let mut aim = AnimationClip::default();
+        aim.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            VariableCurve::new(
+                vec![0.0, 1.0, 2.0],
+                Keyframes::Translation(vec![-Vec3::Y, Vec3::ZERO, Vec3::Y]),
+            ),
+        );
+        let aim = app.world.resource_mut::<Assets<AnimationClip>>().add(aim);
+        let entity = spawn_player(&mut app, "root", base);
+        let mut player = app.world.get_mut::<AnimationPlayer>(entity).unwrap();
+        player
+            .slot("aim")
+            .play(aim)
+            .set_elapsed(1.5)
+            .set_blend_mode(BlendMode::Additive {
+                reference_time: 1.0,
+            });
+
+        advance_time(&mut app, 0.5);
+        app.update();
+        let translation = app.world.get::<Transform>(entity).unwrap().translation;
+        assert!(translation.abs_diff_eq(Vec3::new(0.5, 1.0, 0.0), 1e-5));
+    }
+
+    #[test]
+    fn frozen_bone_is_not_animated() {
+        let mut app = setup_app();
+        let root = EntityPath {
+            parts: vec![Name::new("root")],
+        };
+        let arm = EntityPath {
+            parts: vec![Name::new("root"), Name::new("arm")],
+        };
+        let mut clip = AnimationClip::default();
+        for path in [&root, &arm] {
+            clip.add_curve_to_path(
+                path.clone(),
+                VariableCurve::new(
+                    vec![0.0, 1.0],
+                    Keyframes::Translation(vec![Vec3::ZERO, Vec3::X]),
+                ),
+            );
+        }
+        let handle = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+        let entity = spawn_player(&mut app, "root", handle);
+        let arm_entity = app
+            .world
+            .spawn((Name::new("arm"), Transform::from_translation(Vec3::Y)))
+            .id();
+        app.world.entity_mut(entity).push_children(&[arm_entity]);
+        app.world
+            .get_mut::<AnimationPlayer>(entity)
+            .unwrap()
+            .freeze(arm.clone());
+
+        advance_time(&mut app, 0.5);
+        app.update();
+        let translation = app.world.get::<Transform>(entity).unwrap().translation;
+        assert_eq!(translation, Vec3::new(0.5, 0.0, 0.0));
+        let translation = app.world.get::<Transform>(arm_entity).unwrap().translation;
+        assert_eq!(translation, Vec3::Y);
+    }
+
+    #[test]
+    fn marker_reached_every_loop() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let entity = spawn_player(&mut app, "root", handle);
+        app.world
+            .get_mut::<AnimationPlayer>(entity)
+            .unwrap()
+            .add_marker(0.25, 7)
+            .repeat();
+        let mut reader = ManualEventReader::<AnimationMarkerReached>::default();
+        let mut reached = |app: &mut App, seconds| {
+            advance_time(app, seconds);
+            app.update();
+            let events = app.world.resource::<Events<AnimationMarkerReached>>();
+            reader.iter(events).copied().collect::<Vec<_>>()
+        };
+
+        let event = AnimationMarkerReached { entity, id: 7 };
+        assert_eq!(reached(&mut app, 0.5), vec![event]);
+        // Goes past the marker after wrapping around the end of the clip
+        assert_eq!(reached(&mut app, 1.0), vec![event]);
+        assert_eq!(reached(&mut app, 0.5), vec![]);
+        assert_eq!(reached(&mut app, 2.0), vec![event, event]);
+    }
+
+    #[test]
+    fn seek_with_events() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let entity = spawn_player(&mut app, "root", handle);
+        app.world
+            .get_mut::<AnimationPlayer>(entity)
+            .unwrap()
+            .add_marker(0.25, 7)
+            .repeat();
+        let mut reader = ManualEventReader::<AnimationMarkerReached>::default();
+        let mut seek = |app: &mut App, elapsed, fire| {
+            app.world
+                .get_mut::<AnimationPlayer>(entity)
+                .unwrap()
+                .set_elapsed_with_events(elapsed, fire);
+            advance_time(app, 0.0);
+            app.update();
+            let events = app.world.resource::<Events<AnimationMarkerReached>>();
+            reader.iter(events).count()
+        };
+
+        // Suppressed by default, like scrubbing
+        assert_eq!(seek(&mut app, 0.5, false), 0);
+        assert_eq!(seek(&mut app, 0.0, false), 0);
+        assert_eq!(seek(&mut app, 0.5, true), 1);
+        // Across the end of the loop
+        assert_eq!(seek(&mut app, 2.5, true), 2);
+        assert_eq!(seek(&mut app, 0.0, true), 0);
+    }
+
+    #[test]
+    fn clip_pose_applied_without_player() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let root = app
+            .world
+            .spawn((Name::new("root"), Transform::default()))
+            .id();
+        let clip = app
+            .world
+            .resource::<Assets<AnimationClip>>()
+            .get(&handle)
+            .unwrap()
+            .clone();
+
+        let mut system_state: SystemState<(Query<&Children>, Query<&Name>, Query<&mut Transform>)> =
+            SystemState::new(&mut app.world);
+        let (children, names, mut transforms) = system_state.get_mut(&mut app.world);
+        assert!(apply_clip_pose(
+            &clip,
+            0.25,
+            root,
+            &children,
+            &names,
+            &mut transforms
+        ));
+        let translation = app.world.get::<Transform>(root).unwrap().translation;
+        assert_eq!(translation, Vec3::new(0.25, 0.0, 0.0));
+    }
+
+    #[test]
+    fn speed_scaled_transition_is_stretched() {
+        let mut app = setup_app();
+        let first = translation_clip(&mut app, "root");
+        let second = translation_clip(&mut app, "root");
+        let entity = spawn_player(&mut app, "root", first);
+        app.world
+            .get_mut::<AnimationPlayer>(entity)
+            .unwrap()
+            .set_speed_scaled_transitions(true)
+            .play_with_transition(second, Duration::from_secs(1))
+            .set_speed(0.5);
+
+        advance_time(&mut app, 1.5);
+        app.update();
+        let player = app.world.get::<AnimationPlayer>(entity).unwrap();
+        assert!(player.is_transitioning());
+
+        advance_time(&mut app, 0.6);
+        app.update();
+        let player = app.world.get::<AnimationPlayer>(entity).unwrap();
+        assert!(!player.is_transitioning());
+    }
+
+    #[test]
+    fn invalid_curve_is_not_added() {
+        fn add(clip: &mut AnimationClip, timestamps: Vec<f32>) -> Result<(), AnimationError> {
+            let path = EntityPath {
+                parts: vec![Name::new("root")],
+            };
+            let keyframes = Keyframes::Translation(vec![Vec3::ZERO; timestamps.len()]);
+            clip.try_add_curve_to_path(path, VariableCurve::new(timestamps, keyframes))?;
+            Ok(())
+        }
+
+        let mut clip = AnimationClip::default();
+        assert_eq!(add(&mut clip, vec![0.0, 1.0]), Ok(()));
+        assert_eq!(
+            add(&mut clip, vec![2.0, 1.0]),
+            Err(AnimationError::Clip(
+                AnimationClipError::UnsortedTimestamps {
+                    path: EntityPath {
+                        parts: vec![Name::new("root")],
+                    },
+                    curve: 1,
+                }
+            ))
+        );
+        assert_eq!(clip.curve_count(), 1);
+        assert_eq!(clip.duration(), 1.0);
+    }
+
+    #[test]
+    fn blend_clips_matches_player() {
+        let path = EntityPath {
+            parts: vec![Name::new("root")],
+        };
+        let clip = |target| {
+            let mut clip = AnimationClip::default();
+            clip.add_curve_to_path(
+                path.clone(),
+                VariableCurve::new(vec![0.0, 1.0], Keyframes::Translation(vec![target, target])),
+            );
+            clip
+        };
+        let (walk, run) = (clip(Vec3::X), clip(Vec3::Y));
+
+        let pose = blend_clips(&[(&walk, 1.0), (&run, 0.25)], 0.5, &path).unwrap();
+        assert_eq!(pose.translation, Vec3::new(0.75, 0.25, 0.0));
+        let other = EntityPath {
+            parts: vec![Name::new("other")],
+        };
+        assert_eq!(blend_clips(&[(&walk, 1.0)], 0.5, &other), None);
+
+        let mut app = setup_app();
+        let mut assets = app.world.resource_mut::<Assets<AnimationClip>>();
+        let (walk, run) = (assets.add(walk), assets.add(run));
+        let entity = spawn_player(&mut app, "root", walk);
+        app.world
+            .get_mut::<AnimationPlayer>(entity)
+            .unwrap()
+            .slot("run")
+            .play(run)
+            .set_weight(0.25);
+        app.update();
+        let transform = app.world.get::<Transform>(entity).unwrap();
+        assert_eq!(*transform, pose);
+    }
+
+    #[test]
+    fn mismatched_lengths_are_repaired_on_load() {
+        let mut app = setup_app();
+        let path = EntityPath {
+            parts: vec![Name::new("root")],
+        };
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(
+                vec![0.0, 1.0, 2.0],
+                Keyframes::Translation(vec![Vec3::ZERO, Vec3::X]),
+            ),
+        );
+        let handle = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+        spawn_player(&mut app, "root", handle.clone());
+
+        for _ in 0..2 {
+            advance_time(&mut app, 1.5);
+            app.update();
+        }
+        let clip = app
+            .world
+            .resource::<Assets<AnimationClip>>()
+            .get(&handle)
+            .unwrap();
+        assert_eq!(
+            clip.curves.get(&path).unwrap()[0].keyframe_timestamps,
+            vec![0.0, 1.0]
+        );
+        assert!(clip.validate().is_empty());
+    }
+
+    #[test]
+    fn rotation_around_pivot() {
+        let mut app = setup_app();
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("door")],
+            },
+            VariableCurve::new(
+                vec![0.0, 1.0],
+                Keyframes::Rotation(vec![
+                    Quat::IDENTITY,
+                    Quat::from_rotation_y(std::f32::consts::FRAC_PI_2),
+                ]),
+            )
+            .with_pivot(Vec3::X),
+        );
+        let handle = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+        let entity = spawn_player(&mut app, "door", handle);
+
+        advance_time(&mut app, 0.5);
+        app.update();
+        let transform = *app.world.get::<Transform>(entity).unwrap();
+        // The hinge stays in place
+        let hinge = transform.translation + transform.rotation * Vec3::X;
+        assert!(hinge.abs_diff_eq(Vec3::X, 1e-5));
+
+        advance_time(&mut app, 0.5);
+        app.update();
+        let translation = app.world.get::<Transform>(entity).unwrap().translation;
+        assert!(translation.abs_diff_eq(Vec3::new(1.0, 0.0, 1.0), 1e-5));
+    }
+
+    #[test]
+    fn time_to_next_completion() {
+        let mut clip = AnimationClip::default();
+        clip.set_duration_min(2.0);
+        let mut player = AnimationPlayer::default();
+        player.play(Handle::default()).repeat().set_speed(2.0);
+        player.set_elapsed(1.5);
+        assert_eq!(
+            player.time_to_next_completion(&clip),
+            Some(Duration::from_secs_f32(0.25))
+        );
+
+        player.set_speed(-1.0);
+        assert_eq!(
+            player.time_to_next_completion(&clip),
+            Some(Duration::from_secs_f32(1.5))
+        );
+        player.set_elapsed(-0.5);
+        assert_eq!(
+            player.time_to_next_completion(&clip),
+            Some(Duration::from_secs_f32(1.5))
+        );
+
+        player.pause();
+        assert_eq!(player.time_to_next_completion(&clip), None);
+    }
+
+    #[test]
+    fn average_of_three_rotations() {
+        let quarter = std::f32::consts::FRAC_PI_2;
+        let rotations = [
+            (Quat::IDENTITY, 1.0),
+            (Quat::from_rotation_y(quarter), 1.0),
+            (Quat::from_rotation_y(2.0 * quarter), 1.0),
+        ];
+        let average = average_rotations(&rotations).unwrap();
+        assert!(average.abs_diff_eq(Quat::from_rotation_y(quarter), 1e-5));
+
+        // The order of the rotations doesn't matter, even when one is given with the opposite sign
+        let reversed = [
+            (-Quat::from_rotation_y(2.0 * quarter), 1.0),
+            (Quat::from_rotation_y(quarter), 1.0),
+            (Quat::IDENTITY, 1.0),
+        ];
+        let average = average_rotations(&reversed).unwrap();
+        assert!(
+            average.abs_diff_eq(Quat::from_rotation_y(quarter), 1e-5)
+                || average.abs_diff_eq(-Quat::from_rotation_y(quarter), 1e-5)
+        );
+
+        assert_eq!(average_rotations(&[(Quat::IDENTITY, 0.0)]), None);
+    }
+
+    #[test]
+    fn transition_continues_while_main_paused() {
+        let mut app = setup_app();
+        let first = translation_clip(&mut app, "root");
+        let second = translation_clip(&mut app, "root");
+        let entity = spawn_player(&mut app, "root", first);
+        let mut player = app.world.get_mut::<AnimationPlayer>(entity).unwrap();
+        player.play_with_transition(second, Duration::from_secs(1));
+        player.pause();
+
+        advance_time(&mut app, 0.5);
+        app.update();
+        let player = app.world.get::<AnimationPlayer>(entity).unwrap();
+        assert_eq!(player.elapsed(), 0.0);
+        let (_, transition) = player.animations().nth(1).unwrap();
+        assert_eq!(transition.elapsed(), 0.5);
+
+        advance_time(&mut app, 0.6);
+        app.update();
+        let player = app.world.get::<AnimationPlayer>(entity).unwrap();
+        assert!(!player.is_transitioning());
+        assert_eq!(player.elapsed(), 0.0);
+    }
+
+    #[test]
+    fn clip_metadata() {
+        let mut clip = AnimationClip::default();
+        assert_eq!(clip.metadata("combat"), None);
+        assert_eq!(clip.set_metadata("combat", "true"), None);
+        assert_eq!(clip.metadata("combat"), Some("true"));
+        assert_eq!(
+            clip.metadata_iter().collect::<Vec<_>>(),
+            vec![("combat", "true")]
+        );
+        assert_eq!(clip.remove_metadata("combat"), Some("true".to_string()));
+        assert_eq!(clip.metadata("combat"), None);
+    }
+
+    #[test]
+    fn synced_transition_keeps_phase() {
+        let mut app = setup_app();
+        let walk = translation_clip(&mut app, "root");
+        let mut run = AnimationClip::default();
+        run.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            VariableCurve::new(
+                vec![0.0, 2.0],
+                Keyframes::Translation(vec![Vec3::ZERO, Vec3::Y]),
+            ),
+        );
+        let run = app.world.resource_mut::<Assets<AnimationClip>>().add(run);
+        let entity = spawn_player(&mut app, "root", run);
+        app.world
+            .get_mut::<AnimationPlayer>(entity)
+            .unwrap()
+            .repeat()
+            .set_synced_transitions(true)
+            .play_with_transition(walk, Duration::from_secs(2))
+            .repeat();
+
+        advance_time(&mut app, 0.25);
+        app.update();
+        let player = app.world.get::<AnimationPlayer>(entity).unwrap();
+        let (_, transition) = player.animations().nth(1).unwrap();
+        assert_eq!(player.elapsed(), 0.25);
+        assert_eq!(transition.elapsed(), 0.5);
+    }
+
+    #[test]
+    fn markers_sorted_by_time() {
+        let mut player = AnimationPlayer::default();
+        player
+            .add_marker(0.5, 1)
+            .add_marker(0.25, 2)
+            .add_marker(0.5, 3)
+            .add_marker(0.0, 4);
+        assert_eq!(
+            player.markers().collect::<Vec<_>>(),
+            vec![(0.0, 4), (0.25, 2), (0.5, 1), (0.5, 3)]
+        );
+        player.remove_marker(1);
+        assert_eq!(
+            player.markers().collect::<Vec<_>>(),
+            vec![(0.0, 4), (0.25, 2), (0.5, 3)]
+        );
+    }
+
+    #[test]
+    fn step_paused_player() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let mut clip = app
+            .world
+            .resource::<Assets<AnimationClip>>()
+            .get(&handle)
+            .unwrap()
+            .clone();
+        let entity = spawn_player(&mut app, "root", handle);
+        let mut player = app.world.get_mut::<AnimationPlayer>(entity).unwrap();
+        player.repeat().pause();
+        player
+            .step(Duration::from_secs_f32(0.25), &clip)
+            .step(Duration::from_secs_f32(0.25), &clip);
+
+        advance_time(&mut app, 0.1);
+        app.update();
+        let translation = app.world.get::<Transform>(entity).unwrap().translation;
+        assert_eq!(translation, Vec3::new(0.5, 0.0, 0.0));
+
+        app.world
+            .get_mut::<AnimationPlayer>(entity)
+            .unwrap()
+            .step(Duration::from_secs_f32(0.75), &clip);
+        advance_time(&mut app, 0.1);
+        app.update();
+        let player = app.world.get::<AnimationPlayer>(entity).unwrap();
+        assert_eq!(player.elapsed(), 0.25);
+        assert_eq!(player.completions(), 1);
+        let translation = app.world.get::<Transform>(entity).unwrap().translation;
+        assert_eq!(translation, Vec3::new(0.25, 0.0, 0.0));
+
+        clip.set_loop_points(Some((0.25, 0.75)));
+        let mut player = AnimationPlayer::default();
+        player.play(Handle::default()).repeat().pause();
+        player.step(Duration::from_secs_f32(1.0), &clip);
+        assert_eq!(player.elapsed(), 0.5);
+        assert_eq!(player.completions(), 0);
+    }
+
+    #[test]
+    fn inserted_keyframe_keeps_curve() {
+        let curve = VariableCurve::new(
+            vec![0.0, 1.0, 3.0],
+            Keyframes::Translation(vec![Vec3::ZERO, Vec3::X, Vec3::Y]),
+        );
+        let mut edited = curve.clone();
+        assert_eq!(edited.insert_keyframe(2.0), Some(2));
+        assert_eq!(edited.keyframe_timestamps, vec![0.0, 1.0, 2.0, 3.0]);
+        assert_eq!(edited.insert_keyframe(1.0), Some(1));
+        assert_eq!(edited.keyframe_timestamps.len(), 4);
+        for step in 0..=30 {
+            let time = step as f32 * 0.1;
+            let (a, b) = (
+                curve.sample_translation(time).unwrap(),
+                edited.sample_translation(time).unwrap(),
+            );
+            assert!(a.abs_diff_eq(b, 1e-5));
+        }
+    }
+
+    #[test]
+    fn finished_behaviors() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let behaviors = [
+            (FinishedBehavior::HoldPose, false),
+            (FinishedBehavior::FreeTransform, false),
+            (FinishedBehavior::Stop, false),
+            (FinishedBehavior::FreeTransform, true),
+        ];
+        let entities = behaviors.map(|(behavior, warped)| {
+            let entity = spawn_player(&mut app, "root", handle.clone());
+            let mut player = app.world.get_mut::<AnimationPlayer>(entity).unwrap();
+            player.set_finished_behavior(behavior);
+            if warped {
+                player.set_time_warp(Some(TimeWarp {
+                    keyframe_timestamps: vec![0.0, 0.5, 1.0],
+                    keyframes: vec![0.0, 0.25, 1.0],
+                }));
+            }
+            entity
+        });
+
+        advance_time(&mut app, 0.75);
+        app.update();
+        for entity in entities {
+            app.world.get_mut::<Transform>(entity).unwrap().translation = Vec3::Y;
+        }
+        advance_time(&mut app, 0.5);
+        app.update();
+
+        let [hold, free, stop, warped_free] = entities;
+        assert_eq!(
+            app.world.get::<Transform>(hold).unwrap().translation,
+            Vec3::X
+        );
+        assert_eq!(
+            app.world.get::<Transform>(free).unwrap().translation,
+            Vec3::Y
+        );
+        assert_eq!(
+            app.world.get::<Transform>(warped_free).unwrap().translation,
+            Vec3::Y
+        );
+        assert_eq!(
+            app.world.get::<Transform>(stop).unwrap().translation,
+            Vec3::Y
+        );
+        let player = app.world.get::<AnimationPlayer>(stop).unwrap();
+        assert_eq!(player.animation.animation_clip(), &Handle::default());
+        let player = app.world.get::<AnimationPlayer>(free).unwrap();
+        assert_ne!(player.animation.animation_clip(), &Handle::default());
+    }
+
+    #[test]
+    fn relative_seek() {
+        let mut clip = AnimationClip::default();
+        clip.set_duration_min(1.0);
+        let mut player = AnimationPlayer::default();
+        player.play(Handle::default()).repeat();
+        player.advance_by(0.75, &clip).advance_by(0.5, &clip);
+        assert_eq!(player.completions(), 1);
+        assert_eq!(player.elapsed(), 0.25);
+        player.rewind_by(0.5, &clip);
+        assert_eq!(player.elapsed(), 0.75);
+        assert_eq!(player.completions(), 0);
+        player.advance_by(2.5, &clip).rewind_by(1.5, &clip);
+        assert_eq!(player.elapsed(), 0.75);
+        assert_eq!(player.completions(), 1);
+        player.rewind_by(5.0, &clip);
+        assert_eq!(player.completions(), 0);
+
+        player.play(Handle::default());
+        player.advance_by(2.0, &clip);
+        assert_eq!(player.elapsed(), 1.0);
+        assert!(player.is_finished());
+        player.rewind_by(3.0, &clip);
+        assert_eq!(player.elapsed(), 0.0);
+        assert!(!player.is_finished());
+    }
+
+    #[test]
+    fn player_summary() {
+        let handle = Handle::<AnimationClip>::weak(HandleId::random::<AnimationClip>());
+        let mut player = AnimationPlayer::default();
+        player.play(handle.clone()).set_speed(2.0).set_elapsed(0.5);
+        player.pause();
+        assert_eq!(
+            player.to_string(),
+            format!(
+                "{:?} at 0.500s, speed 2, paused, 0 completions, 0 transitions, 0 slots",
+                handle.id()
+            )
+        );
+    }
+
+    #[test]
+    fn curve_cursor_samples_like_curve() {
+        let curve = VariableCurve::new(
+            vec![1.0, 2.0, 2.0, 3.0, 4.0],
+            Keyframes::Translation(vec![Vec3::ZERO, Vec3::X, Vec3::Y, Vec3::Z, Vec3::ONE]),
+        );
+        let mut cursor = CurveCursor::default();
+        for time in [0.5, 1.5, 1.5, 2.0, 2.5, 3.5, 4.0, 4.5, 1.25, 3.0, 2.75] {
+            assert_eq!(
+                cursor.sample_translation(&curve, time),
+                curve.sample_translation(time),
+                "at {time}"
+            );
+        }
+        assert_eq!(cursor.sample_rotation(&curve, 1.5), None);
+    }
+
+    #[test]
+    fn loop_speed_multiplier_speeds_up_each_loop() {
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            VariableCurve::new(
+                vec![0.0, 1.0],
+                Keyframes::Translation(vec![Vec3::ZERO, Vec3::X]),
+            ),
+        );
+        let mut animation = PlayingAnimation::default();
+        animation.repeat().set_loop_speed_multiplier(2.0);
+        animation.advance(Duration::from_secs_f32(0.5), &clip);
+        assert_eq!(animation.speed(), 1.0);
+        animation.advance(Duration::from_secs_f32(0.75), &clip);
+        assert_eq!(animation.completions(), 1);
+        assert_eq!(animation.speed(), 2.0);
+        animation.advance(Duration::from_secs_f32(0.5), &clip);
+        assert_eq!(animation.completions(), 2);
+        assert_eq!(animation.speed(), 4.0);
+
+        // Reverse playback keeps its direction
+        animation.set_speed(-1.0).set_elapsed(0.0);
+        animation.advance(Duration::from_secs_f32(1.5), &clip);
+        assert_eq!(animation.completions(), 3);
+        assert_eq!(animation.speed(), -2.0);
+    }
+
+    #[test]
+    fn sample_clip_into_buffer() {
+        let mut clip = AnimationClip::default();
+        for (name, keyframes) in [
+            ("root", Keyframes::Translation(vec![Vec3::ZERO, Vec3::X])),
+            ("child", Keyframes::Scale(vec![Vec3::ONE, Vec3::splat(3.0)])),
+        ] {
+            clip.add_curve_to_path(
+                EntityPath {
+                    parts: vec![Name::new(name)],
+                },
+                VariableCurve::new(vec![0.0, 1.0], keyframes),
+            );
+        }
+        let mut out = vec![(7, Transform::IDENTITY); 5];
+        clip.sample_into(0.5, &mut out);
+        assert_eq!(out.len(), 2);
+        for (path, (index, pose)) in clip.paths().zip(&out) {
+            assert_eq!(clip.paths().nth(*index), Some(path));
+            assert_eq!(Some(*pose), blend_clips(&[(&clip, 1.0)], 0.5, path));
+        }
+        let capacity = out.capacity();
+        clip.sample_into(1.0, &mut out);
+        assert_eq!(out.capacity(), capacity);
+        assert!(
+            out.contains(&(0, Transform::from_translation(Vec3::X)))
+                || out.contains(&(1, Transform::from_translation(Vec3::X)))
+        );
+
+        // Times outside of the clip are clamped to it instead of wrapping
+        clip.sample_into(-0.5, &mut out);
+        assert!(out.contains(&(0, Transform::IDENTITY)));
+        assert!(out.contains(&(1, Transform::IDENTITY)));
+        clip.sample_into(2.5, &mut out);
+        assert!(out.contains(&(0, Transform::from_translation(Vec3::X))));
+        assert!(out.contains(&(1, Transform::from_scale(Vec3::splat(3.0)))));
+    }
+
+    #[test]
+    fn hold_first_frame_of_loading_clip() {
+        let mut app = setup_app();
+        let handle = Handle::<AnimationClip>::weak(HandleId::random::<AnimationClip>());
+        let entity = spawn_player(&mut app, "root", handle.clone());
+        app.world
+            .get_mut::<AnimationPlayer>(entity)
+            .unwrap()
+            .set_hold_first_frame(true)
+            .pause();
+        app.update();
+        app.update();
+
+        // The clip finishes loading after the player was last changed
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            VariableCurve::new(
+                vec![0.0, 1.0],
+                Keyframes::Translation(vec![Vec3::Y, Vec3::X]),
+            ),
+        );
+        app.world
+            .resource_mut::<Assets<AnimationClip>>()
+            .set_untracked(handle.id(), clip);
+        app.update();
+        let transform = app.world.get::<Transform>(entity).unwrap();
+        assert_eq!(transform.translation, Vec3::Y);
+    }
+
+    #[test]
+    fn manual_transition_weight() {
+        let mut app = setup_app();
+        let mut handles = Vec::new();
+        for translation in [Vec3::X, Vec3::Y] {
+            let mut clip = AnimationClip::default();
+            clip.add_curve_to_path(
+                EntityPath {
+                    parts: vec![Name::new("root")],
+                },
+                VariableCurve::new(vec![0.0], Keyframes::Translation(vec![translation])),
+            );
+            handles.push(app.world.resource_mut::<Assets<AnimationClip>>().add(clip));
+        }
+        let root = spawn_player(&mut app, "root", handles[0].clone());
+        app.update();
+        app.world
+            .get_mut::<AnimationPlayer>(root)
+            .unwrap()
+            .play_with_transition(handles[1].clone(), Duration::from_secs(2))
+            .set_transition_weight(0, 0.25);
+        advance_time(&mut app, 1.0);
+        app.update();
+        let transform = app.world.get::<Transform>(root).unwrap();
+        assert_eq!(transform.translation, Vec3::new(0.25, 0.75, 0.0));
+
+        // The weight doesn't decline with time anymore
+        let mut player = app.world.get_mut::<AnimationPlayer>(root).unwrap();
+        assert_eq!(player.transition_weight(0), Some(0.25));
+        player.set_transition_weight(0, 0.5);
+        advance_time(&mut app, 5.0);
+        app.update();
+        let transform = app.world.get::<Transform>(root).unwrap();
+        assert_eq!(transform.translation, Vec3::new(0.5, 0.5, 0.0));
+
+        app.world
+            .get_mut::<AnimationPlayer>(root)
+            .unwrap()
+            .set_transition_weight(0, 0.0);
+        app.update();
+        let player = app.world.get::<AnimationPlayer>(root).unwrap();
+        assert_eq!(player.transition_count(), 0);
+    }
+
+    #[test]
+    fn anchor_root_translation() {
+        let mut app = setup_app();
+        let mut trs_clip = AnimationClip::default();
+        trs_clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            VariableCurve::new(
+                vec![0.0, 1.0],
+                Keyframes::Trs(vec![
+                    Transform::IDENTITY,
+                    Transform::from_xyz(1.0, 0.0, 0.0),
+                ]),
+            ),
+        );
+        let handles = [
+            translation_clip(&mut app, "root"),
+            app.world
+                .resource_mut::<Assets<AnimationClip>>()
+                .add(trs_clip),
+        ];
+        for handle in handles {
+            let root = spawn_player(&mut app, "root", handle);
+            let parent = app
+                .world
+                .spawn(GlobalTransform::from(Transform::from_xyz(10.0, 0.0, 0.0)))
+                .id();
+            app.world.entity_mut(parent).push_children(&[root]);
+            app.world
+                .get_mut::<AnimationPlayer>(root)
+                .unwrap()
+                .set_anchor(Some(Vec3::new(0.0, 5.0, 0.0)));
+
+            advance_time(&mut app, 0.5);
+            app.update();
+            let transform = app.world.get::<Transform>(root).unwrap();
+            assert_eq!(transform.translation, Vec3::new(-9.5, 5.0, 0.0));
+        }
+    }
+
+    #[test]
+    fn any_animation_active() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let finished = spawn_player(&mut app, "finished", handle.clone());
+        app.world
+            .get_mut::<AnimationPlayer>(finished)
+            .unwrap()
+            .set_elapsed(2.0);
+        let playing = spawn_player(&mut app, "playing", handle);
+        app.update();
+        assert!(app.world.resource::<AnyAnimationActive>().0);
+
+        app.world
+            .get_mut::<AnimationPlayer>(playing)
+            .unwrap()
+            .set_elapsed(2.0);
+        app.update();
+        assert!(!app.world.resource::<AnyAnimationActive>().0);
+    }
+
+    #[test]
+    fn disabled_curve_is_not_applied() {
+        let mut app = setup_app();
+        let path = EntityPath {
+            parts: vec![Name::new("root")],
+        };
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(
+                vec![0.0, 1.0],
+                Keyframes::Translation(vec![Vec3::ZERO, Vec3::X]),
+            ),
+        );
+        let mut rotation = VariableCurve::new(
+            vec![0.0, 1.0],
+            Keyframes::Rotation(vec![Quat::IDENTITY, Quat::from_rotation_z(1.0)]),
+        );
+        rotation.enabled = false;
+        clip.add_curve_to_path(path, rotation);
+        let handle = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+        let root = spawn_player(&mut app, "root", handle);
+
+        advance_time(&mut app, 0.5);
+        app.update();
+        let transform = app.world.get::<Transform>(root).unwrap();
+        assert_eq!(transform.translation, Vec3::new(0.5, 0.0, 0.0));
+        assert_eq!(transform.rotation, Quat::IDENTITY);
+    }
+
+    #[test]
+    fn record_clip() {
+        let mut app = setup_app();
+        let path = EntityPath {
+            parts: vec![Name::new("root")],
+        };
+        let mut recorder = ClipRecorder::default();
+        for frame in 0..10 {
+            let time = frame as f32 * 0.1;
+            let transform = Transform::from_xyz(time, 0.0, 0.0)
+                .with_rotation(Quat::from_rotation_z(time))
+                .with_scale(Vec3::splat(1.0 + time));
+            recorder.record(time, &path, transform);
+        }
+        let clip = recorder.into_clip();
+        assert_eq!(clip.curves().get(&path).map(Vec::len), Some(3));
+        let handle = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+        let root = spawn_player(&mut app, "root", handle);
+
+        advance_time(&mut app, 0.45);
+        app.update();
+        let transform = app.world.get::<Transform>(root).unwrap();
+        assert!((transform.translation - Vec3::new(0.45, 0.0, 0.0)).length() < 1e-5);
+        assert!(
+            transform
+                .rotation
+                .angle_between(Quat::from_rotation_z(0.45))
+                < 1e-3
+        );
+        assert!((transform.scale - Vec3::splat(1.45)).length() < 1e-5);
+    }
+
+    #[test]
+    fn multiplicative_scale_curve() {
+        let mut app = setup_app();
+        let mut curve = VariableCurve::new(vec![0.0], Keyframes::Scale(vec![Vec3::splat(2.0)]));
+        curve.scale_blend = Some(ScaleBlend::Multiplicative);
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            curve,
+        );
+        let handle = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+        let entity = spawn_player(&mut app, "root", handle);
+        app.world.get_mut::<Transform>(entity).unwrap().scale = Vec3::splat(0.5);
+
+        app.update();
+        let scale = app.world.get::<Transform>(entity).unwrap().scale;
+        assert_eq!(scale, Vec3::ONE);
+    }
+
+    #[test]
+    fn reset_players() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let root = spawn_player(&mut app, "root", handle.clone());
+        let mut player = app.world.get_mut::<AnimationPlayer>(root).unwrap();
+        player
+            .repeat()
+            .play_with_transition(handle, Duration::from_secs(1))
+            .repeat();
+        advance_time(&mut app, 2.5);
+        app.update();
+        let player = app.world.get::<AnimationPlayer>(root).unwrap();
+        assert_eq!(player.completions(), 2);
+
+        app.world
+            .resource_mut::<Events<ResetAnimationPlayers>>()
+            .send(ResetAnimationPlayers);
+        advance_time(&mut app, 0.0);
+        app.update();
+        let player = app.world.get::<AnimationPlayer>(root).unwrap();
+        assert_eq!(player.elapsed(), 0.0);
+        assert_eq!(player.completions(), 0);
+        assert!(!player.is_transitioning());
+        assert_eq!(player.repeat_mode(), RepeatAnimation::Forever);
+        let transform = app.world.get::<Transform>(root).unwrap();
+        assert_eq!(transform.translation, Vec3::ZERO);
+    }
+
+    #[test]
+    fn get_curve_by_kind() {
+        let path = EntityPath {
+            parts: vec![Name::new("root")],
+        };
+        let mut clip = AnimationClip::default();
+        for keyframes in [
+            Keyframes::Translation(vec![Vec3::X]),
+            Keyframes::Rotation(vec![Quat::IDENTITY]),
+            Keyframes::Translation(vec![Vec3::Y]),
+        ] {
+            clip.add_curve_to_path(path.clone(), VariableCurve::new(vec![0.0], keyframes));
+        }
+        let translation = clip.get_curve(&path, CurveKind::Translation).unwrap();
+        assert_eq!(translation.sample_translation(0.0), Some(Vec3::X));
+        assert_eq!(
+            clip.curves_of_kind(&path, CurveKind::Translation).count(),
+            2
+        );
+        assert!(clip.get_curve(&path, CurveKind::Rotation).is_some());
+        assert!(clip.get_curve(&path, CurveKind::Scale).is_none());
+    }
+
+    #[test]
+    fn interval_events() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        app.world
+            .resource_mut::<Assets<AnimationClip>>()
+            .get_mut(&handle)
+            .unwrap()
+            .add_interval_event(0.3, 0.5, 4);
+        let entity = spawn_player(&mut app, "root", handle);
+        app.world
+            .get_mut::<AnimationPlayer>(entity)
+            .unwrap()
+            .repeat();
+        let mut entered_reader = ManualEventReader::<AnimationIntervalEntered>::default();
+        let mut exited_reader = ManualEventReader::<AnimationIntervalExited>::default();
+        let mut crossed = |app: &mut App, seconds| {
+            advance_time(app, seconds);
+            app.update();
+            let entered = app.world.resource::<Events<AnimationIntervalEntered>>();
+            let entered = entered_reader.iter(entered).count();
+            let exited = app.world.resource::<Events<AnimationIntervalExited>>();
+            (entered, exited_reader.iter(exited).count())
+        };
+
+        assert_eq!(crossed(&mut app, 0.2), (0, 0));
+        // A single frame stepping over the whole interval
+        assert_eq!(crossed(&mut app, 0.4), (1, 1));
+        // Entering across the end of the loop, then leaving
+        assert_eq!(crossed(&mut app, 0.8), (1, 0));
+        assert_eq!(crossed(&mut app, 0.2), (0, 1));
+    }
+
+    #[test]
+    fn interval_events_follow_clip_edits() {
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            VariableCurve::new(
+                vec![0.0, 2.0],
+                Keyframes::Translation(vec![Vec3::ZERO, Vec3::X]),
+            ),
+        );
+        clip.add_interval_event(0.25, 0.5, 1);
+        clip.add_interval_event(0.75, 1.5, 2);
+        clip.add_interval_event(1.75, 2.0, 3);
+
+        let mut shifted = clip.clone();
+        shifted.shift_time(1.0);
+        assert_eq!(
+            shifted.interval_events(),
+            &[(1.25, 1.5, 1), (1.75, 2.5, 2), (2.75, 3.0, 3)]
+        );
+
+        let sliced = clip.slice(0.5, 1.0);
+        assert_eq!(sliced.interval_events(), &[(0.25, 0.5, 2)]);
+        clip.add_interval_event(0.625, 0.625, 4);
+        let sliced = clip.slice(0.5, 1.0);
+        assert_eq!(
+            sliced.interval_events(),
+            &[(0.25, 0.5, 2), (0.125, 0.125, 4)]
+        );
+
+        let mut moved = clip.clone();
+        moved.interval_events[0].1 = 0.6;
+        assert_ne!(moved.fingerprint(), clip.fingerprint());
+    }
+
+    #[test]
+    fn paths_in_insertion_order() {
+        let mut clip = AnimationClip::default();
+        let names = ["spine", "arm", "head", "leg", "hand", "foot"];
+        for name in names.into_iter().chain(["arm"]) {
+            clip.add_curve_to_path(
+                EntityPath {
+                    parts: vec![Name::new(name)],
+                },
+                VariableCurve::new(vec![0.0], Keyframes::Translation(vec![Vec3::X])),
+            );
+        }
+        let paths: Vec<_> = clip
+            .clone()
+            .paths()
+            .map(|path| path.parts[0].as_str().to_string())
+            .collect();
+        assert_eq!(paths, names);
+        let sliced = clip.slice(0.0, 1.0);
+        assert!(sliced.paths().eq(clip.paths()));
+    }
+
+    #[test]
+    fn evaluate_blend_tree() {
+        let mut app = setup_app();
+        let path = EntityPath {
+            parts: vec![Name::new("root")],
+        };
+        let mut add_clip = |timestamps: Vec<f32>, translations: Vec<Vec3>| {
+            let mut clip = AnimationClip::default();
+            clip.add_curve_to_path(
+                path.clone(),
+                VariableCurve::new(timestamps, Keyframes::Translation(translations)),
+            );
+            app.world.resource_mut::<Assets<AnimationClip>>().add(clip)
+        };
+        let idle = add_clip(vec![0.0], vec![Vec3::X]);
+        let run = add_clip(vec![0.0], vec![Vec3::X * 3.0]);
+        let lean = add_clip(vec![0.0, 1.0], vec![Vec3::ZERO, Vec3::Y]);
+        let mut tree = BlendTree::new(BlendNode::additive(
+            BlendNode::blend_1d(
+                "speed",
+                [(0.0, BlendNode::clip(idle)), (2.0, BlendNode::clip(run))],
+            ),
+            BlendNode::clip(lean),
+            "lean",
+        ));
+        tree.set_parameter("speed", 1.0).set_parameter("lean", 1.0);
+        let animations = app.world.resource::<Assets<AnimationClip>>();
+        assert_eq!(
+            tree.evaluate(0.5, animations, &path),
+            Some(Transform::from_xyz(2.0, 0.5, 0.0))
+        );
+
+        let root = spawn_player(&mut app, "root", Handle::default());
+        app.world
+            .get_mut::<AnimationPlayer>(root)
+            .unwrap()
+            .play_blend_tree(tree);
+        advance_time(&mut app, 0.5);
+        app.update();
+        let transform = app.world.get::<Transform>(root).unwrap();
+        assert_eq!(transform.translation, Vec3::new(2.0, 0.5, 0.0));
+
+        app.world
+            .get_mut::<AnimationPlayer>(root)
+            .unwrap()
+            .blend_tree_mut()
+            .unwrap()
+            .set_parameter("speed", 2.0)
+            .set_parameter("lean", 0.0);
+        app.update();
+        let transform = app.world.get::<Transform>(root).unwrap();
+        assert_eq!(transform.translation, Vec3::new(3.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn is_playing_asset() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let mut player = AnimationPlayer::default();
+        player.play(handle.clone_weak());
+        assert!(player.is_playing_asset(&handle));
+        assert!(player.is_playing_asset(handle.clone_weak()));
+        assert!(player.is_playing_asset(handle.id()));
+        assert!(!player.is_playing_asset(HandleId::random::<AnimationClip>()));
+    }
+
+    #[test]
+    fn min_weight_drops_faded_transition() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let mut players = Vec::new();
+        for min_weight in [0.0, 0.01] {
+            let root = spawn_player(&mut app, "root", handle.clone());
+            app.world
+                .get_mut::<AnimationPlayer>(root)
+                .unwrap()
+                .set_min_weight(min_weight)
+                .play_with_transition(handle.clone(), Duration::from_secs(1));
+            players.push(root);
+        }
+        advance_time(&mut app, 0.999);
+        app.update();
+        let transitioning: Vec<_> = players
+            .iter()
+            .map(|&root| {
+                app.world
+                    .get::<AnimationPlayer>(root)
+                    .unwrap()
+                    .is_transitioning()
+            })
+            .collect();
+        assert_eq!(transitioning, vec![true, false]);
+    }
+
+    #[test]
+    fn trs_keyframes() {
+        let mut app = setup_app();
+        let start = Transform::IDENTITY;
+        let end = Transform::from_xyz(2.0, 0.0, 0.0)
+            .with_rotation(Quat::from_rotation_y(1.0))
+            .with_scale(Vec3::splat(3.0));
+        let curve = VariableCurve::new(vec![0.0, 1.0], Keyframes::Trs(vec![start, end]));
+        let expected = Transform::from_xyz(1.0, 0.0, 0.0)
+            .with_rotation(Quat::from_rotation_y(0.5))
+            .with_scale(Vec3::splat(2.0));
+        let sampled = curve.sample_trs(0.5).unwrap();
+        assert!(sampled.translation.abs_diff_eq(expected.translation, 1e-5));
+        assert!(sampled.rotation.abs_diff_eq(expected.rotation, 1e-5));
+        assert!(sampled.scale.abs_diff_eq(expected.scale, 1e-5));
+        assert_eq!(curve.sample_translation(0.5), None);
+
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            curve,
+        );
+        let handle = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+        let root = spawn_player(&mut app, "root", handle);
+        advance_time(&mut app, 0.5);
+        app.update();
+        let transform = app.world.get::<Transform>(root).unwrap();
+        assert_eq!(*transform, sampled);
+    }
+
+    #[test]
+    fn matrices_output() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let entity = spawn_player(&mut app, "root", handle);
+        let root = EntityPath {
+            parts: vec![Name::new("root")],
+        };
+        let other = EntityPath {
+            parts: vec![Name::new("other")],
+        };
+        app.world
+            .get_mut::<AnimationPlayer>(entity)
+            .unwrap()
+            .set_output(AnimationOutput::Matrices(vec![other, root]));
+
+        advance_time(&mut app, 0.5);
+        app.update();
+        let player = app.world.get::<AnimationPlayer>(entity).unwrap();
+        assert_eq!(
+            player.bone_matrices(),
+            &[Mat4::IDENTITY, Mat4::from_translation(Vec3::X * 0.5)]
+        );
+        assert_eq!(
+            *app.world.get::<Transform>(entity).unwrap(),
+            Transform::default()
+        );
+    }
+
+    #[test]
+    fn clamped_scalar_curve() {
+        let mut curve = VariableCurve::new(
+            vec![0.0, 1.0, 2.0, 3.0],
+            Keyframes::Scalar(vec![0.0, 1.0, 1.0, 0.0]),
+        );
+        curve.interpolation = Interpolation::Cubic;
+        assert!(curve.sample_scalar(1.5).unwrap() > 1.0);
+        let inside = curve.sample_scalar(0.5);
+
+        curve.clamp_scalar = true;
+        assert_eq!(curve.sample_scalar(1.5), Some(1.0));
+        assert_eq!(curve.sample_scalar(0.5), inside);
+    }
+
+    #[test]
+    fn affected_bones() {
+        let moving = EntityPath {
+            parts: vec![Name::new("moving")],
+        };
+        let still = EntityPath {
+            parts: vec![Name::new("still")],
+        };
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            still.clone(),
+            VariableCurve::new(
+                vec![0.0, 1.0],
+                Keyframes::Translation(vec![Vec3::X, Vec3::X * 1.001]),
+            ),
+        );
+        clip.add_curve_to_path(
+            moving.clone(),
+            VariableCurve::new(
+                vec![0.0, 1.0],
+                Keyframes::Rotation(vec![Quat::IDENTITY, Quat::from_rotation_x(1.0)]),
+            ),
+        );
+        assert_eq!(clip.affected_bones(0.01), vec![moving]);
+        assert_eq!(clip.affected_bones(0.0001).len(), 2);
+    }
+
+    #[test]
+    fn slot_priority() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            VariableCurve::new(vec![0.0], Keyframes::Translation(vec![Vec3::Y])),
+        );
+        let other = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+        let entity = spawn_player(&mut app, "root", handle.clone());
+        let mut player = app.world.get_mut::<AnimationPlayer>(entity).unwrap();
+        player.set_slot_priority("high", 1);
+        player.slot("high").play(handle);
+        player.slot("low").play(other.clone());
+        player.set_slot_priority("lowest", -1);
+        player.slot("lowest").play(other);
+        let names: Vec<_> = player.slots().map(|(name, _)| name).collect();
+        assert_eq!(names, ["lowest", "low", "high"]);
+        assert_eq!(player.get_slot("high").unwrap().priority(), 1);
+
+        advance_time(&mut app, 0.5);
+        app.update();
+        assert_eq!(
+            app.world.get::<Transform>(entity).unwrap().translation,
+            Vec3::X * 0.5
+        );
+    }
+
+    #[test]
+    fn seamless_clip() {
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            VariableCurve::new(
+                vec![0.0, 0.5, 1.0],
+                Keyframes::Translation(vec![Vec3::ZERO, Vec3::X, Vec3::ZERO]),
+            ),
+        );
+        assert!(clip.is_seamless(0.001));
+
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            VariableCurve::new(vec![0.0, 1.0], Keyframes::Scalar(vec![0.0, 0.5])),
+        );
+        assert!(!clip.is_seamless(0.001));
+        assert!(clip.is_seamless(1.0));
+    }
+
+    #[test]
+    fn start_with_jitter() {
+        let handle = Handle::<AnimationClip>::default();
+        let start = |seed| {
+            let mut player = AnimationPlayer::default();
+            player.start_with_jitter(handle.clone(), 2.0, seed);
+            player.elapsed()
+        };
+        let starts: Vec<f32> = (0..8).map(start).collect();
+        assert!(starts.iter().all(|start| (0.0..=2.0).contains(start)));
+        assert!(starts.windows(2).all(|starts| starts[0] != starts[1]));
+        assert_eq!(start(3), starts[3]);
+    }
+
+    #[test]
+    fn clip_memory_usage() {
+        let clip = |len: usize| {
+            let mut clip = AnimationClip::default();
+            clip.add_curve_to_path(
+                EntityPath {
+                    parts: vec![Name::new("root")],
+                },
+                VariableCurve::new(
+                    (0..len).map(|index| index as f32).collect(),
+                    Keyframes::Translation(vec![Vec3::ZERO; len]),
+                ),
+            );
+            clip
+        };
+        let small = clip(2).memory_usage();
+        let large = clip(102).memory_usage();
+        assert!(small > 2 * 16);
+        assert!(large >= small + 100 * 16);
+    }
+
+    #[test]
+    fn global_pause_freezes_players() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let entity = spawn_player(&mut app, "root", handle);
+        app.insert_resource(GlobalAnimationPause(true));
+
+        advance_time(&mut app, 0.5);
+        app.update();
+        let player = app.world.get::<AnimationPlayer>(entity).unwrap();
+        assert_eq!(player.elapsed(), 0.0);
+        assert!(!player.is_paused());
+
+        app.insert_resource(GlobalAnimationPause(false));
+        advance_time(&mut app, 0.5);
+        app.update();
+        let player = app.world.get::<AnimationPlayer>(entity).unwrap();
+        assert_eq!(player.elapsed(), 0.5);
+        assert!(!player.is_paused());
+    }
+
+    #[test]
+    fn normalized_marker_follows_duration() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        app.world
+            .resource_mut::<Assets<AnimationClip>>()
+            .get_mut(&handle)
+            .unwrap()
+            .set_duration_min(2.0);
+        let entity = spawn_player(&mut app, "root", handle);
+        app.world
+            .get_mut::<AnimationPlayer>(entity)
+            .unwrap()
+            .add_marker_normalized(0.5, 3);
+        let mut reader = ManualEventReader::<AnimationMarkerReached>::default();
+        let mut reached = |app: &mut App, seconds| {
+            advance_time(app, seconds);
+            app.update();
+            let events = app.world.resource::<Events<AnimationMarkerReached>>();
+            reader.iter(events).count()
+        };
+
+        assert_eq!(reached(&mut app, 0.75), 0);
+        assert_eq!(reached(&mut app, 0.5), 1);
+        assert_eq!(reached(&mut app, 0.5), 0);
+    }
+
+    #[test]
+    fn mask_fade() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let entity = spawn_player(&mut app, "root", handle);
+        let root = EntityPath {
+            parts: vec![Name::new("root")],
+        };
+        let mut mask = AnimationMask::default();
+        mask.set_weight(root.clone(), 0.0);
+        app.world
+            .get_mut::<AnimationPlayer>(entity)
+            .unwrap()
+            .animation
+            .set_mask_with_fade(Some(mask), Duration::from_secs(1));
+
+        advance_time(&mut app, 0.25);
+        app.update();
+        let player = app.world.get::<AnimationPlayer>(entity).unwrap();
+        assert_eq!(player.animation.mask_weight(&root), 0.75);
+        advance_time(&mut app, 0.5);
+        app.update();
+        let player = app.world.get::<AnimationPlayer>(entity).unwrap();
+        assert_eq!(player.animation.mask_weight(&root), 0.25);
+        advance_time(&mut app, 0.5);
+        app.update();
+        let player = app.world.get::<AnimationPlayer>(entity).unwrap();
+        assert_eq!(player.animation.mask_weight(&root), 0.0);
+        assert!(player.animation.mask_fade.is_none());
+    }
+
+    #[test]
+    fn procedural_clip() {
+        let mut app = setup_app();
+        let entity = spawn_player(&mut app, "root", Handle::default());
+        let bob = ProceduralClip::new(
+            [EntityPath {
+                parts: vec![Name::new("root")],
+            }],
+            |time, _| {
+                let height = (time * std::f32::consts::TAU).sin();
+                Some(Keyframes::Translation(vec![Vec3::Y * height]))
+            },
+        );
+        assert_eq!(bob.animated_paths().len(), 1);
+        app.world
+            .get_mut::<AnimationPlayer>(entity)
+            .unwrap()
+            .play_source(bob);
+
+        advance_time(&mut app, 0.25);
+        app.update();
+        let translation = app.world.get::<Transform>(entity).unwrap().translation;
+        assert!(translation.abs_diff_eq(Vec3::Y, 1e-5));
+        advance_time(&mut app, 0.5);
+        app.update();
+        let translation = app.world.get::<Transform>(entity).unwrap().translation;
+        assert!(translation.abs_diff_eq(-Vec3::Y, 1e-5));
+    }
+
+    #[test]
+    fn consolidate_curves() {
+        let path = EntityPath {
+            parts: vec![Name::new("root")],
+        };
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(
+                vec![0.0, 1.0],
+                Keyframes::Rotation(vec![Quat::IDENTITY, Quat::from_rotation_y(1.0)]),
+            ),
+        );
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(vec![0.0], Keyframes::Translation(vec![Vec3::X])),
+        );
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(
+                vec![0.5, 1.5],
+                Keyframes::Rotation(vec![Quat::from_rotation_x(1.0), Quat::from_rotation_x(2.0)]),
+            ),
+        );
+        clip.consolidate();
+
+        let curves = &clip.curves()[&path];
+        assert_eq!(curves.len(), 2);
+        let rotation = clip.get_curve(&path, CurveKind::Rotation).unwrap();
+        assert_eq!(rotation.keyframe_timestamps, vec![0.0, 0.5, 1.0, 1.5]);
+        assert_eq!(rotation.sample_rotation(0.0), Some(Quat::IDENTITY));
+        assert!(rotation
+            .sample_rotation(1.0)
+            .unwrap()
+            .abs_diff_eq(Quat::from_rotation_x(1.5), 1e-5));
+        assert!(rotation
+            .sample_rotation(1.5)
+            .unwrap()
+            .abs_diff_eq(Quat::from_rotation_x(2.0), 1e-5));
+    }
+
+    #[test]
+    fn set_elapsed_clamped() {
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            VariableCurve::new(
+                vec![0.0, 1.0],
+                Keyframes::Translation(vec![Vec3::ZERO, Vec3::X]),
+            ),
+        );
+        let mut player = AnimationPlayer::default();
+        player.set_elapsed_clamped(3.0, &clip);
+        assert_eq!(player.elapsed(), 1.0);
+        player.set_elapsed_clamped(-1.0, &clip);
+        assert_eq!(player.elapsed(), 0.0);
+
+        player.set_repeat(RepeatAnimation::Count(2));
+        player.set_elapsed_clamped(3.0, &clip);
+        assert_eq!(player.elapsed(), 2.0);
+
+        player.repeat();
+        player.set_elapsed_clamped(2.5, &clip);
+        assert_eq!(player.elapsed(), 0.5);
+        player.set_elapsed_clamped(-0.25, &clip);
+        assert_eq!(player.elapsed(), 0.75);
+    }
+
+    #[test]
+    fn inspect_bone_contributions() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let other = translation_clip(&mut app, "other");
+        let entity = spawn_player(&mut app, "root", handle.clone());
+        let root = EntityPath {
+            parts: vec![Name::new("root")],
+        };
+        let mut player = app.world.get_mut::<AnimationPlayer>(entity).unwrap();
+        player.set_inspected_bone(Some(root));
+        player.slot("other").play(other);
+        player.slot("half").play(handle.clone()).set_weight(0.5);
+
+        advance_time(&mut app, 0.5);
+        app.update();
+        let player = app.world.get::<AnimationPlayer>(entity).unwrap();
+        let value = Transform::from_translation(Vec3::X * 0.5);
+        assert_eq!(
+            player.bone_contributions(),
+            &[
+                BoneContribution {
+                    clip: handle.clone_weak(),
+                    weight: 1.0,
+                    value,
+                },
+                BoneContribution {
+                    clip: handle.clone_weak(),
+                    weight: 0.5,
+                    value,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn clip_from_csv() {
+        let clip = AnimationClip::from_csv(
+            "# path, kind, time, values
+            root, translation, 0.0, 0, 0, 0
+            root/arm, rotation, 0.0, 0, 0, 0, 1
+
+            root, translation, 1.0, 1, 0, 0
+            root/arm, scalar, 0.5, 0.25",
+        )
+        .unwrap();
+        let root = EntityPath {
+            parts: vec![Name::new("root")],
+        };
+        let arm = EntityPath {
+            parts: vec![Name::new("root"), Name::new("arm")],
+        };
+        assert_eq!(clip.paths().collect::<Vec<_>>(), vec![&root, &arm]);
+        assert_eq!(clip.duration(), 1.0);
+        let translation = clip.get_curve(&root, CurveKind::Translation).unwrap();
+        assert_eq!(translation.sample_translation(0.5), Some(Vec3::X * 0.5));
+        assert_eq!(clip.curves()[&arm].len(), 2);
+
+        assert_eq!(
+            AnimationClip::from_csv("root, scale, 0.0, 1, 1").unwrap_err(),
+            AnimationCsvError::WrongValueCount {
+                line: 1,
+                kind: CurveKind::Scale,
+                expected: 3,
+                found: 2,
+            }
+        );
+        assert_eq!(
+            AnimationClip::from_csv("root, scalar, 1.0, 0\n\nroot, scalar, 0.5, 1").unwrap_err(),
+            AnimationCsvError::UnsortedTime { line: 3, time: 0.5 }
+        );
+        assert_eq!(
+            AnimationClip::from_csv("root, color, 0.0, 1").unwrap_err(),
+            AnimationCsvError::UnknownKind {
+                line: 1,
+                kind: "color".to_string(),
+            }
+        );
+        assert_eq!(
+            AnimationClip::from_csv("root, scalar, x, 1").unwrap_err(),
+            AnimationCsvError::InvalidNumber {
+                line: 1,
+                value: "x".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn averaged_transition_rotations() {
+        let mut app = setup_app();
+        let mut rotation_clip = |angle: f32| {
+            let mut clip = AnimationClip::default();
+            clip.add_curve_to_path(
+                EntityPath {
+                    parts: vec![Name::new("root")],
+                },
+                VariableCurve::new(
+                    vec![0.0, 1.0],
+                    Keyframes::Rotation(vec![Quat::from_rotation_y(angle); 2]),
+                ),
+            );
+            app.world.resource_mut::<Assets<AnimationClip>>().add(clip)
+        };
+        let first = rotation_clip(0.0);
+        let second = rotation_clip(2.0);
+        let third = rotation_clip(-2.5);
+        let entity = spawn_player(&mut app, "root", first);
+        let mut player = app.world.get_mut::<AnimationPlayer>(entity).unwrap();
+        player
+            .play_with_transition(second, Duration::from_secs(1))
+            .play_with_transition(third, Duration::from_secs(1))
+            .set_transition_weight(0, 0.5)
+            .set_transition_weight(1, 0.5)
+            .set_average_transition_rotations(true);
+        advance_time(&mut app, 0.0);
+        app.update();
+
+        // The newest transition is applied last and keeps half the weight
+        let expected = average_rotations(&[
+            (Quat::from_rotation_y(2.0), 0.5),
+            (Quat::from_rotation_y(0.0), 0.25),
+            (Quat::from_rotation_y(-2.5), 0.25),
+        ])
+        .unwrap();
+        let rotation = app.world.get::<Transform>(entity).unwrap().rotation;
+        assert!(rotation.abs_diff_eq(expected, 1e-4) || rotation.abs_diff_eq(-expected, 1e-4));
+    }
+
+    #[test]
+    fn gltf_channels_round_trip() {
+        let path = EntityPath {
+            parts: vec![Name::new("root"), Name::new("arm")],
+        };
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(
+                vec![0.0, 0.5, 1.0],
+                Keyframes::Rotation(vec![
+                    Quat::IDENTITY,
+                    Quat::from_rotation_y(1.0),
+                    -Quat::from_rotation_y(2.0),
+                ]),
+            ),
+        );
+        let mut translation = VariableCurve::new(
+            vec![0.0, 0.5, 1.0],
+            Keyframes::Translation(vec![Vec3::ZERO, Vec3::X, Vec3::Y]),
+        );
+        translation.interpolation = Interpolation::Cubic;
+        clip.add_curve_to_path(path.clone(), translation);
+        let mut weights = VariableCurve::new(vec![0.0, 1.0], Keyframes::Scalar(vec![0.0, 1.0]));
+        weights.interpolation = Interpolation::Step;
+        clip.add_curve_to_path(path.clone(), weights);
+
+        let channels = clip.to_gltf_channels();
+        assert_eq!(channels.len(), 3);
+        assert_eq!(channels[1].interpolation, GltfInterpolation::CubicSpline);
+        // In-tangent, value and out-tangent of the middle keyframe
+        assert_eq!(
+            channels[1].output[9..18],
+            [0.0, 1.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0]
+        );
+        assert_eq!(channels[2].property, GltfProperty::MorphTargetWeights);
+
+        let imported = AnimationClip::from_gltf_channels(&channels);
+        let curves = &imported.curves()[&path];
+        assert_eq!(curves[1].interpolation, Interpolation::Cubic);
+        assert_eq!(curves[2].interpolation, Interpolation::Step);
+        for time in [0.0, 0.25, 0.75, 1.0] {
+            let original = clip.curves()[&path][0].sample_rotation(time).unwrap();
+            let rotation = curves[0].sample_rotation(time).unwrap();
+            assert!(rotation.abs_diff_eq(original, 1e-5) || rotation.abs_diff_eq(-original, 1e-5));
+            let original = clip.curves()[&path][1].sample_translation(time).unwrap();
+            assert!(curves[1]
+                .sample_translation(time)
+                .unwrap()
+                .abs_diff_eq(original, 1e-5));
+        }
+        for (exported, channel) in imported.to_gltf_channels().iter().zip(&channels) {
+            assert_eq!(exported.property, channel.property);
+            assert_eq!(exported.input, channel.input);
+            for (a, b) in exported.output.iter().zip(&channel.output) {
+                assert!((a - b).abs() < 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn start_keeps_transitions() {
+        let mut app = setup_app();
+        let first = translation_clip(&mut app, "root");
+        let second = translation_clip(&mut app, "root");
+        let third = translation_clip(&mut app, "root");
+        let entity = spawn_player(&mut app, "root", first);
+        app.world
+            .get_mut::<AnimationPlayer>(entity)
+            .unwrap()
+            .play_with_transition(second, Duration::from_secs(1));
+        advance_time(&mut app, 0.5);
+        app.update();
+        let weight = app
+            .world
+            .get::<AnimationPlayer>(entity)
+            .unwrap()
+            .transition_weight(0)
+            .unwrap();
+
+        let mut player = app.world.get_mut::<AnimationPlayer>(entity).unwrap();
+        player.start_keep_transitions(third.clone());
+        assert!(player.is_playing_asset(&third));
+        assert_eq!(player.transition_weight(0), Some(weight));
+        advance_time(&mut app, 0.25);
+        app.update();
+        let player = app.world.get::<AnimationPlayer>(entity).unwrap();
+        assert!(player.transition_weight(0).unwrap() < weight);
+    }
+
+    #[test]
+    fn master_weight_fades_player() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let entity = spawn_player(&mut app, "root", handle);
+        let mut player = app.world.get_mut::<AnimationPlayer>(entity).unwrap();
+        player.set_elapsed(1.0);
+        player.pause();
+        for master_weight in [1.0, 0.75, 0.5, 0.25, 0.0] {
+            app.world.get_mut::<Transform>(entity).unwrap().translation = Vec3::ZERO;
+            app.world
+                .get_mut::<AnimationPlayer>(entity)
+                .unwrap()
+                .set_master_weight(master_weight);
+            app.update();
+            let translation = app.world.get::<Transform>(entity).unwrap().translation;
+            assert!((translation.x - master_weight).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn uniform_step_lookup() {
+        let timestamps: Vec<f32> = (0..=30).map(|frame| frame as f32 / 30.0).collect();
+        let translations = timestamps.iter().map(|&time| Vec3::X * time).collect();
+        let mut curve = VariableCurve::new(timestamps, Keyframes::Translation(translations));
+        let searched = curve.clone();
+        let step = curve.detect_uniform_step(1e-5).unwrap();
+        assert!((step - 1.0 / 30.0).abs() < 1e-6);
+        for time in [0.0, 0.01, 1.0 / 3.0, 0.5, 0.99, 1.0] {
+            assert_eq!(
+                curve.sample_translation(time),
+                searched.sample_translation(time)
+            );
+        }
+        assert_eq!(curve.sample_translation(1.5), None);
+
+        // A wrong step falls back to searching the keyframes
+        curve.uniform_step = Some(0.2);
+        assert_eq!(
+            curve.sample_translation(0.5),
+            searched.sample_translation(0.5)
+        );
+
+        curve.keyframe_timestamps[1] = 0.05;
+        assert_eq!(curve.detect_uniform_step(1e-5), None);
+        assert_eq!(curve.uniform_step, None);
+    }
+
+    #[test]
+    fn clip_channels() {
+        let path = EntityPath {
+            parts: vec![Name::new("root")],
+        };
+        let mut clip = AnimationClip::default();
+        assert_eq!(clip.channels(), ChannelFlags::empty());
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(vec![0.0], Keyframes::Translation(vec![Vec3::X])),
+        );
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(vec![0.0], Keyframes::Rotation(vec![Quat::IDENTITY])),
+        );
+        let channels = clip.channels();
+        assert_eq!(channels, ChannelFlags::TRANSLATION | ChannelFlags::ROTATION);
+        assert!(channels.intersects(ChannelFlags::TRANSFORM));
+        assert!(!channels.contains(ChannelFlags::SCALAR));
+
+        clip.add_curve_to_path(
+            path,
+            VariableCurve::new(vec![0.0], Keyframes::Scalar(vec![1.0])),
+        );
+        assert!(clip.channels().contains(ChannelFlags::SCALAR));
+    }
+
+    #[test]
+    fn animation_in_custom_stage() {
+        fn raise(mut transforms: Query<&mut Transform, With<AnimationPlayer>>) {
+            for mut transform in &mut transforms {
+                transform.translation.y += 1.0;
+            }
+        }
+
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_plugin(AnimationPlugin::default().in_stage(CoreStage::Update))
+            .add_system_to_stage(CoreStage::Update, raise.after(AnimationSystem::Apply));
+        let mut time = Time::default();
+        time.update();
+        app.insert_resource(time);
+        let handle = translation_clip(&mut app, "root");
+        let entity = spawn_player(&mut app, "root", handle);
+
+        advance_time(&mut app, 0.5);
+        app.update();
+        let translation = app.world.get::<Transform>(entity).unwrap().translation;
+        assert_eq!(translation, Vec3::new(0.5, 1.0, 0.0));
+    }
+
+    #[derive(Component)]
+    struct SpriteFrame(usize);
+
+    #[test]
+    fn sprite_index_cycles() {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_plugin(AnimationPlugin::default().register_animated::<SpriteFrame>(
+                |frame, curve, time, _| {
+                    if let Some(index) = curve.sample_sprite_index(time) {
+                        frame.0 = index;
+                    }
+                },
+            ));
+        let mut time = Time::default();
+        time.update();
+        app.insert_resource(time);
+        let mut curve = VariableCurve::new(
+            vec![0.0, 0.25, 0.5, 0.75],
+            Keyframes::SpriteIndex(vec![4, 5, 6, 7]),
+        )
+        .with_interpolation(Interpolation::Cubic);
+        curve.edge_behavior = EdgeBehavior::Clamp;
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            curve,
+        );
+        clip.set_duration_min(1.0);
+        let handle = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+        let entity = spawn_player(&mut app, "root", handle);
+        app.world
+            .entity_mut(entity)
+            .insert(SpriteFrame(0))
+            .get_mut::<AnimationPlayer>()
+            .unwrap()
+            .repeat();
+
+        for expected in [5, 6, 7, 4, 6] {
+            advance_time(&mut app, 0.3);
+            app.update();
+            assert_eq!(app.world.get::<SpriteFrame>(entity).unwrap().0, expected);
+        }
+    }
+
+    #[derive(Component)]
+    struct MorphWeights(Vec<f32>);
+
+    #[test]
+    fn additive_morph_weights() {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_plugin(
+                AnimationPlugin::default().register_animated_scalar::<MorphWeights>(
+                    |weights, index| weights.0.get_mut(index),
+                ),
+            );
+        let mut time = Time::default();
+        time.update();
+        app.insert_resource(time);
+        let path = EntityPath {
+            parts: vec![Name::new("root")],
+        };
+        let mut clip = |jaw: [f32; 2], blink: [f32; 2]| {
+            let mut clip = AnimationClip::default();
+            for values in [jaw, blink] {
+                clip.add_curve_to_path(
+                    path.clone(),
+                    VariableCurve::new(vec![0.0, 1.0], Keyframes::Scalar(values.to_vec())),
+                );
+            }
+            app.world.resource_mut::<Assets<AnimationClip>>().add(clip)
+        };
+        let speak = clip([0.5, 0.5], [0.25, 0.25]);
+        let blink = clip([0.0, 0.0], [0.0, 1.0]);
+        let entity = spawn_player(&mut app, "root", speak);
+        app.world
+            .entity_mut(entity)
+            .insert(MorphWeights(vec![0.0, 0.0]))
+            .get_mut::<AnimationPlayer>()
+            .unwrap()
+            .slot("blink")
+            .play(blink)
+            .set_blend_mode(BlendMode::Additive {
+                reference_time: 0.0,
+            });
+
+        advance_time(&mut app, 0.5);
+        app.update();
+        let weights = &app.world.get::<MorphWeights>(entity).unwrap().0;
+        assert!((weights[0] - 0.5).abs() < 1e-5);
+        assert!((weights[1] - 0.75).abs() < 1e-5);
+    }
+
+    #[test]
+    fn master_weight_scales_scalars() {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_plugin(
+                AnimationPlugin::default().register_animated_scalar::<MorphWeights>(
+                    |weights, index| weights.0.get_mut(index),
+                ),
+            );
+        let mut time = Time::default();
+        time.update();
+        app.insert_resource(time);
+        let path = EntityPath {
+            parts: vec![Name::new("root")],
+        };
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(vec![0.0, 1.0], Keyframes::Scalar(vec![1.0, 1.0])),
+        );
+        let handle = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+        let entity = spawn_player(&mut app, "root", handle);
+        app.world
+            .entity_mut(entity)
+            .insert(MorphWeights(vec![0.0]))
+            .get_mut::<AnimationPlayer>()
+            .unwrap()
+            .set_master_weight(0.0);
+        let weight_after = |app: &mut App, update: fn(&mut AnimationPlayer)| {
+            update(&mut app.world.get_mut::<AnimationPlayer>(entity).unwrap());
+            advance_time(app, 0.1);
+            app.update();
+            app.world.get::<MorphWeights>(entity).unwrap().0[0]
+        };
+
+        assert_eq!(weight_after(&mut app, |_| {}), 0.0);
+        let freeze = |player: &mut AnimationPlayer| {
+            player.set_master_weight(0.5).freeze(EntityPath {
+                parts: vec![Name::new("root")],
+            });
+        };
+        assert_eq!(weight_after(&mut app, freeze), 0.0);
+        let unfreeze = |player: &mut AnimationPlayer| {
+            player.unfreeze(&EntityPath {
+                parts: vec![Name::new("root")],
+            });
+        };
+        assert_eq!(weight_after(&mut app, unfreeze), 0.5);
+        let skip = |player: &mut AnimationPlayer| {
+            player.set_min_weight(0.75);
+        };
+        assert_eq!(weight_after(&mut app, skip), 0.5);
+    }
+
+    #[test]
+    fn loop_progress_wraps() {
+        let mut clip = AnimationClip::default();
+        clip.set_duration_min(2.0);
+        let mut player = AnimationPlayer::default();
+        player.play(Handle::default());
+        player.set_elapsed(5.0);
+        assert_eq!(player.loop_progress(&clip), 0.5);
+
+        player.set_elapsed(-0.5);
+        assert_eq!(player.loop_progress(&clip), 0.75);
+        player.set_elapsed(-1e-9);
+        assert_eq!(player.loop_progress(&clip), 0.0);
+
+        assert_eq!(player.loop_progress(&AnimationClip::default()), 0.0);
+    }
+
+    #[test]
+    fn players_share_clock() {
+        let mut app = setup_app();
+        let clock = app.world.spawn(AnimationClock::default()).id();
+        let handle = translation_clip(&mut app, "root");
+        let spawn_bound = |app: &mut App| {
+            let entity = spawn_player(app, "root", handle.clone());
+            app.world
+                .get_mut::<AnimationPlayer>(entity)
+                .unwrap()
+                .repeat()
+                .set_driver(AnimationDriver::Clock(clock));
+            entity
+        };
+        let first = spawn_bound(&mut app);
+        advance_time(&mut app, 0.25);
+        app.update();
+        let others = [spawn_bound(&mut app), spawn_bound(&mut app)];
+
+        for _ in 0..3 {
+            advance_time(&mut app, 0.2);
+            app.update();
+            let elapsed = app.world.get::<AnimationClock>(clock).unwrap().elapsed();
+            for player in [first, others[0], others[1]] {
+                let player = app.world.get::<AnimationPlayer>(player).unwrap();
+                assert_eq!(player.elapsed(), elapsed);
+            }
+        }
+        let elapsed = app.world.get::<AnimationClock>(clock).unwrap().elapsed();
+        assert!((elapsed - 0.85).abs() < 1e-5);
+    }
+
+    #[test]
+    fn value_transform_exaggerates_motion() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let player = spawn_player(&mut app, "root", handle);
+        let path = EntityPath {
+            parts: vec!["root".into()],
+        };
+        app.world
+            .get_mut::<AnimationPlayer>(player)
+            .unwrap()
+            .set_value_transform(
+                path.clone(),
+                ValueTransform::default().with_translation_factor(Vec3::splat(1.5)),
+            );
+        advance_time(&mut app, 0.5);
+        app.update();
+        let translation = app.world.get::<Transform>(player).unwrap().translation;
+        assert!(translation.abs_diff_eq(Vec3::new(0.75, 0.0, 0.0), 1e-5));
+
+        let mut animation_player = app.world.get_mut::<AnimationPlayer>(player).unwrap();
+        assert!(animation_player.value_transform(&path).is_some());
+        animation_player.remove_value_transform(&path);
+        advance_time(&mut app, 0.0);
+        app.update();
+        let translation = app.world.get::<Transform>(player).unwrap().translation;
+        assert!(translation.abs_diff_eq(Vec3::new(0.5, 0.0, 0.0), 1e-5));
+    }
+
+    #[test]
+    fn zero_speed_seeked_to_end_finishes() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let never = spawn_player(&mut app, "root", handle.clone());
+        let count = spawn_player(&mut app, "root", handle.clone());
+        let before_end = spawn_player(&mut app, "root", handle);
+        app.world
+            .get_mut::<AnimationPlayer>(never)
+            .unwrap()
+            .set_speed(0.0)
+            .set_elapsed(1.0);
+        app.world
+            .get_mut::<AnimationPlayer>(count)
+            .unwrap()
+            .set_repeat(RepeatAnimation::Count(2))
+            .set_speed(0.0)
+            .set_elapsed(2.5);
+        app.world
+            .get_mut::<AnimationPlayer>(before_end)
+            .unwrap()
+            .set_repeat(RepeatAnimation::Count(2))
+            .set_speed(0.0)
+            .set_elapsed(1.5);
+        for _ in 0..2 {
+            advance_time(&mut app, 0.25);
+            app.update();
+        }
+
+        let player = app.world.get::<AnimationPlayer>(never).unwrap();
+        assert!(player.is_finished());
+        assert_eq!(player.elapsed(), 1.0);
+        let translation = app.world.get::<Transform>(never).unwrap().translation;
+        assert_eq!(translation, Vec3::X);
+        let player = app.world.get::<AnimationPlayer>(count).unwrap();
+        assert!(player.is_finished());
+        assert_eq!(player.completions(), 2);
+        let player = app.world.get::<AnimationPlayer>(before_end).unwrap();
+        assert!(!player.is_finished());
+        assert_eq!(player.completions(), 1);
+        assert_eq!(player.elapsed(), 1.5);
+    }
+
+    #[test]
+    fn bundle_plays_clip() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let entity = app
+            .world
+            .spawn((
+                Name::new("root"),
+                Transform::default(),
+                AnimationBundle::new(handle.clone()),
+            ))
+            .id();
+        advance_time(&mut app, 0.5);
+        app.update();
+
+        let player = app.world.get::<AnimationPlayer>(entity).unwrap();
+        assert!(player.is_playing_asset(&handle));
+        let translation = app.world.get::<Transform>(entity).unwrap().translation;
+        assert_eq!(translation, Vec3::new(0.5, 0.0, 0.0));
+
+        let other = translation_clip(&mut app, "root");
+        app.world.entity_mut(entity).insert(other.clone());
+        app.update();
+        let player = app.world.get::<AnimationPlayer>(entity).unwrap();
+        assert!(player.is_playing_asset(&other));
+    }
+
+    #[test]
+    fn scale_flip_keeps_magnitude() {
+        let mut curve = VariableCurve::new(
+            vec![0.0, 1.0],
+            Keyframes::Scale(vec![Vec3::ONE, Vec3::new(-1.0, 2.0, 1.0)]),
+        );
+        assert_eq!(curve.sample_scale(0.5), Some(Vec3::new(0.0, 1.5, 1.0)));
+
+        curve.preserve_scale_sign = true;
+        for step in 0..=10 {
+            let scale = curve.sample_scale(step as f32 / 10.0).unwrap();
+            assert!(scale.x.abs() >= 1.0 - 1e-6);
+        }
+        assert_eq!(curve.sample_scale(0.25), Some(Vec3::new(1.0, 1.25, 1.0)));
+        assert_eq!(curve.sample_scale(0.75), Some(Vec3::new(-1.0, 1.75, 1.0)));
+
+        curve.interpolation = Interpolation::Cubic;
+        let scale = curve.sample_scale(0.5).unwrap();
+        assert_eq!(scale.x, -1.0);
+    }
+
+    #[test]
+    fn completion_times_in_range() {
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec!["root".into()],
+            },
+            VariableCurve::new(
+                vec![0.0, 1.0],
+                Keyframes::Translation(vec![Vec3::ZERO, Vec3::X]),
+            ),
+        );
+        assert_eq!(clip.completion_times(0.2, 1.1, 2.0), vec![0.5, 1.0]);
+        assert_eq!(clip.completion_times(0.0, 2.5, -1.0), vec![1.0, 2.0]);
+        assert_eq!(clip.completion_times(0.0, 0.9, 1.0), Vec::<f32>::new());
+        assert!(clip.completion_times(0.0, 10.0, 0.0).is_empty());
+    }
+
+    #[test]
+    fn override_blends_with_animation() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        let entity = spawn_player(&mut app, "root", handle);
+        app.world.entity_mut(entity).insert(AnimationOverride::new(
+            Transform::from_xyz(0.0, 1.0, 0.0),
+            0.5,
+        ));
+        advance_time(&mut app, 0.5);
+        app.update();
+        let translation = app.world.get::<Transform>(entity).unwrap().translation;
+        assert_eq!(translation, Vec3::new(0.25, 0.5, 0.0));
+
+        // A paused animation doesn't sample its pose again, which isn't blended twice
+        app.world
+            .get_mut::<AnimationPlayer>(entity)
+            .unwrap()
+            .pause();
+        advance_time(&mut app, 0.25);
+        app.update();
+        app.update();
+        let translation = app.world.get::<Transform>(entity).unwrap().translation;
+        assert_eq!(translation, Vec3::new(0.25, 0.5, 0.0));
+
+        app.world
+            .get_mut::<AnimationPlayer>(entity)
+            .unwrap()
+            .resume();
+        app.world
+            .get_mut::<AnimationOverride>(entity)
+            .unwrap()
+            .weight = 0.0;
+        advance_time(&mut app, 0.25);
+        app.update();
+        let translation = app.world.get::<Transform>(entity).unwrap().translation;
+        assert_eq!(translation, Vec3::new(0.75, 0.0, 0.0));
+    }
+
+    #[test]
+    fn unloaded_clip_player_is_stuck() {
+        let mut app = setup_app();
+        app.insert_resource(StuckAnimationPlayers::new(3));
+        let handle = translation_clip(&mut app, "root");
+        let playing = spawn_player(&mut app, "root", handle);
+        let unloaded = Handle::weak(HandleId::random::<AnimationClip>());
+        let stuck = spawn_player(&mut app, "root", unloaded);
+        app.world
+            .get_mut::<AnimationPlayer>(playing)
+            .unwrap()
+            .repeat();
+
+        for _ in 0..3 {
+            advance_time(&mut app, 0.1);
+            app.update();
+        }
+        assert!(app
+            .world
+            .resource::<StuckAnimationPlayers>()
+            .stuck()
+            .is_empty());
+        advance_time(&mut app, 0.1);
+        app.update();
+        let detector = app.world.resource::<StuckAnimationPlayers>();
+        assert_eq!(detector.stuck(), &[stuck]);
+        assert!(!detector.is_stuck(playing));
+    }
+
+    #[test]
+    fn clip_default_repeat() {
+        let mut app = setup_app();
+        let handle = translation_clip(&mut app, "root");
+        app.world
+            .resource_mut::<Assets<AnimationClip>>()
+            .get_mut(&handle)
+            .unwrap()
+            .set_default_repeat(Some(RepeatAnimation::Forever));
+        let looping = spawn_player(&mut app, "root", handle.clone());
+        let once = spawn_player(&mut app, "root", handle);
+        app.world
+            .get_mut::<AnimationPlayer>(once)
+            .unwrap()
+            .stop_repeating();
+        for _ in 0..3 {
+            advance_time(&mut app, 0.5);
+            app.update();
+        }
+
+        let player = app.world.get::<AnimationPlayer>(looping).unwrap();
+        assert_eq!(player.repeat_mode(), RepeatAnimation::Forever);
+        assert!(!player.is_finished());
+        assert_eq!(player.elapsed(), 0.5);
+        let player = app.world.get::<AnimationPlayer>(once).unwrap();
+        assert_eq!(player.repeat_mode(), RepeatAnimation::Never);
+        assert!(player.is_finished());
+    }
+
+    #[test]
+    fn evaluate_matches_applied_pose() {
+        let mut app = setup_app();
+        let walk = translation_clip(&mut app, "root");
+        let path = EntityPath {
+            parts: vec!["root".into()],
+        };
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(
+                vec![0.0, 1.0],
+                Keyframes::Translation(vec![Vec3::Y, Vec3::Z]),
+            ),
+        );
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(
+                vec![0.0, 1.0],
+                Keyframes::Rotation(vec![Quat::IDENTITY, Quat::from_rotation_y(1.0)]),
+            ),
+        );
+        let run = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            path.clone(),
+            VariableCurve::new(
+                vec![0.0, 1.0],
+                Keyframes::Scale(vec![Vec3::ONE, Vec3::splat(2.0)]),
+            ),
+        );
+        let breathe = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+        let entity = spawn_player(&mut app, "root", walk);
+        advance_time(&mut app, 0.25);
+        app.update();
+        let mut player = app.world.get_mut::<AnimationPlayer>(entity).unwrap();
+        player.play_with_transition(run, Duration::from_secs(1));
+        player.slot("breathe").play(breathe).set_weight(0.5);
+        advance_time(&mut app, 0.4);
+        app.update();
+
+        let player = app.world.get::<AnimationPlayer>(entity).unwrap();
+        let poses = player.evaluate(app.world.resource::<Assets<AnimationClip>>());
+        let transform = app.world.get::<Transform>(entity).unwrap();
+        assert_eq!(poses.len(), 1);
+        let pose = poses[&path];
+        assert!(pose.translation.abs_diff_eq(transform.translation, 1e-5));
+        assert!(pose.rotation.abs_diff_eq(transform.rotation, 1e-5));
+        assert!(pose.scale.abs_diff_eq(transform.scale, 1e-5));
+        assert!(transform.translation.x > 0.0 && transform.translation.y > 0.0);
+        assert!(transform.scale.x > 1.0);
+    }
+
+    /// Mirrors the `Val` of `bevy_ui`
+    #[derive(Reflect, FromReflect, Clone, Copy, Debug, PartialEq)]
+    enum Val {
+        Auto,
+        Px(f32),
+        Percent(f32),
+    }
+
+    #[derive(Component, Reflect)]
+    struct Style {
+        left: Val,
+    }
+
+    #[test]
+    fn animate_reflected_val_keeps_unit() {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_plugin(AnimationPlugin::default().register_animated::<Style>(
+                |style, curve, time, weight| {
+                    animate_reflected_scalar(&mut style.left, curve, time, weight);
+                },
+            ));
+        let mut time = Time::default();
+        time.update();
+        app.insert_resource(time);
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("panel")],
+            },
+            VariableCurve::new(vec![0.0, 1.0], Keyframes::Scalar(vec![0.0, 100.0])),
+        );
+        let handle = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+        let panel = spawn_player(&mut app, "panel", handle);
+        app.world
+            .entity_mut(panel)
+            .insert(Style { left: Val::Px(0.0) });
+        let left_after = |app: &mut App, secs: f32| {
+            advance_time(app, secs);
+            app.update();
+            app.world.get::<Style>(panel).unwrap().left
+        };
+
+        assert_eq!(left_after(&mut app, 0.0), Val::Px(0.0));
+        assert_eq!(left_after(&mut app, 0.5), Val::Px(50.0));
+        assert_eq!(left_after(&mut app, 0.5), Val::Px(100.0));
+
+        app.world.get_mut::<Style>(panel).unwrap().left = Val::Percent(0.0);
+        app.world
+            .get_mut::<AnimationPlayer>(panel)
+            .unwrap()
+            .set_elapsed(0.25);
+        assert_eq!(left_after(&mut app, 0.0), Val::Percent(25.0));
+
+        app.world.get_mut::<Style>(panel).unwrap().left = Val::Auto;
+        assert_eq!(left_after(&mut app, 0.25), Val::Auto);
+    }
+
+    #[test]
+    fn register_animated_twice() {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_plugin(
+                AnimationPlugin::default()
+                    .register_animated::<MorphWeights>(|weights, _, _, _| weights.0[0] += 1.0)
+                    .register_animated::<MorphWeights>(|weights, _, _, _| weights.0[1] += 1.0)
+                    .register_animated_scalar::<MorphWeights>(|weights, index| {
+                        weights.0.get_mut(index + 2)
+                    })
+                    .register_animated_scalar::<MorphWeights>(|weights, index| {
+                        weights.0.get_mut(index + 3)
+                    }),
+            );
+        let mut time = Time::default();
+        time.update();
+        app.insert_resource(time);
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root")],
+            },
+            VariableCurve {
+                weight: 2.0,
+                clamp_scalar: true,
+                ..VariableCurve::new(vec![0.0, 1.0], Keyframes::Scalar(vec![0.75, 0.75]))
+            },
+        );
+        let handle = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+        let entity = spawn_player(&mut app, "root", handle);
+        app.world
+            .entity_mut(entity)
+            .insert(MorphWeights(vec![0.0; 4]));
+        advance_time(&mut app, 0.1);
+        app.update();
+
+        // Each closure runs once, and both scalar fields are blended then clamped
+        let weights = &app.world.get::<MorphWeights>(entity).unwrap().0;
+        assert_eq!(weights, &[1.0, 1.0, 1.0, 1.0]);
     }
 }
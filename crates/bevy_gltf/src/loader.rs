@@ -155,6 +155,9 @@ async fn load_gltf<'a, 'b>(
         let mut animation_roots = HashSet::default();
         for animation in gltf.animations() {
             let mut animation_clip = bevy_animation::AnimationClip::default();
+            if let Some(extras) = animation.extras() {
+                animation_clip.set_metadata("gltf_extras", extras.get());
+            }
             for channel in animation.channels() {
                 match channel.sampler().interpolation() {
                     gltf::animation::Interpolation::Linear => (),
@@ -207,10 +210,7 @@ async fn load_gltf<'a, 'b>(
                         bevy_animation::EntityPath {
                             parts: path.clone(),
                         },
-                        bevy_animation::VariableCurve {
-                            keyframe_timestamps,
-                            keyframes,
-                        },
+                        bevy_animation::VariableCurve::new(keyframe_timestamps, keyframes),
                     );
                 } else {
                     warn!(
@@ -498,6 +498,26 @@ async fn load_gltf<'a, 'b>(
                         .insert(bevy_animation::AnimationPlayer::default());
                 }
             }
+            // mark the animated nodes of the scene with their path, so they don't have to be
+            // found by name when played
+            for (node_index, (root_index, path)) in &paths {
+                if !animation_roots.contains(root_index) {
+                    continue;
+                }
+                if let (Some(&entity), Some(&root)) = (
+                    node_index_to_entity_map.get(node_index),
+                    node_index_to_entity_map.get(root_index),
+                ) {
+                    world
+                        .entity_mut(entity)
+                        .insert(bevy_animation::AnimationTarget {
+                            root,
+                            path: bevy_animation::EntityPath {
+                                parts: path.clone(),
+                            },
+                        });
+                }
+            }
         }
 
         for (&entity, &skin_index) in &entity_to_skin_index_map {
@@ -39,9 +39,9 @@ fn setup(
         EntityPath {
             parts: vec![planet.clone()],
         },
-        VariableCurve {
-            keyframe_timestamps: vec![0.0, 1.0, 2.0, 3.0, 4.0],
-            keyframes: Keyframes::Translation(vec![
+        VariableCurve::new(
+            vec![0.0, 1.0, 2.0, 3.0, 4.0],
+            Keyframes::Translation(vec![
                 Vec3::new(1.0, 0.0, 1.0),
                 Vec3::new(-1.0, 0.0, 1.0),
                 Vec3::new(-1.0, 0.0, -1.0),
@@ -50,7 +50,7 @@ fn setup(
                 // be the same as the first one
                 Vec3::new(1.0, 0.0, 1.0),
             ]),
-        },
+        ),
     );
     // Or it can modify the rotation of the transform.
     // To find the entity to modify, the hierarchy  will be traversed looking for
@@ -59,16 +59,16 @@ fn setup(
         EntityPath {
             parts: vec![planet.clone(), orbit_controller.clone()],
         },
-        VariableCurve {
-            keyframe_timestamps: vec![0.0, 1.0, 2.0, 3.0, 4.0],
-            keyframes: Keyframes::Rotation(vec![
+        VariableCurve::new(
+            vec![0.0, 1.0, 2.0, 3.0, 4.0],
+            Keyframes::Rotation(vec![
                 Quat::IDENTITY,
                 Quat::from_axis_angle(Vec3::Y, PI / 2.),
                 Quat::from_axis_angle(Vec3::Y, PI / 2. * 2.),
                 Quat::from_axis_angle(Vec3::Y, PI / 2. * 3.),
                 Quat::IDENTITY,
             ]),
-        },
+        ),
     );
     // If a curve in an animation is shorter than the other, it will not repeat
     // until all other curves are finished. In that case, another animation should
@@ -77,9 +77,9 @@ fn setup(
         EntityPath {
             parts: vec![planet.clone(), orbit_controller.clone(), satellite.clone()],
         },
-        VariableCurve {
-            keyframe_timestamps: vec![0.0, 0.5, 1.0, 1.5, 2.0, 2.5, 3.0, 3.5, 4.0],
-            keyframes: Keyframes::Scale(vec![
+        VariableCurve::new(
+            vec![0.0, 0.5, 1.0, 1.5, 2.0, 2.5, 3.0, 3.5, 4.0],
+            Keyframes::Scale(vec![
                 Vec3::splat(0.8),
                 Vec3::splat(1.2),
                 Vec3::splat(0.8),
@@ -90,23 +90,23 @@ fn setup(
                 Vec3::splat(1.2),
                 Vec3::splat(0.8),
             ]),
-        },
+        ),
     );
     // There can be more than one curve targeting the same entity path
     animation.add_curve_to_path(
         EntityPath {
             parts: vec![planet.clone(), orbit_controller.clone(), satellite.clone()],
         },
-        VariableCurve {
-            keyframe_timestamps: vec![0.0, 1.0, 2.0, 3.0, 4.0],
-            keyframes: Keyframes::Rotation(vec![
+        VariableCurve::new(
+            vec![0.0, 1.0, 2.0, 3.0, 4.0],
+            Keyframes::Rotation(vec![
                 Quat::IDENTITY,
                 Quat::from_axis_angle(Vec3::Y, PI / 2.),
                 Quat::from_axis_angle(Vec3::Y, PI / 2. * 2.),
                 Quat::from_axis_angle(Vec3::Y, PI / 2. * 3.),
                 Quat::IDENTITY,
             ]),
-        },
+        ),
     );
 
     // Create the animation player, and set it to repeat